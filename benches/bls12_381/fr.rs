@@ -223,6 +223,40 @@ fn bench_fr_inverse(b: &mut ::test::Bencher) {
     });
 }
 
+#[bench]
+fn bench_fr_inverse_ct(b: &mut ::test::Bencher) {
+    const SAMPLES: usize = 1000;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let v: Vec<Fr> = (0..SAMPLES).map(|_| Fr::random(&mut rng)).collect();
+
+    let mut count = 0;
+    b.iter(|| {
+        count = (count + 1) % SAMPLES;
+        v[count].inverse_ct()
+    });
+}
+
+#[bench]
+fn bench_fr_inverse_vartime(b: &mut ::test::Bencher) {
+    const SAMPLES: usize = 1000;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let v: Vec<Fr> = (0..SAMPLES).map(|_| Fr::random(&mut rng)).collect();
+
+    let mut count = 0;
+    b.iter(|| {
+        count = (count + 1) % SAMPLES;
+        v[count].inverse_vartime()
+    });
+}
+
 #[bench]
 fn bench_fr_negate(b: &mut ::test::Bencher) {
     const SAMPLES: usize = 1000;
@@ -1,6 +1,7 @@
+// Requires nightly: `cargo +nightly bench --bench pairing_benches`.
 #![feature(test)]
 
-extern crate ff;
+extern crate ff_zeroize as ff;
 extern crate pairing_plus as pairing;
 extern crate rand_core;
 extern crate rand_xorshift;
@@ -0,0 +1,122 @@
+/*!
+ This module implements batch inversion of field elements with Montgomery's
+ trick, which replaces `n` inversions by a single inversion and `3(n - 1)`
+ multiplications.
+
+ Zero elements have no inverse; they are skipped and left as zero, so that
+ callers need not filter their inputs. With the `parallel` feature,
+ `parallel_batch_inverse` splits large inputs across threads, at the cost of
+ one inversion per thread.
+*/
+
+use alloc::vec::Vec;
+use ff::Field;
+
+/// Replaces every nonzero element of `v` by its inverse. Zero elements are left unchanged.
+pub fn batch_inverse<F: Field>(v: &mut [F]) {
+    // Montgomery’s Trick and Fast Implementation of Masked AES
+    // Genelle, Prouff and Quisquater
+    // Section 3.2
+
+    // First pass: compute [a, ab, abc, ...]
+    let mut prod = Vec::with_capacity(v.len());
+    let mut acc = F::one();
+    for f in v.iter().filter(|f| !f.is_zero()) {
+        acc.mul_assign(f);
+        prod.push(acc);
+    }
+
+    // Invert `acc`; it is nonzero since it is a product of nonzero elements.
+    acc = acc.inverse().unwrap();
+
+    // Second pass: iterate backwards to compute inverses
+    for (f, s) in v
+        .iter_mut()
+        // Backwards
+        .rev()
+        // Ignore zero elements
+        .filter(|f| !f.is_zero())
+        // Backwards, skip last element, fill in one for last term.
+        .zip(prod.into_iter().rev().skip(1).chain(Some(F::one())))
+    {
+        // acc := acc * f; f := acc * s = 1/f
+        let mut newacc = acc;
+        newacc.mul_assign(f);
+        *f = acc;
+        f.mul_assign(&s);
+        acc = newacc;
+    }
+}
+
+/// Same as `batch_inverse`, but splits `v` into one chunk per available CPU
+/// and inverts the chunks on separate threads.
+#[cfg(feature = "parallel")]
+pub fn parallel_batch_inverse<F: Field>(v: &mut [F]) {
+    let cpus = ::std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    // below this many elements per thread, spawning costs more than it saves
+    const MIN_CHUNK: usize = 64;
+    let chunk_size = ::core::cmp::max(MIN_CHUNK, v.len().div_ceil(cpus));
+
+    if v.len() <= chunk_size {
+        batch_inverse(v);
+    } else {
+        ::std::thread::scope(|scope| {
+            for chunk in v.chunks_mut(chunk_size) {
+                scope.spawn(move || batch_inverse(chunk));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::{Fq, Fq2, Fr};
+    use rand_core::SeedableRng;
+
+    fn batch_inverse_tests<F: Field>(invert: fn(&mut [F])) {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // empty input
+        invert(&mut []);
+
+        for &n in &[1, 2, 10, 1000] {
+            let mut v: Vec<F> = (0..n).map(|_| F::random(&mut rng)).collect();
+            // sprinkle in some zeros, including at both ends
+            v[0] = F::zero();
+            v[n / 2] = F::zero();
+            v[n - 1] = F::zero();
+
+            let expected: Vec<F> = v
+                .iter()
+                .map(|f| f.inverse().unwrap_or_else(F::zero))
+                .collect();
+            invert(&mut v);
+            assert_eq!(v, expected);
+        }
+
+        // all zeros
+        let mut v = vec![F::zero(); 5];
+        invert(&mut v);
+        assert!(v.iter().all(|f| f.is_zero()));
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        batch_inverse_tests::<Fr>(batch_inverse);
+        batch_inverse_tests::<Fq>(batch_inverse);
+        batch_inverse_tests::<Fq2>(batch_inverse);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_batch_inverse() {
+        batch_inverse_tests::<Fr>(parallel_batch_inverse);
+        batch_inverse_tests::<Fq2>(parallel_batch_inverse);
+    }
+}
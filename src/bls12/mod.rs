@@ -0,0 +1,477 @@
+/*!
+ A generic engine for the BLS12 family of pairing-friendly curves.
+
+ A BLS12 curve is determined by its parameter x: the base field has
+ characteristic q = (x - 1)^2 (x^4 - x^2 + 1) / 3 + x, and the groups have
+ prime order r = x^4 - x^2 + 1. A concrete curve implements `Bls12Parameters`
+ to supply its field tower, the curves hosting G1 and G2, the type of the
+ sextic twist and x; `Bls12<P>` then provides the optimal ate pairing.
+*/
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use ff::{BitIterator, Field, PrimeField, ScalarEngine, SqrtField};
+use {CurveAffine, CurveProjective, Engine};
+
+/// The type of the sextic twist E' hosting G2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwistType {
+    /// E': y^2 = x^3 + b * xi, where Fq12 = Fq2[w]/(w^6 - xi)
+    M,
+    /// E': y^2 = x^3 + b / xi, where Fq12 = Fq2[w]/(w^6 - xi)
+    D,
+}
+
+/// Operations on the quadratic extension hosting G2.
+pub trait Fq2Ops<Fq>: SqrtField {
+    /// Multiply this element by an element of the base field.
+    fn mul_by_fq(&mut self, other: &Fq);
+}
+
+/// Operations on the degree 12 extension hosting the target group.
+pub trait Fq12Ops<Fq2>: Field {
+    /// Raise this element to the power q^6, i.e., invert an element of the
+    /// cyclotomic subgroup.
+    fn conjugate(&mut self);
+
+    /// Multiply by the sparse element c0 + c1 * v + c4 * v * w.
+    fn mul_by_014(&mut self, c0: &Fq2, c1: &Fq2, c4: &Fq2);
+
+    /// Multiply by the sparse element c0 + c3 * w + c4 * v * w.
+    fn mul_by_034(&mut self, c0: &Fq2, c3: &Fq2, c4: &Fq2);
+}
+
+/// The constants and types defining a BLS12 curve.
+pub trait Bls12Parameters: 'static + Sized + Clone + fmt::Debug + Send + Sync {
+    /// The absolute value of the BLS parameter x
+    const X: u64;
+    /// Whether the BLS parameter x is negative
+    const X_IS_NEGATIVE: bool;
+    /// The type of the twist hosting G2
+    const TWIST_TYPE: TwistType;
+
+    /// The scalar field of G1 and G2.
+    type Fr: PrimeField + SqrtField;
+    /// The base field that hosts G1.
+    type Fq: PrimeField + SqrtField;
+    /// The quadratic extension that hosts G2.
+    type Fq2: Fq2Ops<Self::Fq>;
+    /// The degree 12 extension that hosts the target group.
+    type Fq12: Fq12Ops<Self::Fq2>;
+
+    /// The projective representation of an element in G1.
+    type G1: CurveProjective<
+            Engine = Bls12<Self>,
+            Base = Self::Fq,
+            Scalar = Self::Fr,
+            Affine = Self::G1Affine,
+        > + From<Self::G1Affine>;
+
+    /// The affine representation of an element in G1.
+    type G1Affine: CurveAffine<
+            Engine = Bls12<Self>,
+            Base = Self::Fq,
+            Scalar = Self::Fr,
+            Projective = Self::G1,
+            Prepared = G1Prepared<Self>,
+            Pair = Self::G2Affine,
+            PairingResult = Self::Fq12,
+        > + From<Self::G1>;
+
+    /// The projective representation of an element in G2.
+    type G2: CurveProjective<
+            Engine = Bls12<Self>,
+            Base = Self::Fq2,
+            Scalar = Self::Fr,
+            Affine = Self::G2Affine,
+        > + From<Self::G2Affine>;
+
+    /// The affine representation of an element in G2.
+    type G2Affine: CurveAffine<
+            Engine = Bls12<Self>,
+            Base = Self::Fq2,
+            Scalar = Self::Fr,
+            Projective = Self::G2,
+            Prepared = G2Prepared<Self>,
+            Pair = Self::G1Affine,
+            PairingResult = Self::Fq12,
+        > + From<Self::G2>;
+}
+
+/// The optimal ate pairing on the BLS12 curve described by `P`.
+#[derive(Clone, Debug)]
+pub struct Bls12<P: Bls12Parameters>(PhantomData<P>);
+
+/// A G1 element prepared for the Miller loop.
+#[derive(Clone, Debug)]
+pub struct G1Prepared<P: Bls12Parameters>(pub(crate) P::G1Affine);
+
+impl<P: Bls12Parameters> G1Prepared<P> {
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn from_affine(p: P::G1Affine) -> Self {
+        G1Prepared(p)
+    }
+}
+
+/// A G2 element prepared for the Miller loop: the coefficients of the line
+/// functions for each of its doubling and addition steps, along with the
+/// element itself.
+#[derive(Clone, Debug)]
+pub struct G2Prepared<P: Bls12Parameters> {
+    pub(crate) coeffs: Vec<(P::Fq2, P::Fq2, P::Fq2)>,
+    pub(crate) point: P::G2Affine,
+}
+
+impl<P: Bls12Parameters> ScalarEngine for Bls12<P> {
+    type Fr = P::Fr;
+}
+
+impl<P: Bls12Parameters> Engine for Bls12<P> {
+    type G1 = P::G1;
+    type G1Affine = P::G1Affine;
+    type G2 = P::G2;
+    type G2Affine = P::G2Affine;
+    type Fq = P::Fq;
+    type Fqe = P::Fq2;
+    type Fqk = P::Fq12;
+
+    fn miller_loop<'a, I>(i: I) -> Self::Fqk
+    where
+        I: IntoIterator<
+            Item = &'a (
+                &'a <Self::G1Affine as CurveAffine>::Prepared,
+                &'a <Self::G2Affine as CurveAffine>::Prepared,
+            ),
+        >,
+    {
+        let mut pairs = vec![];
+        for &(p, q) in i {
+            if !p.is_zero() && !q.is_zero() {
+                pairs.push((p, q.coeffs.iter()));
+            }
+        }
+
+        // Twisting isomorphism from E to E'
+        fn ell<P: Bls12Parameters>(
+            f: &mut P::Fq12,
+            coeffs: &(P::Fq2, P::Fq2, P::Fq2),
+            p: &P::G1Affine,
+        ) {
+            let (px, py) = p.as_tuple();
+            let mut c0 = coeffs.0;
+            let mut c1 = coeffs.1;
+
+            c0.mul_by_fq(py);
+            c1.mul_by_fq(px);
+
+            // Sparse multiplication in Fq12
+            match P::TWIST_TYPE {
+                TwistType::M => f.mul_by_014(&coeffs.2, &c1, &c0),
+                TwistType::D => f.mul_by_034(&c0, &c1, &coeffs.2),
+            }
+        }
+
+        let mut f = P::Fq12::one();
+
+        let mut found_one = false;
+        for i in BitIterator::new([P::X]) {
+            if !found_one {
+                found_one = i;
+                continue;
+            }
+
+            f.square();
+
+            for &mut (p, ref mut coeffs) in &mut pairs {
+                ell::<P>(&mut f, coeffs.next().unwrap(), &p.0);
+            }
+
+            if i {
+                for &mut (p, ref mut coeffs) in &mut pairs {
+                    ell::<P>(&mut f, coeffs.next().unwrap(), &p.0);
+                }
+            }
+        }
+
+        if P::X_IS_NEGATIVE {
+            f.conjugate();
+        }
+
+        f
+    }
+
+    fn final_exponentiation(r: &P::Fq12) -> Option<P::Fq12> {
+        let mut f1 = *r;
+        f1.conjugate();
+
+        match r.inverse() {
+            Some(mut f2) => {
+                // Easy part: r^((q^6 - 1) * (q^2 + 1))
+                let mut r = f1;
+                r.mul_assign(&f2);
+                f2 = r;
+                r.frobenius_map(2);
+                r.mul_assign(&f2);
+
+                fn exp_by_x<P: Bls12Parameters>(f: &mut P::Fq12) {
+                    *f = f.pow([P::X]);
+                    if P::X_IS_NEGATIVE {
+                        f.conjugate();
+                    }
+                }
+
+                // Hard part, following Hayashida, Hayasaka and Teruya,
+                // https://eprint.iacr.org/2020/875. This raises r to
+                //     3 * (q^4 - q^2 + 1) / (x^4 - x^2 + 1)
+                //         = (x - 1)^2 * (x + q) * (x^2 + q^2 - 1) + 3,
+                // i.e., the result is the cube of the reduced pairing. As 3 does not
+                // divide the group order, this is still a non-degenerate bilinear pairing.
+
+                // t0 = r^((x - 1)^2); in the cyclotomic subgroup, conjugation is inversion
+                let mut rinv = r;
+                rinv.conjugate();
+                let mut t0 = r;
+                exp_by_x::<P>(&mut t0);
+                t0.mul_assign(&rinv);
+                let mut t0inv = t0;
+                t0inv.conjugate();
+                exp_by_x::<P>(&mut t0);
+                t0.mul_assign(&t0inv);
+
+                // t1 = t0^(x + q)
+                let mut t1 = t0;
+                exp_by_x::<P>(&mut t1);
+                t0.frobenius_map(1);
+                t1.mul_assign(&t0);
+
+                // t2 = t1^(x^2 + q^2 - 1)
+                let mut t2 = t1;
+                exp_by_x::<P>(&mut t2);
+                exp_by_x::<P>(&mut t2);
+                let mut t1inv = t1;
+                t1inv.conjugate();
+                t1.frobenius_map(2);
+                t2.mul_assign(&t1);
+                t2.mul_assign(&t1inv);
+
+                // t2 * r^3
+                let mut r3 = r;
+                r3.square();
+                r3.mul_assign(&r);
+                t2.mul_assign(&r3);
+
+                Some(t2)
+            }
+            None => None,
+        }
+    }
+}
+
+/// A point of E' in Jacobian coordinates, used while computing the line functions.
+struct G2Jacobian<F: Field> {
+    x: F,
+    y: F,
+    z: F,
+}
+
+impl<P: Bls12Parameters> G2Prepared<P> {
+    pub fn is_zero(&self) -> bool {
+        self.point.is_zero()
+    }
+
+    pub fn from_affine(q: P::G2Affine) -> Self {
+        if q.is_zero() {
+            return G2Prepared {
+                coeffs: vec![],
+                point: q,
+            };
+        }
+
+        fn doubling_step<F: Field>(r: &mut G2Jacobian<F>) -> (F, F, F) {
+            // Adaptation of Algorithm 26, https://eprint.iacr.org/2010/354.pdf
+            let mut tmp0 = r.x;
+            tmp0.square();
+
+            let mut tmp1 = r.y;
+            tmp1.square();
+
+            let mut tmp2 = tmp1;
+            tmp2.square();
+
+            let mut tmp3 = tmp1;
+            tmp3.add_assign(&r.x);
+            tmp3.square();
+            tmp3.sub_assign(&tmp0);
+            tmp3.sub_assign(&tmp2);
+            tmp3.double();
+
+            let mut tmp4 = tmp0;
+            tmp4.double();
+            tmp4.add_assign(&tmp0);
+
+            let mut tmp6 = r.x;
+            tmp6.add_assign(&tmp4);
+
+            let mut tmp5 = tmp4;
+            tmp5.square();
+
+            let mut zsquared = r.z;
+            zsquared.square();
+
+            r.x = tmp5;
+            r.x.sub_assign(&tmp3);
+            r.x.sub_assign(&tmp3);
+
+            r.z.add_assign(&r.y);
+            r.z.square();
+            r.z.sub_assign(&tmp1);
+            r.z.sub_assign(&zsquared);
+
+            r.y = tmp3;
+            r.y.sub_assign(&r.x);
+            r.y.mul_assign(&tmp4);
+
+            tmp2.double();
+            tmp2.double();
+            tmp2.double();
+
+            r.y.sub_assign(&tmp2);
+
+            tmp3 = tmp4;
+            tmp3.mul_assign(&zsquared);
+            tmp3.double();
+            tmp3.negate();
+
+            tmp6.square();
+            tmp6.sub_assign(&tmp0);
+            tmp6.sub_assign(&tmp5);
+
+            tmp1.double();
+            tmp1.double();
+
+            tmp6.sub_assign(&tmp1);
+
+            tmp0 = r.z;
+            tmp0.mul_assign(&zsquared);
+            tmp0.double();
+
+            (tmp0, tmp3, tmp6)
+        }
+
+        fn addition_step<F: Field>(r: &mut G2Jacobian<F>, qx: &F, qy: &F) -> (F, F, F) {
+            // Adaptation of Algorithm 27, https://eprint.iacr.org/2010/354.pdf
+            let mut zsquared = r.z;
+            zsquared.square();
+
+            let mut ysquared = *qy;
+            ysquared.square();
+
+            let mut t0 = zsquared;
+            t0.mul_assign(qx);
+
+            let mut t1 = *qy;
+            t1.add_assign(&r.z);
+            t1.square();
+            t1.sub_assign(&ysquared);
+            t1.sub_assign(&zsquared);
+            t1.mul_assign(&zsquared);
+
+            let mut t2 = t0;
+            t2.sub_assign(&r.x);
+
+            let mut t3 = t2;
+            t3.square();
+
+            let mut t4 = t3;
+            t4.double();
+            t4.double();
+
+            let mut t5 = t4;
+            t5.mul_assign(&t2);
+
+            let mut t6 = t1;
+            t6.sub_assign(&r.y);
+            t6.sub_assign(&r.y);
+
+            let mut t9 = t6;
+            t9.mul_assign(qx);
+
+            let mut t7 = t4;
+            t7.mul_assign(&r.x);
+
+            r.x = t6;
+            r.x.square();
+            r.x.sub_assign(&t5);
+            r.x.sub_assign(&t7);
+            r.x.sub_assign(&t7);
+
+            r.z.add_assign(&t2);
+            r.z.square();
+            r.z.sub_assign(&zsquared);
+            r.z.sub_assign(&t3);
+
+            let mut t10 = *qy;
+            t10.add_assign(&r.z);
+
+            let mut t8 = t7;
+            t8.sub_assign(&r.x);
+            t8.mul_assign(&t6);
+
+            t0 = r.y;
+            t0.mul_assign(&t5);
+            t0.double();
+
+            r.y = t8;
+            r.y.sub_assign(&t0);
+
+            t10.square();
+            t10.sub_assign(&ysquared);
+
+            let mut ztsquared = r.z;
+            ztsquared.square();
+
+            t10.sub_assign(&ztsquared);
+
+            t9.double();
+            t9.sub_assign(&t10);
+
+            t10 = r.z;
+            t10.double();
+
+            t6.negate();
+
+            t1 = t6;
+            t1.double();
+
+            (t10, t1, t9)
+        }
+
+        let mut coeffs = vec![];
+        let (qx, qy) = q.as_tuple();
+        let mut r = G2Jacobian {
+            x: *qx,
+            y: *qy,
+            z: P::Fq2::one(),
+        };
+
+        let mut found_one = false;
+        for i in BitIterator::new([P::X]) {
+            if !found_one {
+                found_one = i;
+                continue;
+            }
+
+            coeffs.push(doubling_step(&mut r));
+
+            if i {
+                coeffs.push(addition_step(&mut r, qx, qy));
+            }
+        }
+
+        G2Prepared { coeffs, point: q }
+    }
+}
@@ -0,0 +1,84 @@
+/*!
+Cofactor clearing for G1 and G2.
+*/
+
+// As for BLS12-381, we multiply by the effective cofactors h_eff of section 7
+// of https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-07: x - 1 for
+// G1, and 3 * (x^2 - 1) * h2 for G2, which the Budroni-Pintore method computes
+// with the endomorphism. Here x = 0x8508c00000000001 is the BLS parameter.
+
+use bls12_377::{G1, G2};
+use ff::BitIterator;
+use osswu_map::ClearH;
+use CurveProjective;
+
+impl ClearH for G1 {
+    fn clear_h(&mut self) {
+        let h_eff = BitIterator::new([0x8508c00000000000]);
+        *self = self.into_affine().mul_bits(h_eff);
+    }
+}
+
+impl ClearH for G2 {
+    fn clear_h(&mut self) {
+        let h_eff = BitIterator::new([
+            0x1e34800000000000,
+            0xcf664765b0000003,
+            0x8e8e73ad8a538800,
+            0x78ba279637388559,
+            0xb85860aaaad29276,
+            0xf7ee7c4b03103b45,
+            0x8f6ade35a5c7d769,
+            0xa951764c46f4edd2,
+            0x53648d3d9502abfb,
+            0x001f60243677e306,
+        ]);
+        *self = self.into_affine().mul_bits(h_eff);
+    }
+}
+
+/// Tests for cofactor clearing
+#[cfg(test)]
+mod tests {
+    use super::ClearH;
+    use bls12_377::{FrRepr, G1, G2};
+    use rand_core::SeedableRng;
+    use CurveProjective;
+
+    #[test]
+    fn test_clear_h() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        // on the subgroup, clearing the cofactor is multiplication by x - 1
+        for _ in 0..32 {
+            let mut input = G1::random(&mut rng);
+            let mut result = input;
+            result.clear_h();
+            input.mul_assign(FrRepr([0x8508c00000000000, 0x0, 0x0, 0x0]));
+            assert_eq!(result, input);
+        }
+    }
+
+    #[test]
+    fn test_clear_h2() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        // on the subgroup, clearing the cofactor is multiplication by h_eff mod r
+        for _ in 0..32 {
+            let mut input = G2::random(&mut rng);
+            let mut result = input;
+            result.clear_h();
+            input.mul_assign(FrRepr([
+                0x1e34800000000001,
+                0x14885f3240000003,
+                0x0000000000000001,
+                0x0000000000000000,
+            ]));
+            assert_eq!(result, input);
+        }
+    }
+}
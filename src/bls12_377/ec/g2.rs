@@ -0,0 +1,372 @@
+use super::super::{Bls12_377, Bls12_377Parameters, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
+use super::g1::G1Affine;
+use core::fmt;
+use encoding_macros::{repr_from_be, repr_to_be};
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, SubgroupCheck};
+
+curve_impl!(
+    "G2",
+    G2,
+    G2Affine,
+    G2Prepared,
+    Fq2,
+    Fr,
+    G2Uncompressed,
+    G2Compressed,
+    G1Affine,
+    Bls12_377,
+    Fq12
+);
+
+group_bytes_impl!(G2, G2Affine, G2Compressed, 96, G2Uncompressed, 192);
+
+#[derive(Copy, Clone)]
+pub struct G2Uncompressed([u8; 192]);
+
+impl AsRef<[u8]> for G2Uncompressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G2Uncompressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G2Uncompressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G2Uncompressed {
+    type Affine = G2Affine;
+
+    fn empty() -> Self {
+        G2Uncompressed([0; 192])
+    }
+    fn size() -> usize {
+        192
+    }
+    fn into_affine(&self) -> Result<G2Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        if !affine.is_on_curve() {
+            Err(GroupDecodingError::NotOnCurve)
+        } else if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G2Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 7) != 0 {
+            // Distinguisher bit is set, but this should be uncompressed!
+            return Err(GroupDecodingError::UnexpectedCompressionMode);
+        }
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G2Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            if copy[0] & (1 << 5) != 0 {
+                // The bit indicating the y-coordinate should be lexicographically
+                // largest is set, but this is an uncompressed element.
+                return Err(GroupDecodingError::UnexpectedInformation);
+            }
+
+            // Unset the three most significant bits.
+            copy[0] &= 0x1f;
+
+            let x_c1: FqRepr = repr_from_be(&copy[..48]);
+            let x_c0: FqRepr = repr_from_be(&copy[48..96]);
+            let y_c1: FqRepr = repr_from_be(&copy[96..144]);
+            let y_c0: FqRepr = repr_from_be(&copy[144..]);
+
+            Ok(G2Affine {
+                x: Fq2 {
+                    c0: Fq::from_repr(x_c0).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("x coordinate (c0)", e)
+                    })?,
+                    c1: Fq::from_repr(x_c1).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("x coordinate (c1)", e)
+                    })?,
+                },
+                y: Fq2 {
+                    c0: Fq::from_repr(y_c0).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("y coordinate (c0)", e)
+                    })?,
+                    c1: Fq::from_repr(y_c1).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("y coordinate (c1)", e)
+                    })?,
+                },
+                infinity: false,
+            })
+        }
+    }
+    fn from_affine(affine: G2Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..48]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[48..96]);
+            repr_to_be(&affine.y.c1.into_repr(), &mut res.0[96..144]);
+            repr_to_be(&affine.y.c0.into_repr(), &mut res.0[144..]);
+        }
+
+        res
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct G2Compressed([u8; 96]);
+
+impl AsRef<[u8]> for G2Compressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G2Compressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G2Compressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G2Compressed {
+    type Affine = G2Affine;
+
+    fn empty() -> Self {
+        G2Compressed([0; 96])
+    }
+    fn size() -> usize {
+        96
+    }
+    fn into_affine(&self) -> Result<G2Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        // NB: Decompression guarantees that it is on the curve already.
+
+        if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G2Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 7) == 0 {
+            // Distinguisher bit isn't set.
+            return Err(GroupDecodingError::UnexpectedCompressionMode);
+        }
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G2Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            // Determine if the intended y coordinate must be greater
+            // lexicographically.
+            let greatest = copy[0] & (1 << 5) != 0;
+
+            // Unset the three most significant bits.
+            copy[0] &= 0x1f;
+
+            let x_c1: FqRepr = repr_from_be(&copy[..48]);
+            let x_c0: FqRepr = repr_from_be(&copy[48..]);
+
+            // Interpret as Fq element.
+            let x = Fq2 {
+                c0: Fq::from_repr(x_c0).map_err(|e| {
+                    GroupDecodingError::CoordinateDecodingError("x coordinate (c0)", e)
+                })?,
+                c1: Fq::from_repr(x_c1).map_err(|e| {
+                    GroupDecodingError::CoordinateDecodingError("x coordinate (c1)", e)
+                })?,
+            };
+
+            G2Affine::get_point_from_x(x, greatest).ok_or(GroupDecodingError::NotOnCurve)
+        }
+    }
+    fn from_affine(affine: G2Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..48]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[48..]);
+
+            let mut negy = affine.y;
+            negy.negate();
+
+            // Set the third most significant bit if the correct y-coordinate
+            // is lexicographically largest.
+            if affine.y > negy {
+                res.0[0] |= 1 << 5;
+            }
+        }
+
+        // Set highest bit to distinguish this as a compressed element.
+        res.0[0] |= 1 << 7;
+
+        res
+    }
+}
+
+impl G2Affine {
+    fn get_generator() -> Self {
+        G2Affine {
+            x: Fq2 {
+                c0: super::super::fq::G2_GENERATOR_X_C0,
+                c1: super::super::fq::G2_GENERATOR_X_C1,
+            },
+            y: Fq2 {
+                c0: super::super::fq::G2_GENERATOR_Y_C0,
+                c1: super::super::fq::G2_GENERATOR_Y_C1,
+            },
+            infinity: false,
+        }
+    }
+
+    fn get_coeff_b() -> Fq2 {
+        super::super::fq::B_COEFF_FQ2
+    }
+
+    pub(crate) fn scale_by_cofactor(&self) -> G2 {
+        // G2 cofactor = (x^8 - 4 x^7 + 5 x^6 - 4 x^4 + 6 x^3 - 4 x^2 - 4 x + 13) / 9
+        // 0x26ba558ae9562addd88d99a6f6a829fbb36b00e1dcc40c8c505634fae2e189d693e8c36676bd09a0f3622fba094800452217cc900000000000000000000001
+        let cofactor = BitIterator::new([
+            0x0000000000000001,
+            0x452217cc90000000,
+            0xa0f3622fba094800,
+            0xd693e8c36676bd09,
+            0x8c505634fae2e189,
+            0xfbb36b00e1dcc40c,
+            0xddd88d99a6f6a829,
+            0x0026ba558ae9562a,
+        ]);
+        self.mul_bits(cofactor)
+    }
+
+    fn perform_pairing(&self, other: &G1Affine) -> Fq12 {
+        super::super::Bls12_377::pairing(*other, *self)
+    }
+}
+
+impl G2 {
+    fn empirical_recommended_wnaf_for_scalar(scalar: FrRepr) -> usize {
+        let num_bits = scalar.num_bits() as usize;
+
+        if num_bits >= 103 {
+            4
+        } else if num_bits >= 37 {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn empirical_recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize {
+        const RECOMMENDATIONS: [usize; 11] = [1, 3, 8, 20, 47, 126, 260, 826, 1501, 4555, 84071];
+
+        let mut ret = 4;
+        for r in &RECOMMENDATIONS {
+            if num_scalars > *r {
+                ret += 1;
+            } else {
+                break;
+            }
+        }
+
+        ret
+    }
+}
+
+pub type G2Prepared = ::bls12::G2Prepared<Bls12_377Parameters>;
+
+mod subgroup_check {
+    use super::G2Affine;
+    #[cfg(test)]
+    use rand_core::SeedableRng;
+    #[cfg(test)]
+    use CurveAffine;
+    use SubgroupCheck;
+
+    impl SubgroupCheck for G2Affine {
+        fn in_subgroup(&self) -> bool {
+            self.is_on_curve() && self.is_in_correct_subgroup_assuming_on_curve()
+        }
+    }
+
+    #[test]
+    fn test_g2_subgroup_check() {
+        use bls12_377::G2;
+        use osswu_map::ClearH;
+        use CurveProjective;
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..32 {
+            let p = G2::random(&mut rng).into_affine();
+            assert_eq!(
+                p.in_subgroup(),
+                p.is_in_correct_subgroup_assuming_on_curve()
+            );
+
+            let mut pp = p.into_projective();
+            pp.clear_h();
+            let p = pp.into_affine();
+            assert!(p.in_subgroup() && p.is_in_correct_subgroup_assuming_on_curve());
+        }
+    }
+}
+
+#[test]
+fn g2_generator() {
+    let g = G2Affine::one();
+    assert!(g.is_on_curve());
+    assert!(g.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn g2_curve_tests() {
+    ::tests::curve::curve_tests::<G2>();
+}
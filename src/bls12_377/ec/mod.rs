@@ -0,0 +1,15 @@
+pub mod g1;
+pub mod g2;
+
+pub use self::g1::*;
+pub use self::g2::*;
+
+#[test]
+fn test_group_defaults() {
+    use CurveAffine;
+    use CurveProjective;
+    assert_eq!(G1::default(), G1::zero());
+    assert_eq!(G2::default(), G2::zero());
+    assert_eq!(G1Affine::default(), G1Affine::zero());
+    assert_eq!(G2Affine::default(), G2Affine::zero());
+}
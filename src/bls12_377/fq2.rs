@@ -0,0 +1,356 @@
+use super::fq::{Fq, FROBENIUS_COEFF_FQ2_C1};
+use bls12::Fq2Ops;
+use core::cmp::Ordering;
+use digest::generic_array::{
+    typenum::{U128, U64},
+    GenericArray,
+};
+use ff::{Field, LegendreSymbol, SqrtField};
+use hash_to_field::{BaseFromRO, FromRO};
+use signum::{Sgn0Result, Signum0};
+
+/// An element of Fq2, represented by c0 + c1 * u, where u^2 = -5.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Zeroize)]
+pub struct Fq2 {
+    pub c0: Fq,
+    pub c1: Fq,
+}
+
+extension_field_bytes_impl!(Fq2, 96, Fq, 48, c0, c1);
+
+impl ::core::fmt::Display for Fq2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "Fq2({} + {} * u)", self.c0, self.c1)
+    }
+}
+
+/// `Fq2` elements are ordered lexicographically.
+impl Ord for Fq2 {
+    #[inline(always)]
+    fn cmp(&self, other: &Fq2) -> Ordering {
+        match self.c1.cmp(&other.c1) {
+            Ordering::Greater => Ordering::Greater,
+            Ordering::Less => Ordering::Less,
+            Ordering::Equal => self.c0.cmp(&other.c0),
+        }
+    }
+}
+
+impl PartialOrd for Fq2 {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Fq2) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Fq2 {
+    /// Multiply an element of Fq by the quadratic nonresidue -5.
+    fn mul_fq_by_nonresidue(fe: &Fq) -> Fq {
+        let mut res = *fe;
+        res.double();
+        res.double();
+        res.add_assign(fe);
+        res.negate();
+        res
+    }
+
+    /// Multiply this element by the cubic and quadratic nonresidue u.
+    pub fn mul_by_nonresidue(&mut self) {
+        // (c0 + c1 * u) * u = -5 * c1 + c0 * u
+        let t0 = self.c0;
+        self.c0 = Self::mul_fq_by_nonresidue(&self.c1);
+        self.c1 = t0;
+    }
+
+    /// Norm of Fq2 as extension field in u over Fq
+    pub fn norm(&self) -> Fq {
+        let mut t0 = self.c0;
+        let mut t1 = self.c1;
+        t0.square();
+        t1.square();
+        t0.sub_assign(&Self::mul_fq_by_nonresidue(&t1));
+
+        t0
+    }
+}
+
+impl Fq2Ops<Fq> for Fq2 {
+    fn mul_by_fq(&mut self, other: &Fq) {
+        self.c0.mul_assign(other);
+        self.c1.mul_assign(other);
+    }
+}
+
+impl Field for Fq2 {
+    fn random<R: rand_core::RngCore + ?core::marker::Sized>(rng: &mut R) -> Self {
+        Fq2 {
+            c0: Fq::random(rng),
+            c1: Fq::random(rng),
+        }
+    }
+    fn zero() -> Self {
+        Fq2 {
+            c0: Fq::zero(),
+            c1: Fq::zero(),
+        }
+    }
+
+    fn one() -> Self {
+        Fq2 {
+            c0: Fq::one(),
+            c1: Fq::zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    fn square(&mut self) {
+        // (c0 + c1 * u)^2 = (c0 + c1) * (c0 - 5 * c1) + 4 * c0 * c1 + 2 * c0 * c1 * u
+        let mut ab = self.c0;
+        ab.mul_assign(&self.c1);
+        let mut c0c1 = self.c0;
+        c0c1.add_assign(&self.c1);
+        let mut c0 = Self::mul_fq_by_nonresidue(&self.c1);
+        c0.add_assign(&self.c0);
+        c0.mul_assign(&c0c1);
+        c0.sub_assign(&ab);
+        c0.sub_assign(&Self::mul_fq_by_nonresidue(&ab));
+        self.c1 = ab;
+        self.c1.double();
+        self.c0 = c0;
+    }
+
+    fn double(&mut self) {
+        self.c0.double();
+        self.c1.double();
+    }
+
+    fn negate(&mut self) {
+        self.c0.negate();
+        self.c1.negate();
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.c0.add_assign(&other.c0);
+        self.c1.add_assign(&other.c1);
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.c0.sub_assign(&other.c0);
+        self.c1.sub_assign(&other.c1);
+    }
+
+    fn mul_assign(&mut self, other: &Self) {
+        let mut aa = self.c0;
+        aa.mul_assign(&other.c0);
+        let mut bb = self.c1;
+        bb.mul_assign(&other.c1);
+        let mut o = other.c0;
+        o.add_assign(&other.c1);
+        self.c1.add_assign(&self.c0);
+        self.c1.mul_assign(&o);
+        self.c1.sub_assign(&aa);
+        self.c1.sub_assign(&bb);
+        self.c0 = aa;
+        self.c0.add_assign(&Self::mul_fq_by_nonresidue(&bb));
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        self.norm().inverse().map(|t| {
+            let mut tmp = Fq2 {
+                c0: self.c0,
+                c1: self.c1,
+            };
+            tmp.c0.mul_assign(&t);
+            tmp.c1.mul_assign(&t);
+            tmp.c1.negate();
+
+            tmp
+        })
+    }
+
+    fn frobenius_map(&mut self, power: usize) {
+        self.c1.mul_assign(&FROBENIUS_COEFF_FQ2_C1[power % 2]);
+    }
+}
+
+impl SqrtField for Fq2 {
+    fn legendre(&self) -> LegendreSymbol {
+        self.norm().legendre()
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        // q = 1 mod 4, so the square root is computed from square roots in Fq
+        // via the norm, see Algorithm 8, https://eprint.iacr.org/2012/685.pdf
+
+        if self.c1.is_zero() {
+            // c0 is either a square in Fq, or -5 * c0 is, in which case
+            // sqrt(c0) = sqrt(c0 / -5) * u.
+            return match self.c0.sqrt() {
+                Some(c0) => Some(Fq2 { c0, c1: Fq::zero() }),
+                None => {
+                    let mut t = self.c0;
+                    t.mul_assign(&Self::mul_fq_by_nonresidue(&Fq::one()).inverse().unwrap());
+                    t.sqrt().map(|c1| Fq2 { c0: Fq::zero(), c1 })
+                }
+            };
+        }
+
+        let alpha = self.norm().sqrt()?;
+        let two_inv = {
+            let mut two = Fq::one();
+            two.double();
+            two.inverse().unwrap()
+        };
+
+        // delta = (c0 + alpha) / 2, or (c0 - alpha) / 2 if the former is a nonresidue
+        let mut delta = self.c0;
+        delta.add_assign(&alpha);
+        delta.mul_assign(&two_inv);
+        if delta.legendre() == LegendreSymbol::QuadraticNonResidue {
+            delta = self.c0;
+            delta.sub_assign(&alpha);
+            delta.mul_assign(&two_inv);
+        }
+
+        // since c1 != 0, delta != 0 here
+        let c0 = delta.sqrt()?;
+        let mut c1 = c0;
+        c1.double();
+        c1 = c1.inverse().unwrap();
+        c1.mul_assign(&self.c1);
+
+        Some(Fq2 { c0, c1 })
+    }
+}
+
+/// Fq2 implementation: hash to two elemnts of Fq and combine.
+impl FromRO for Fq2 {
+    type Length = U128;
+
+    fn from_ro(okm: &GenericArray<u8, U128>) -> Fq2 {
+        let c0 = Fq::from_okm(GenericArray::<u8, U64>::from_slice(&okm[..64]));
+        let c1 = Fq::from_okm(GenericArray::<u8, U64>::from_slice(&okm[64..]));
+        Fq2 { c0, c1 }
+    }
+}
+
+impl Signum0 for Fq2 {
+    fn sgn0(&self) -> Sgn0Result {
+        let Fq2 { c0, c1 } = self;
+        if c0.is_zero() {
+            c1.sgn0()
+        } else {
+            c0.sgn0()
+        }
+    }
+}
+
+#[test]
+fn test_fq2_ordering() {
+    let mut a = Fq2 {
+        c0: Fq::zero(),
+        c1: Fq::zero(),
+    };
+
+    let mut b = a;
+
+    assert!(a.cmp(&b) == Ordering::Equal);
+    b.c0.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Less);
+    a.c0.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Equal);
+    b.c1.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Less);
+    a.c0.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Less);
+    a.c1.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Greater);
+    b.c0.add_assign(&Fq::one());
+    assert!(a.cmp(&b) == Ordering::Equal);
+}
+
+#[test]
+fn test_fq2_basics() {
+    assert_eq!(
+        Fq2 {
+            c0: Fq::zero(),
+            c1: Fq::zero(),
+        },
+        Fq2::zero()
+    );
+    assert_eq!(
+        Fq2 {
+            c0: Fq::one(),
+            c1: Fq::zero(),
+        },
+        Fq2::one()
+    );
+    assert!(Fq2::zero().is_zero());
+    assert!(!Fq2::one().is_zero());
+    assert!(!Fq2 {
+        c0: Fq::zero(),
+        c1: Fq::one(),
+    }
+    .is_zero());
+}
+
+#[cfg(test)]
+use rand_core::SeedableRng;
+
+#[test]
+fn test_fq2_mul_nonresidue() {
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let nqr = Fq2 {
+        c0: Fq::zero(),
+        c1: Fq::one(),
+    };
+
+    for _ in 0..1000 {
+        let mut a = Fq2::random(&mut rng);
+        let mut b = a;
+        a.mul_by_nonresidue();
+        b.mul_assign(&nqr);
+
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn test_fq2_legendre() {
+    use ff::LegendreSymbol::*;
+
+    assert_eq!(Zero, Fq2::zero().legendre());
+    // every element of Fq is a square in Fq2
+    let mut m1 = Fq2::one();
+    m1.negate();
+    assert_eq!(QuadraticResidue, m1.legendre());
+    let m5 = Fq2 {
+        c0: Fq2::mul_fq_by_nonresidue(&Fq::one()),
+        c1: Fq::zero(),
+    };
+    assert_eq!(QuadraticResidue, m5.legendre());
+    let mut root = m5.sqrt().unwrap();
+    assert!(root.c0.is_zero());
+    root.square();
+    assert_eq!(root, m5);
+    // u is the nonresidue defining Fq6
+    let mut nqr = Fq2::one();
+    nqr.mul_by_nonresidue();
+    assert_eq!(QuadraticNonResidue, nqr.legendre());
+    assert!(nqr.sqrt().is_none());
+}
+
+#[test]
+fn fq2_field_tests() {
+    use ff::PrimeField;
+
+    ::tests::field::random_field_tests::<Fq2>();
+    ::tests::field::random_sqrt_tests::<Fq2>();
+    ::tests::field::random_frobenius_tests::<Fq2, _>(super::fq::Fq::char(), 13);
+}
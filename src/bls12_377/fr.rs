@@ -0,0 +1,61 @@
+use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+
+#[derive(PrimeField, Zeroize)]
+#[PrimeFieldModulus = "8444461749428370424248824938781546531375899335154063827935233455917409239041"]
+#[PrimeFieldGenerator = "22"]
+pub struct Fr(FrRepr);
+
+prime_field_bytes_impl!(Fr, FrRepr, 32);
+
+/// set the default value for Fr to 0
+impl ::core::default::Default for Fr {
+    fn default() -> Self {
+        Fr::zero()
+    }
+}
+
+/// # Safety
+pub const unsafe fn transmute(r: FrRepr) -> Fr {
+    Fr(r)
+}
+
+#[test]
+fn test_fr_num_bits() {
+    assert_eq!(Fr::NUM_BITS, 253);
+    assert_eq!(Fr::CAPACITY, 252);
+}
+
+#[test]
+fn test_fr_root_of_unity() {
+    use ff::SqrtField;
+
+    assert_eq!(Fr::S, 47);
+    assert_eq!(
+        Fr::multiplicative_generator(),
+        Fr::from_repr(FrRepr::from(22)).unwrap()
+    );
+    assert_eq!(
+        Fr::multiplicative_generator().pow([
+            0xedfda00000021423,
+            0x9a3cb86f6002b354,
+            0xcabd34594aacc168,
+            0x0000000000002556,
+        ]),
+        Fr::root_of_unity()
+    );
+    assert_eq!(Fr::root_of_unity().pow([1 << Fr::S]), Fr::one());
+    assert!(Fr::multiplicative_generator().sqrt().is_none());
+}
+
+#[test]
+fn fr_field_tests() {
+    ::tests::field::random_field_tests::<Fr>();
+    ::tests::field::random_sqrt_tests::<Fr>();
+    ::tests::field::random_frobenius_tests::<Fr, _>(Fr::char(), 13);
+    ::tests::field::from_str_tests::<Fr>();
+}
+
+#[test]
+fn fr_repr_tests() {
+    ::tests::repr::random_repr_tests::<Fr, FrRepr>();
+}
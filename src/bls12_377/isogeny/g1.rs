@@ -0,0 +1,116 @@
+/*!
+Constants for G1 isogeny.
+*/
+
+use bls12_377::{Fq, FqRepr, G1};
+use osswu_map::{eval_iso, IsogenyMap};
+
+/// Coefficients of the 2-isogeny x map's numerator
+const XNUM: [Fq; 3] = [
+    Fq(FqRepr([
+        0xc4f21c523c235aa4,
+        0x59ec3b912011fd78,
+        0x84052a14777e011b,
+        0xd2a48980486fcc26,
+        0xa8d7b962b4aaafa6,
+        0x010c5f3277f2a315,
+    ])),
+    Fq(FqRepr([
+        0xac492836d2c23c6d,
+        0x8999b34a056153a5,
+        0x4db250a866fb9367,
+        0x2e621304854e2c3f,
+        0x2e7c2456fee6b206,
+        0x0023815f9d6011c0,
+    ])),
+    Fq(FqRepr([
+        0x6c5fcbe4969ee1ca,
+        0x46b8d4fd154f562d,
+        0xe8a088c3a986da4c,
+        0x75e063773dd373a7,
+        0x4bdf70b4b6dd4b9a,
+        0x00c55c733d327f95,
+    ])),
+];
+
+/// Coefficients of the 2-isogeny x map's denominator
+const XDEN: [Fq; 2] = [
+    Fq(FqRepr([
+        0x7f6cc00000000131,
+        0x748a1e3d3000009e,
+        0xdff7fadca50ec81b,
+        0x2385aa8423fc4d83,
+        0x2c51da416c283f6a,
+        0x00936d8251c927b6,
+    ])),
+    Fq(FqRepr([
+        0x02cdffffffffff68,
+        0x51409f837fffffb1,
+        0x9f7db3a98a7d3ff2,
+        0x7b4e97b76e7c6305,
+        0x4cf495bf803c84e8,
+        0x008d6661e2fdf49a,
+    ])),
+];
+
+/// Coefficients of the 2-isogeny y map's numerator
+const YNUM: [Fq; 3] = [
+    Fq(FqRepr([
+        0xe2743fffffffff7b,
+        0x07188b930fffffba,
+        0xeb8dfd34592d97f4,
+        0x6be4c4c080acd6a4,
+        0x035603079034f44b,
+        0x007bb995a69e3607,
+    ])),
+    Fq(FqRepr([
+        0x03a1c0000000004d,
+        0xee6b0d8270000028,
+        0x4f34885af4caa806,
+        0xdc7b8e1749b6e20c,
+        0x9fc0bae0ac8306c6,
+        0x016787152646169d,
+    ])),
+    Fq(FqRepr([
+        0x2059bfffffffffed,
+        0x4a2813f06ffffff6,
+        0xb3efb675314fa7fe,
+        0x0f69d2f6edcf8c60,
+        0x499e92b7f007909d,
+        0x0011accc3c5fbe93,
+    ])),
+];
+
+/// Coefficients of the 2-isogeny y map's denominator
+const YDEN: [Fq; 3] = [
+    Fq(FqRepr([
+        0x862f3ffffffffd9f,
+        0x2df720c9cffffec3,
+        0x5f036c766febb7c9,
+        0xd31784eab8fc7887,
+        0x6d97513d9450ca66,
+        0x00875f417432c17e,
+    ])),
+    Fq(FqRepr([
+        0xfed9800000000262,
+        0xe9143c7a6000013c,
+        0xbfeff5b94a1d9036,
+        0x470b550847f89b07,
+        0x58a3b482d8507ed4,
+        0x0126db04a3924f6c,
+    ])),
+    Fq(FqRepr([
+        0x02cdffffffffff68,
+        0x51409f837fffffb1,
+        0x9f7db3a98a7d3ff2,
+        0x7b4e97b76e7c6305,
+        0x4cf495bf803c84e8,
+        0x008d6661e2fdf49a,
+    ])),
+];
+
+impl IsogenyMap for G1 {
+    fn isogeny_map(&mut self) {
+        eval_iso::<_, { YNUM.len() }>(self, [&XNUM[..], &XDEN[..], &YNUM[..], &YDEN[..]]);
+    }
+}
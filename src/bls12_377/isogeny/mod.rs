@@ -0,0 +1,11 @@
+/*!
+Isogenies E' -> E and E2' -> E2 for the simplified SWU map.
+
+G1 uses a 2-isogeny from y^2 = x^3 - 15 x + 22. The smallest isogeny onto
+the G2 curve from a curve with non-zero A and B has degree 23.
+*/
+
+mod g1;
+mod g2;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,81 @@
+/*!
+Tests for isogenies.
+*/
+
+use bls12_377::fq::{B_COEFF, B_COEFF_FQ2};
+use bls12_377::{Fq, Fq2, G1, G2};
+use ff::Field;
+use osswu_map::{IsogenyMap, OSSWUMap};
+use rand_core::SeedableRng;
+use CurveProjective;
+
+/// check that the point (X : Y : Z)==(X/Z^2, Y/Z^3) is on E: y^2 = x^3 + b
+fn check_on_curve<PtT: CurveProjective>(pt: &PtT, b: &PtT::Base) {
+    let (x, y, z) = pt.as_tuple();
+    let mut lhs = *y;
+    lhs.square();
+
+    let mut rhs = *x;
+    rhs.square();
+    rhs.mul_assign(x);
+    let mut z6 = *z;
+    z6.square();
+    let z2 = z6;
+    z6.square();
+    z6.mul_assign(&z2);
+    z6.mul_assign(b);
+    rhs.add_assign(&z6);
+
+    assert!(!z.is_zero());
+    assert_eq!(lhs, rhs);
+}
+
+/// check that the isogeny maps onto E and is a group homomorphism
+fn check_isogeny<PtT: IsogenyMap + OSSWUMap>(u0: &PtT::Base, u1: &PtT::Base, b: &PtT::Base) {
+    let mut p = PtT::osswu_map(u0);
+    let mut q = PtT::osswu_map(u1);
+    let mut p_plus_q = p;
+    p_plus_q.add_assign(&q);
+
+    p.isogeny_map();
+    q.isogeny_map();
+    p_plus_q.isogeny_map();
+    check_on_curve(&p, b);
+    check_on_curve(&q, b);
+    check_on_curve(&p_plus_q, b);
+
+    p.add_assign(&q);
+    assert_eq!(p, p_plus_q);
+}
+
+#[test]
+fn test_iso2() {
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..32 {
+        check_isogeny::<G1>(&Fq::random(&mut rng), &Fq::random(&mut rng), &B_COEFF);
+    }
+
+    // the point at infinity is mapped to itself
+    let mut pt = G1::zero();
+    pt.isogeny_map();
+    assert!(pt.is_zero());
+}
+
+#[test]
+fn test_iso23() {
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..8 {
+        check_isogeny::<G2>(&Fq2::random(&mut rng), &Fq2::random(&mut rng), &B_COEFF_FQ2);
+    }
+
+    // the point at infinity is mapped to itself
+    let mut pt = G2::zero();
+    pt.isogeny_map();
+    assert!(pt.is_zero());
+}
@@ -0,0 +1,201 @@
+/*!
+ This module implements the BLS12-377 pairing-friendly curve, which has
+ two-adicity 47 in the scalar field and 46 in the base field, making it
+ suitable for recursive proof composition.
+
+ The base field is extended as Fq2 = Fq[u]/(u^2 + 5),
+ Fq6 = Fq2[v]/(v^3 - u) and Fq12 = Fq6[w]/(w^2 - v).
+ G1 is `y^2 = x^3 + 1` over Fq, and G2 lives on the D-type sextic twist
+ `y^2 = x^3 + 1 / u` over Fq2.
+*/
+
+mod cofactor;
+mod ec;
+mod fq;
+mod fq12;
+mod fq2;
+mod fq6;
+mod fr;
+mod isogeny;
+mod osswu_map;
+
+pub use self::ec::{
+    G1Affine, G1Compressed, G1Prepared, G1Uncompressed, G2Affine, G2Compressed, G2Prepared,
+    G2Uncompressed, G1, G2,
+};
+pub use self::fq::{Fq, FqRepr};
+pub use self::fq12::Fq12;
+pub use self::fq2::Fq2;
+pub use self::fq6::Fq6;
+pub use self::fr::{Fr, FrRepr};
+
+pub mod transmute {
+    pub use super::ec::g1::transmute_affine as g1_affine;
+    pub use super::ec::g1::transmute_projective as g1_projective;
+    pub use super::ec::g2::transmute_affine as g2_affine;
+    pub use super::ec::g2::transmute_projective as g2_projective;
+    pub use super::fq::transmute as fq;
+    pub use super::fr::transmute as fr;
+}
+
+use bls12::{Bls12Parameters, TwistType};
+
+/// The types and constants defining BLS12-377.
+#[derive(Clone, Debug)]
+pub struct Bls12_377Parameters;
+
+impl Bls12Parameters for Bls12_377Parameters {
+    // The BLS parameter x for BLS12-377 is 0x8508c00000000001
+    const X: u64 = 0x8508c00000000001;
+    const X_IS_NEGATIVE: bool = false;
+    const TWIST_TYPE: TwistType = TwistType::D;
+
+    type Fr = Fr;
+    type Fq = Fq;
+    type Fq2 = Fq2;
+    type Fq12 = Fq12;
+    type G1 = G1;
+    type G1Affine = G1Affine;
+    type G2 = G2;
+    type G2Affine = G2Affine;
+}
+
+pub type Bls12_377 = ::bls12::Bls12<Bls12_377Parameters>;
+
+#[test]
+fn bls12_377_engine_tests() {
+    ::tests::engine::engine_tests::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_final_exponentiation() {
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use Engine;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    // 3 * (q^12 - 1) / n
+    const EXP: [u64; 67] = [
+        0xb53b000000000000,
+        0x4460d445a0000012,
+        0x7e631cac6b93e025,
+        0x61cb5dc24d69274c,
+        0x9311e232e06fd6a8,
+        0xa8502fa8f466b691,
+        0x5e0c0fc806bd9f60,
+        0x61b4a20cd88a2032,
+        0x4d4372c6979845f2,
+        0xc4e146a17dab2c64,
+        0x19eea6fadb8aace7,
+        0x604c02f7d642f7f8,
+        0x987f28c6604c5dcb,
+        0x0a5af77c105f5e90,
+        0x8588a7f3144642a1,
+        0xfb7671c9134c970a,
+        0x1d6063e0a2e136a3,
+        0xa29ecc6e32919576,
+        0x9e5e9aaadaaa00e0,
+        0x2a36df8fd154f2d8,
+        0x23beb42e79f85dab,
+        0x805c6383d0286c74,
+        0xb5e2250eee395821,
+        0x2b2bf3b20ed5937c,
+        0xea30f6a7b4521829,
+        0xdba8cbef5b03bcd0,
+        0xfc456b8789d5bae1,
+        0x0d9a1e660cbbe91b,
+        0x8f67e5bb28f76a46,
+        0xa5d945707a89d7bf,
+        0xc4a486258bf11fb1,
+        0x0aa7fdb16a887af2,
+        0x57a9e9fef5aa229c,
+        0xf0f18a88f5ffe401,
+        0xf13208590be27e78,
+        0x9898c028c73dd021,
+        0x49502d2a71994fab,
+        0xeb4f981945698ef0,
+        0xce708bdec2c10628,
+        0x4595e2639ae94d35,
+        0x013cbb09227c0942,
+        0x4bf26de9d191661a,
+        0x8941c95aa2d2a04f,
+        0xc04960703d975369,
+        0x9fbfd22831919b4a,
+        0x616067540d419f7f,
+        0x231d500aecc1eb52,
+        0x59ae840de7362f7c,
+        0x1a77f8dab474e99c,
+        0xc010ac1a83b77720,
+        0x9fd0ec1dc0419176,
+        0xfdc39075cc1c01df,
+        0xcf96e02493f4683f,
+        0xef19bb008e047c24,
+        0xd66c0c97066502f8,
+        0x35ca31c4ba994c89,
+        0x370acd05663ffda0,
+        0x80dc48213527e51d,
+        0x1a8663c49da97a28,
+        0x211c240f5489c67b,
+        0x1941dd3f9d646d87,
+        0x3a26e8d85c306e6c,
+        0x98be5439b32fda94,
+        0x9312d07b5d311055,
+        0xed18d4251c557c29,
+        0x4da01ed0ec73865a,
+        0x0000518fe3a45039,
+    ];
+    for _ in 0..3 {
+        let f = Fq12::random(&mut rng);
+        assert_eq!(
+            Bls12_377::final_exponentiation(&f).unwrap(),
+            f.pow(&EXP[..])
+        );
+    }
+    assert!(Bls12_377::final_exponentiation(&Fq12::zero()).is_none());
+}
+
+#[test]
+fn test_bls12_377_pairing_subgroup() {
+    use ff::{Field, PrimeField};
+    use {CurveAffine, Engine};
+
+    let e = Bls12_377::pairing(G1Affine::one(), G2Affine::one());
+    assert_ne!(e, Fq12::one());
+    assert_eq!(e.pow(Fr::char()), Fq12::one());
+}
+
+#[test]
+fn test_bls12_377_fixed_size_bytes() {
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+    use {CurveProjective, Engine};
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for g1 in &[G1::zero(), G1::one(), G1::random(&mut rng)] {
+        assert_eq!(G1::from_bytes(&g1.to_bytes()), Some(*g1));
+        assert_eq!(G1::from_uncompressed(&g1.to_uncompressed()), Some(*g1));
+    }
+    for g2 in &[G2::zero(), G2::one(), G2::random(&mut rng)] {
+        let q = g2.into_affine();
+        assert_eq!(G2Affine::from_bytes(&q.to_bytes()), Some(q));
+        assert_eq!(G2Affine::from_uncompressed(&q.to_uncompressed()), Some(q));
+    }
+    let fr = Fr::random(&mut rng);
+    assert_eq!(Fr::from_bytes(&fr.to_bytes()), Some(fr));
+    let gt = Bls12_377::pairing(G1::random(&mut rng), G2::random(&mut rng));
+    assert_eq!(Fq12::from_bytes(&gt.to_bytes()), Some(gt));
+
+    // the encodings are big-endian
+    let mut bytes = [0u8; 48];
+    bytes[47] = 1;
+    assert_eq!(Fq::one().to_bytes(), bytes);
+    ::encoding_macros::repr_to_be(&Fq::char(), &mut bytes);
+    assert_eq!(Fq::from_bytes(&bytes), None);
+}
@@ -0,0 +1,72 @@
+/*!
+Constants for the simplified SWU map for G1
+*/
+
+use super::{sswu, SqrtRatioConsts};
+use bls12_377::{Fq, FqRepr, G1};
+use osswu_map::OSSWUMap;
+
+pub(super) const ELLP_A: Fq = Fq(FqRepr([
+    0x6f19c000000008ed,
+    0xb06f79a0700004a3,
+    0x426363ff84d7a8cd,
+    0x49135dff8b7f9477,
+    0x5cd256899b9aa488,
+    0x001e23a229f7ff8b,
+]));
+
+pub(super) const ELLP_B: Fq = Fq(FqRepr([
+    0x9a76bffffffff2e9,
+    0x5a3e286faffff932,
+    0xdc25c143d08286d2,
+    0xe1cd141e77fcf991,
+    0x3167b6320cca6b5c,
+    0x0063347edb6f8ed7,
+]));
+
+const XI: Fq = Fq(FqRepr([
+    0xf54900000000068c,
+    0xde669a6a40000366,
+    0xa166d075f4c36096,
+    0x1c2ae2ea447c0cfe,
+    0xca69a7c72feb6eef,
+    0x00a582e39e2ac109,
+]));
+
+/// (c2 - 1) / 2, where q - 1 = 2^46 * c2
+const C3: [u64; 6] = [
+    0xba88600000010a11,
+    0xc45f741290002e16,
+    0xb3e601ea271e3de6,
+    0x0b80d94292763445,
+    0x748c2f8a21d58c76,
+    0x000000000000035c,
+];
+
+const SQRT_RATIO_CONSTS: SqrtRatioConsts<Fq> = SqrtRatioConsts {
+    c1: 46,
+    c3: &C3,
+    c6: Fq(FqRepr([
+        0xe5c85f8bf4c4b7df,
+        0xc714dafc33fe8b3f,
+        0xd1eac56f1c0bd13f,
+        0x8e838cbfaa9cb0d2,
+        0x29212c79fd19edc0,
+        0x00f1f0d31144e581,
+    ])),
+    c7: Fq(FqRepr([
+        0x9b8ecd665efa5275,
+        0x67089c7dc9070a39,
+        0xd998301b52d9dd8a,
+        0xfbfa3741b26bed42,
+        0x7f6e8330c90ce48b,
+        0x01143f3a3b88ee78,
+    ])),
+};
+
+impl OSSWUMap for G1 {
+    fn osswu_map(u: &Fq) -> G1 {
+        let (x, y, z) = sswu(u, &XI, &ELLP_A, &ELLP_B, &SQRT_RATIO_CONSTS);
+        G1 { x, y, z }
+    }
+}
@@ -0,0 +1,128 @@
+/*!
+Constants for the simplified SWU map for G2
+*/
+
+use super::{sswu, SqrtRatioConsts};
+use bls12_377::{Fq, Fq2, FqRepr, G2};
+use osswu_map::OSSWUMap;
+
+pub(super) const ELLP_A: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x3b523c8cdcff9889,
+        0xc4780e8aecb49c4e,
+        0xb6213978038aee6f,
+        0x3dfa913b0bb775fa,
+        0x7dc49dcc25fe2b32,
+        0x013fd9f9381384b3,
+    ])),
+    c1: Fq(FqRepr([
+        0x4037a99f9382f7cf,
+        0xcf59a5e803bc10f3,
+        0xc5fe6b60f3e4a2f9,
+        0x8e73810c455247e7,
+        0x8b82b6cf45af0adf,
+        0x018d59c91b4959e9,
+    ])),
+};
+
+pub(super) const ELLP_B: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x8e12acc352f1d411,
+        0x75a312f9c520c883,
+        0x5c2bd664a307a847,
+        0x17e419dbc89cc18e,
+        0x782239981ee6b04f,
+        0x008a8dd122f55fe5,
+    ])),
+    c1: Fq(FqRepr([
+        0x20c316aeaae12d45,
+        0x9033a807ecda62f5,
+        0xad04281c8e9f799f,
+        0x721250e7ea917628,
+        0x2b735c35b0e9c073,
+        0x0128b6ff2b34c941,
+    ])),
+};
+
+const XI: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x928dbffffffff8dd,
+        0x89e5625d6ffffc4a,
+        0x1d0a45634fc3275b,
+        0x79468ec02af56996,
+        0x48c5f3b8bcf25f34,
+        0x01961dc45c98447b,
+    ])),
+    c1: Fq(FqRepr([
+        0x02cdffffffffff68,
+        0x51409f837fffffb1,
+        0x9f7db3a98a7d3ff2,
+        0x7b4e97b76e7c6305,
+        0x4cf495bf803c84e8,
+        0x008d6661e2fdf49a,
+    ])),
+};
+
+/// (c2 - 1) / 2, where q^2 - 1 = 2^47 * c2
+const C3: [u64; 12] = [
+    0xd254f00000010a11,
+    0x268f2e1bd8007338,
+    0xfbd235f1c09a4ada,
+    0x24b958638c9d6bcb,
+    0xaaaa8bb70ae03183,
+    0xe712502a4d6c460a,
+    0x18afb90660cdc014,
+    0x71413dda4d5c09d0,
+    0xb87da4aee93f1f5e,
+    0x913bb361263c4bb8,
+    0xd307d0bbaffb2256,
+    0x0000000000000002,
+];
+
+const SQRT_RATIO_CONSTS: SqrtRatioConsts<Fq2> = SqrtRatioConsts {
+    c1: 47,
+    c3: &C3,
+    c6: Fq2 {
+        c0: Fq(FqRepr([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ])),
+        c1: Fq(FqRepr([
+            0x8630fe0d7020eeb2,
+            0xb5732f8ec0c0ed7d,
+            0xa5d1c1738530a517,
+            0x9a1b5eec4d5fd8ba,
+            0x0c31e041b98385a2,
+            0x01ab2b45f27b8006,
+        ])),
+    },
+    c7: Fq2 {
+        c0: Fq(FqRepr([
+            0x1487bc83921e06a1,
+            0x270e46d69678643b,
+            0xbe00d71cedc90982,
+            0x688251a6640ddd52,
+            0x00d67328a1a4e4cb,
+            0x0086ab9abbec6c66,
+        ])),
+        c1: Fq(FqRepr([
+            0x4a3fb1f4c68f6765,
+            0x9631f8ae15e2dc7b,
+            0x47176e5449c4b0b2,
+            0x9356c07206260053,
+            0x13ee657c9f4632fe,
+            0x0047dc1cebdb57d3,
+        ])),
+    },
+};
+
+impl OSSWUMap for G2 {
+    fn osswu_map(u: &Fq2) -> G2 {
+        let (x, y, z) = sswu(u, &XI, &ELLP_A, &ELLP_B, &SQRT_RATIO_CONSTS);
+        G2 { x, y, z }
+    }
+}
@@ -0,0 +1,142 @@
+/*!
+Simplified SWU maps for G1 and G2 of BLS12-377.
+
+Both the base field and its quadratic extension have q = 1 mod 4, so the
+square root tricks used for BLS12-381 do not apply. Instead, we follow the
+generic `sqrt_ratio` and `map_to_curve_simple_swu` procedures of appendix F.2
+of https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-07.
+*/
+
+mod g1;
+mod g2;
+#[cfg(test)]
+mod tests;
+
+use ff::Field;
+use signum::Signum0;
+
+/// Constants for `sqrt_ratio` in a field of order q with q - 1 = 2^c1 * c2, c2 odd
+struct SqrtRatioConsts<F: Field> {
+    /// c1, the 2-adicity of q - 1
+    c1: u32,
+    /// c3 = (c2 - 1) / 2
+    c3: &'static [u64],
+    /// c6 = Z^c2
+    c6: F,
+    /// c7 = Z^((c2 + 1) / 2)
+    c7: F,
+}
+
+/// Returns (true, sqrt(u / v)) if u / v is square, and (false, sqrt(Z * u / v)) otherwise.
+/// Requires v != 0.
+fn sqrt_ratio<F: Field>(u: &F, v: &F, consts: &SqrtRatioConsts<F>) -> (bool, F) {
+    let mut tv1 = consts.c6;
+    // tv2 = v^c4 with c4 = 2^c1 - 1
+    let mut tv2 = v.pow([(1u64 << consts.c1) - 1]);
+    let mut tv3 = tv2;
+    tv3.square();
+    tv3.mul_assign(v);
+    let mut tv5 = tv3;
+    tv5.mul_assign(u);
+    tv5 = tv5.pow(consts.c3);
+    tv5.mul_assign(&tv2);
+    tv2 = tv5;
+    tv2.mul_assign(v);
+    tv3 = tv5;
+    tv3.mul_assign(u);
+    let mut tv4 = tv3;
+    tv4.mul_assign(&tv2);
+
+    // tv5 = tv4^c5 with c5 = 2^(c1 - 1)
+    tv5 = tv4;
+    for _ in 1..consts.c1 {
+        tv5.square();
+    }
+    let is_qr = tv5 == F::one();
+    if !is_qr {
+        tv3.mul_assign(&consts.c7);
+        tv4.mul_assign(&tv1);
+    }
+
+    for i in (2..=consts.c1).rev() {
+        tv5 = tv4;
+        for _ in 2..i {
+            tv5.square();
+        }
+        let e1 = tv5 == F::one();
+        tv2 = tv3;
+        tv2.mul_assign(&tv1);
+        tv1.square();
+        if !e1 {
+            tv3 = tv2;
+            tv4.mul_assign(&tv1);
+        }
+    }
+
+    (is_qr, tv3)
+}
+
+/// Simplified SWU map onto y^2 = x^3 + ellp_a * x + ellp_b, returning
+/// Jacobian coordinates (X, Y, Z) of the point (X / Z^2, Y / Z^3).
+fn sswu<F: Field + Signum0>(
+    u: &F,
+    z: &F,
+    ellp_a: &F,
+    ellp_b: &F,
+    consts: &SqrtRatioConsts<F>,
+) -> (F, F, F) {
+    let mut tv1 = *u;
+    tv1.square();
+    tv1.mul_assign(z); // Z * u^2
+    let mut tv2 = tv1;
+    tv2.square();
+    tv2.add_assign(&tv1); // Z^2 * u^4 + Z * u^2
+
+    // x1 = B * (tv2 + 1) / (A * tv4)
+    let mut x_num = tv2;
+    x_num.add_assign(&F::one());
+    x_num.mul_assign(ellp_b);
+    let mut x_den = if tv2.is_zero() {
+        *z
+    } else {
+        let mut tmp = tv2;
+        tmp.negate();
+        tmp
+    };
+    x_den.mul_assign(ellp_a);
+
+    // g(x1) = gx_num / gx_den, scaled to share the denominator x_den^3
+    let mut gx_num = x_num;
+    gx_num.square();
+    let mut gx_den = x_den;
+    gx_den.square();
+    let mut tmp = gx_den;
+    tmp.mul_assign(ellp_a);
+    gx_num.add_assign(&tmp);
+    gx_num.mul_assign(&x_num); // x_num^3 + A * x_num * x_den^2
+    gx_den.mul_assign(&x_den); // x_den^3
+    tmp = gx_den;
+    tmp.mul_assign(ellp_b);
+    gx_num.add_assign(&tmp); // + B * x_den^3
+
+    let (is_gx1_square, y1) = sqrt_ratio(&gx_num, &gx_den, consts);
+    let mut y = if is_gx1_square {
+        y1
+    } else {
+        // x2 = Z * u^2 * x1 and y2 = Z * u^3 * sqrt(g(x1) / Z)
+        x_num.mul_assign(&tv1);
+        let mut tmp = tv1;
+        tmp.mul_assign(u);
+        tmp.mul_assign(&y1);
+        tmp
+    };
+
+    // make sure sign of y and sign of u agree
+    let sgn0_y_xor_u = y.sgn0() ^ u.sgn0();
+    y.negate_if(sgn0_y_xor_u);
+
+    // convert to Jacobian coordinates
+    x_num.mul_assign(&x_den); // x_num * x_den / x_den^2 = x_num / x_den
+    y.mul_assign(&gx_den); // y * x_den^3 / x_den^3 = y
+    (x_num, y, x_den)
+}
@@ -0,0 +1,1153 @@
+/*!
+ Montgomery multiplication for x86_64 CPUs with the ADX and BMI2 extensions,
+ enabled by the `asm` feature.
+
+ The kernels implement the CIOS method, interleaving two carry chains with
+ MULX, ADCX and ADOX. They require the top bit of the modulus to be clear,
+ which holds for both Fq and Fr, so that the intermediate result fits in
+ one extra word. The squaring kernels compute each product of two distinct
+ limbs once, double the sum and add the squares of the limbs, then apply the
+ Montgomery reduction to the double-width result.
+
+ `Fq` and `Fr` name their kernels with the `PrimeFieldKernels` attribute of
+ the `PrimeField` derive, so that the derived `Field::mul_assign` and
+ `Field::square` call them when the CPU supports them, and otherwise fall
+ back to the portable implementation.
+*/
+
+use std::arch::asm;
+
+/// Returns whether the CPU supports the ADX and BMI2 extensions.
+#[inline(always)]
+pub(super) fn has_adx() -> bool {
+    is_x86_feature_detected!("adx") && is_x86_feature_detected!("bmi2")
+}
+
+/// Sets a = a * b / 2^384 mod q, where `q` holds the 6 limbs of the modulus
+/// followed by -q^-1 mod 2^64. Requires a, b < q.
+///
+/// # Safety
+/// The CPU must support ADX and BMI2, and `a` and `b` must point to 6 limbs
+/// each. They may point to the same limbs.
+#[inline(always)]
+pub(super) unsafe fn mont_mul_6(a: *mut u64, b: *const u64, q: &[u64; 7]) {
+    asm!(
+        // t = 0
+        "xor {t0:e}, {t0:e}",
+        "xor {t1:e}, {t1:e}",
+        "xor {t2:e}, {t2:e}",
+        "xor {t3:e}, {t3:e}",
+        "xor {t4:e}, {t4:e}",
+        "xor {t5:e}, {t5:e}",
+        "xor {t6:e}, {t6:e}",
+        // t += a * b[0]
+        "mov rdx, qword ptr [{b}]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t6}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t0}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t6}, {lo}",
+        // t += a * b[1]
+        "mov rdx, qword ptr [{b} + 8]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t1}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t += a * b[2]
+        "mov rdx, qword ptr [{b} + 16]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t2}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t += a * b[3]
+        "mov rdx, qword ptr [{b} + 24]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t3}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // t += a * b[4]
+        "mov rdx, qword ptr [{b} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t3}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t4}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t3}, {lo}",
+        // t += a * b[5]
+        "mov rdx, qword ptr [{b} + 40]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t5}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // a = t; t -= q; if t < q, t = a
+        "mov qword ptr [{a}], {t6}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "mov qword ptr [{a} + 32], {t3}",
+        "mov qword ptr [{a} + 40], {t4}",
+        "sub {t6}, qword ptr [{q}]",
+        "sbb {t0}, qword ptr [{q} + 8]",
+        "sbb {t1}, qword ptr [{q} + 16]",
+        "sbb {t2}, qword ptr [{q} + 24]",
+        "sbb {t3}, qword ptr [{q} + 32]",
+        "sbb {t4}, qword ptr [{q} + 40]",
+        "cmovc {t6}, qword ptr [{a}]",
+        "cmovc {t0}, qword ptr [{a} + 8]",
+        "cmovc {t1}, qword ptr [{a} + 16]",
+        "cmovc {t2}, qword ptr [{a} + 24]",
+        "cmovc {t3}, qword ptr [{a} + 32]",
+        "cmovc {t4}, qword ptr [{a} + 40]",
+        "mov qword ptr [{a}], {t6}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "mov qword ptr [{a} + 32], {t3}",
+        "mov qword ptr [{a} + 40], {t4}",
+        a = in(reg) a,
+        b = in(reg) b,
+        q = in(reg) q.as_ptr(),
+        t0 = out(reg) _,
+        t1 = out(reg) _,
+        t2 = out(reg) _,
+        t3 = out(reg) _,
+        t4 = out(reg) _,
+        t5 = out(reg) _,
+        t6 = out(reg) _,
+        hi = out(reg) _,
+        lo = out(reg) _,
+        out("rdx") _,
+        options(nostack),
+    );
+}
+
+/// Sets a = a^2 / 2^384 mod q, where `q` holds the 6 limbs of the modulus
+/// followed by -q^-1 mod 2^64. Requires a < q.
+///
+/// # Safety
+/// The CPU must support ADX and BMI2, and `a` must point to 6 limbs.
+#[inline(always)]
+pub(super) unsafe fn mont_sqr_6(a: *mut u64, q: &[u64; 7]) {
+    let mut buf = [0u64; 12];
+    asm!(
+        // buf = the products a[i] * a[j] for i < j
+        // buf += a[0] * a[1..6] * 2^(64 * 1)
+        "mov {t0}, qword ptr [{buf} + 8]",
+        "mov {t1}, qword ptr [{buf} + 16]",
+        "mov {t2}, qword ptr [{buf} + 24]",
+        "mov {t3}, qword ptr [{buf} + 32]",
+        "mov {t4}, qword ptr [{buf} + 40]",
+        "mov {t5}, qword ptr [{buf} + 48]",
+        "mov rdx, qword ptr [{a}]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t5}, {lo}",
+        "mov qword ptr [{buf} + 8], {t0}",
+        "mov qword ptr [{buf} + 16], {t1}",
+        "mov qword ptr [{buf} + 24], {t2}",
+        "mov qword ptr [{buf} + 32], {t3}",
+        "mov qword ptr [{buf} + 40], {t4}",
+        "mov qword ptr [{buf} + 48], {t5}",
+        // buf += a[1] * a[2..6] * 2^(64 * 3)
+        "mov {t0}, qword ptr [{buf} + 24]",
+        "mov {t1}, qword ptr [{buf} + 32]",
+        "mov {t2}, qword ptr [{buf} + 40]",
+        "mov {t3}, qword ptr [{buf} + 48]",
+        "mov {t4}, qword ptr [{buf} + 56]",
+        "mov rdx, qword ptr [{a} + 8]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        "mov qword ptr [{buf} + 24], {t0}",
+        "mov qword ptr [{buf} + 32], {t1}",
+        "mov qword ptr [{buf} + 40], {t2}",
+        "mov qword ptr [{buf} + 48], {t3}",
+        "mov qword ptr [{buf} + 56], {t4}",
+        // buf += a[2] * a[3..6] * 2^(64 * 5)
+        "mov {t0}, qword ptr [{buf} + 40]",
+        "mov {t1}, qword ptr [{buf} + 48]",
+        "mov {t2}, qword ptr [{buf} + 56]",
+        "mov {t3}, qword ptr [{buf} + 64]",
+        "mov rdx, qword ptr [{a} + 16]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t3}, {lo}",
+        "mov qword ptr [{buf} + 40], {t0}",
+        "mov qword ptr [{buf} + 48], {t1}",
+        "mov qword ptr [{buf} + 56], {t2}",
+        "mov qword ptr [{buf} + 64], {t3}",
+        // buf += a[3] * a[4..6] * 2^(64 * 7)
+        "mov {t0}, qword ptr [{buf} + 56]",
+        "mov {t1}, qword ptr [{buf} + 64]",
+        "mov {t2}, qword ptr [{buf} + 72]",
+        "mov rdx, qword ptr [{a} + 24]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 32]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        "mov qword ptr [{buf} + 56], {t0}",
+        "mov qword ptr [{buf} + 64], {t1}",
+        "mov qword ptr [{buf} + 72], {t2}",
+        // buf += a[4] * a[5] * 2^(64 * 9)
+        "mov {t0}, qword ptr [{buf} + 72]",
+        "mov {t1}, qword ptr [{buf} + 80]",
+        "mov rdx, qword ptr [{a} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 40]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        "mov qword ptr [{buf} + 72], {t0}",
+        "mov qword ptr [{buf} + 80], {t1}",
+        // buf = 2 * buf + the squares a[i]^2
+        "xor {lo:e}, {lo:e}",
+        "mov {t0}, qword ptr [{buf}]",
+        "mov {t1}, qword ptr [{buf} + 8]",
+        "mov rdx, qword ptr [{a}]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf}], {t0}",
+        "mov qword ptr [{buf} + 8], {t1}",
+        "mov {t0}, qword ptr [{buf} + 16]",
+        "mov {t1}, qword ptr [{buf} + 24]",
+        "mov rdx, qword ptr [{a} + 8]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 16], {t0}",
+        "mov qword ptr [{buf} + 24], {t1}",
+        "mov {t0}, qword ptr [{buf} + 32]",
+        "mov {t1}, qword ptr [{buf} + 40]",
+        "mov rdx, qword ptr [{a} + 16]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 32], {t0}",
+        "mov qword ptr [{buf} + 40], {t1}",
+        "mov {t0}, qword ptr [{buf} + 48]",
+        "mov {t1}, qword ptr [{buf} + 56]",
+        "mov rdx, qword ptr [{a} + 24]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 48], {t0}",
+        "mov qword ptr [{buf} + 56], {t1}",
+        "mov {t0}, qword ptr [{buf} + 64]",
+        "mov {t1}, qword ptr [{buf} + 72]",
+        "mov rdx, qword ptr [{a} + 32]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 64], {t0}",
+        "mov qword ptr [{buf} + 72], {t1}",
+        "mov {t0}, qword ptr [{buf} + 80]",
+        "mov {t1}, qword ptr [{buf} + 88]",
+        "mov rdx, qword ptr [{a} + 40]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 80], {t0}",
+        "mov qword ptr [{buf} + 88], {t1}",
+        // t = the low half of buf
+        "mov {t0}, qword ptr [{buf}]",
+        "mov {t1}, qword ptr [{buf} + 8]",
+        "mov {t2}, qword ptr [{buf} + 16]",
+        "mov {t3}, qword ptr [{buf} + 24]",
+        "mov {t4}, qword ptr [{buf} + 32]",
+        "mov {t5}, qword ptr [{buf} + 40]",
+        "xor {t6:e}, {t6:e}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t0}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t6}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t1}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t2}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t3}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t4}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t4}, {lo}",
+        "adcx {t5}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t3}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t5}",
+        "imul rdx, qword ptr [{q} + 48]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t5}, {lo}",
+        "adcx {t6}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t6}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 32]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 40]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // t += the high half of buf, which is less than 2q
+        "add {t6}, qword ptr [{buf} + 48]",
+        "adc {t0}, qword ptr [{buf} + 56]",
+        "adc {t1}, qword ptr [{buf} + 64]",
+        "adc {t2}, qword ptr [{buf} + 72]",
+        "adc {t3}, qword ptr [{buf} + 80]",
+        "adc {t4}, qword ptr [{buf} + 88]",
+        // a = t; t -= q; if t < q, t = a
+        "mov qword ptr [{a}], {t6}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "mov qword ptr [{a} + 32], {t3}",
+        "mov qword ptr [{a} + 40], {t4}",
+        "sub {t6}, qword ptr [{q}]",
+        "sbb {t0}, qword ptr [{q} + 8]",
+        "sbb {t1}, qword ptr [{q} + 16]",
+        "sbb {t2}, qword ptr [{q} + 24]",
+        "sbb {t3}, qword ptr [{q} + 32]",
+        "sbb {t4}, qword ptr [{q} + 40]",
+        "cmovc {t6}, qword ptr [{a}]",
+        "cmovc {t0}, qword ptr [{a} + 8]",
+        "cmovc {t1}, qword ptr [{a} + 16]",
+        "cmovc {t2}, qword ptr [{a} + 24]",
+        "cmovc {t3}, qword ptr [{a} + 32]",
+        "cmovc {t4}, qword ptr [{a} + 40]",
+        "mov qword ptr [{a}], {t6}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "mov qword ptr [{a} + 32], {t3}",
+        "mov qword ptr [{a} + 40], {t4}",
+        a = in(reg) a,
+        q = in(reg) q.as_ptr(),
+        buf = in(reg) buf.as_mut_ptr(),
+        t0 = out(reg) _,
+        t1 = out(reg) _,
+        t2 = out(reg) _,
+        t3 = out(reg) _,
+        t4 = out(reg) _,
+        t5 = out(reg) _,
+        t6 = out(reg) _,
+        hi = out(reg) _,
+        lo = out(reg) _,
+        out("rdx") _,
+        options(nostack),
+    );
+}
+
+/// Sets a = a * b / 2^256 mod q, where `q` holds the 4 limbs of the modulus
+/// followed by -q^-1 mod 2^64. Requires a, b < q.
+///
+/// # Safety
+/// The CPU must support ADX and BMI2, and `a` and `b` must point to 4 limbs
+/// each. They may point to the same limbs.
+#[inline(always)]
+pub(super) unsafe fn mont_mul_4(a: *mut u64, b: *const u64, q: &[u64; 5]) {
+    asm!(
+        // t = 0
+        "xor {t0:e}, {t0:e}",
+        "xor {t1:e}, {t1:e}",
+        "xor {t2:e}, {t2:e}",
+        "xor {t3:e}, {t3:e}",
+        "xor {t4:e}, {t4:e}",
+        // t += a * b[0]
+        "mov rdx, qword ptr [{b}]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t0}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // t += a * b[1]
+        "mov rdx, qword ptr [{b} + 8]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t1}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t += a * b[2]
+        "mov rdx, qword ptr [{b} + 16]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t2}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t += a * b[3]
+        "mov rdx, qword ptr [{b} + 24]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t3}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // a = t; t -= q; if t < q, t = a
+        "mov qword ptr [{a}], {t4}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "sub {t4}, qword ptr [{q}]",
+        "sbb {t0}, qword ptr [{q} + 8]",
+        "sbb {t1}, qword ptr [{q} + 16]",
+        "sbb {t2}, qword ptr [{q} + 24]",
+        "cmovc {t4}, qword ptr [{a}]",
+        "cmovc {t0}, qword ptr [{a} + 8]",
+        "cmovc {t1}, qword ptr [{a} + 16]",
+        "cmovc {t2}, qword ptr [{a} + 24]",
+        "mov qword ptr [{a}], {t4}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        a = in(reg) a,
+        b = in(reg) b,
+        q = in(reg) q.as_ptr(),
+        t0 = out(reg) _,
+        t1 = out(reg) _,
+        t2 = out(reg) _,
+        t3 = out(reg) _,
+        t4 = out(reg) _,
+        hi = out(reg) _,
+        lo = out(reg) _,
+        out("rdx") _,
+        options(nostack),
+    );
+}
+
+/// Sets a = a^2 / 2^256 mod q, where `q` holds the 4 limbs of the modulus
+/// followed by -q^-1 mod 2^64. Requires a < q.
+///
+/// # Safety
+/// The CPU must support ADX and BMI2, and `a` must point to 4 limbs.
+#[inline(always)]
+pub(super) unsafe fn mont_sqr_4(a: *mut u64, q: &[u64; 5]) {
+    let mut buf = [0u64; 8];
+    asm!(
+        // buf = the products a[i] * a[j] for i < j
+        // buf += a[0] * a[1..4] * 2^(64 * 1)
+        "mov {t0}, qword ptr [{buf} + 8]",
+        "mov {t1}, qword ptr [{buf} + 16]",
+        "mov {t2}, qword ptr [{buf} + 24]",
+        "mov {t3}, qword ptr [{buf} + 32]",
+        "mov rdx, qword ptr [{a}]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t3}, {lo}",
+        "mov qword ptr [{buf} + 8], {t0}",
+        "mov qword ptr [{buf} + 16], {t1}",
+        "mov qword ptr [{buf} + 24], {t2}",
+        "mov qword ptr [{buf} + 32], {t3}",
+        // buf += a[1] * a[2..4] * 2^(64 * 3)
+        "mov {t0}, qword ptr [{buf} + 24]",
+        "mov {t1}, qword ptr [{buf} + 32]",
+        "mov {t2}, qword ptr [{buf} + 40]",
+        "mov rdx, qword ptr [{a} + 8]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        "mov qword ptr [{buf} + 24], {t0}",
+        "mov qword ptr [{buf} + 32], {t1}",
+        "mov qword ptr [{buf} + 40], {t2}",
+        // buf += a[2] * a[3] * 2^(64 * 5)
+        "mov {t0}, qword ptr [{buf} + 40]",
+        "mov {t1}, qword ptr [{buf} + 48]",
+        "mov rdx, qword ptr [{a} + 16]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        "mov qword ptr [{buf} + 40], {t0}",
+        "mov qword ptr [{buf} + 48], {t1}",
+        // buf = 2 * buf + the squares a[i]^2
+        "xor {lo:e}, {lo:e}",
+        "mov {t0}, qword ptr [{buf}]",
+        "mov {t1}, qword ptr [{buf} + 8]",
+        "mov rdx, qword ptr [{a}]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf}], {t0}",
+        "mov qword ptr [{buf} + 8], {t1}",
+        "mov {t0}, qword ptr [{buf} + 16]",
+        "mov {t1}, qword ptr [{buf} + 24]",
+        "mov rdx, qword ptr [{a} + 8]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 16], {t0}",
+        "mov qword ptr [{buf} + 24], {t1}",
+        "mov {t0}, qword ptr [{buf} + 32]",
+        "mov {t1}, qword ptr [{buf} + 40]",
+        "mov rdx, qword ptr [{a} + 16]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 32], {t0}",
+        "mov qword ptr [{buf} + 40], {t1}",
+        "mov {t0}, qword ptr [{buf} + 48]",
+        "mov {t1}, qword ptr [{buf} + 56]",
+        "mov rdx, qword ptr [{a} + 24]",
+        "mulx {hi}, {lo}, rdx",
+        "adcx {t0}, {t0}",
+        "adcx {t1}, {t1}",
+        "adox {t0}, {lo}",
+        "adox {t1}, {hi}",
+        "mov qword ptr [{buf} + 48], {t0}",
+        "mov qword ptr [{buf} + 56], {t1}",
+        // t = the low half of buf
+        "mov {t0}, qword ptr [{buf}]",
+        "mov {t1}, qword ptr [{buf} + 8]",
+        "mov {t2}, qword ptr [{buf} + 16]",
+        "mov {t3}, qword ptr [{buf} + 24]",
+        "xor {t4:e}, {t4:e}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t0}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t4}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t1}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t0}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t2}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t2}, {lo}",
+        "adcx {t3}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t1}, {lo}",
+        // t = (t + m * q) / 2^64, where m = -t / q mod 2^64
+        "mov rdx, {t3}",
+        "imul rdx, qword ptr [{q} + 32]",
+        "xor {lo:e}, {lo:e}",
+        "mulx {hi}, {lo}, qword ptr [{q}]",
+        "adox {t3}, {lo}",
+        "adcx {t4}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 8]",
+        "adox {t4}, {lo}",
+        "adcx {t0}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 16]",
+        "adox {t0}, {lo}",
+        "adcx {t1}, {hi}",
+        "mulx {hi}, {lo}, qword ptr [{q} + 24]",
+        "adox {t1}, {lo}",
+        "adcx {t2}, {hi}",
+        "mov {lo:e}, 0",
+        "adox {t2}, {lo}",
+        // t += the high half of buf, which is less than 2q
+        "add {t4}, qword ptr [{buf} + 32]",
+        "adc {t0}, qword ptr [{buf} + 40]",
+        "adc {t1}, qword ptr [{buf} + 48]",
+        "adc {t2}, qword ptr [{buf} + 56]",
+        // a = t; t -= q; if t < q, t = a
+        "mov qword ptr [{a}], {t4}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        "sub {t4}, qword ptr [{q}]",
+        "sbb {t0}, qword ptr [{q} + 8]",
+        "sbb {t1}, qword ptr [{q} + 16]",
+        "sbb {t2}, qword ptr [{q} + 24]",
+        "cmovc {t4}, qword ptr [{a}]",
+        "cmovc {t0}, qword ptr [{a} + 8]",
+        "cmovc {t1}, qword ptr [{a} + 16]",
+        "cmovc {t2}, qword ptr [{a} + 24]",
+        "mov qword ptr [{a}], {t4}",
+        "mov qword ptr [{a} + 8], {t0}",
+        "mov qword ptr [{a} + 16], {t1}",
+        "mov qword ptr [{a} + 24], {t2}",
+        a = in(reg) a,
+        q = in(reg) q.as_ptr(),
+        buf = in(reg) buf.as_mut_ptr(),
+        t0 = out(reg) _,
+        t1 = out(reg) _,
+        t2 = out(reg) _,
+        t3 = out(reg) _,
+        t4 = out(reg) _,
+        hi = out(reg) _,
+        lo = out(reg) _,
+        out("rdx") _,
+        options(nostack),
+    );
+}
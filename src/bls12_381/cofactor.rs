@@ -12,6 +12,7 @@ Cofactor clearing for G1 and G2.
 // this will be faster than multiplying by the co-factor h
 
 use bls12_381::{G1, G2};
+use osswu_map::ClearH;
 use CurveProjective;
 
 /* *** addchain for 15132376222941642752 *** */
@@ -436,12 +437,6 @@ fn chain_h2_eff<PtT: CurveProjective>(tmpvar1: &mut PtT, tmpvar0: &PtT) {
     tmpvar1.sub_assign(&tmpvar2);
 }
 
-/// Trait implementing cofactor clearing for projective coords
-pub trait ClearH: CurveProjective {
-    /// Clear the cofactor in-place
-    fn clear_h(&mut self);
-}
-
 impl ClearH for G1 {
     // h_eff = 1 - z, therefore
     // out = in * chain_z(in)
@@ -470,9 +465,9 @@ impl ClearH for G2 {
 /// Tests for cofactor clearing
 #[cfg(test)]
 mod tests {
-    use super::ClearH;
     use bls12_381::{Fq, Fq2, FqRepr, FrRepr, G1, G2};
     use ff::PrimeField;
+    use osswu_map::ClearH;
     //    use rand::{thread_rng, Rand};
     use rand_core::SeedableRng;
     use CurveProjective;
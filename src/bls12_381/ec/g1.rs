@@ -1,7 +1,9 @@
-use super::super::{Bls12, Fq, Fq12, FqRepr, Fr, FrRepr};
+use super::super::{Bls12, Bls12_381Parameters, Fq, Fq12, FqRepr, Fr, FrRepr};
+use super::fq_from_eip2537;
 use super::g2::G2Affine;
+use core::fmt;
+use encoding_macros::{repr_from_be, repr_to_be};
 use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
-use std::fmt;
 use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, SubgroupCheck};
 curve_impl!(
     "G1",
@@ -12,9 +14,13 @@ curve_impl!(
     Fr,
     G1Uncompressed,
     G1Compressed,
-    G2Affine
+    G2Affine,
+    Bls12,
+    Fq12
 );
 
+group_bytes_impl!(G1, G1Affine, G1Compressed, 48, G1Uncompressed, 96);
+
 #[derive(Copy, Clone)]
 pub struct G1Uncompressed([u8; 96]);
 
@@ -86,15 +92,8 @@ impl EncodedPoint for G1Uncompressed {
             // Unset the three most significant bits.
             copy[0] &= 0x1f;
 
-            let mut x = FqRepr([0; 6]);
-            let mut y = FqRepr([0; 6]);
-
-            {
-                let mut reader = &copy[..];
-
-                x.read_be(&mut reader).unwrap();
-                y.read_be(&mut reader).unwrap();
-            }
+            let x: FqRepr = repr_from_be(&copy[..48]);
+            let y: FqRepr = repr_from_be(&copy[48..]);
 
             Ok(G1Affine {
                 x: Fq::from_repr(x)
@@ -113,10 +112,8 @@ impl EncodedPoint for G1Uncompressed {
             // is at infinity.
             res.0[0] |= 1 << 6;
         } else {
-            let mut writer = &mut res.0[..];
-
-            affine.x.into_repr().write_be(&mut writer).unwrap();
-            affine.y.into_repr().write_be(&mut writer).unwrap();
+            repr_to_be(&affine.x.into_repr(), &mut res.0[..48]);
+            repr_to_be(&affine.y.into_repr(), &mut res.0[48..]);
         }
 
         res
@@ -192,13 +189,7 @@ impl EncodedPoint for G1Compressed {
             // Unset the three most significant bits.
             copy[0] &= 0x1f;
 
-            let mut x = FqRepr([0; 6]);
-
-            {
-                let mut reader = &copy[..];
-
-                x.read_be(&mut reader).unwrap();
-            }
+            let x: FqRepr = repr_from_be(&copy[..]);
 
             // Interpret as Fq element.
             let x = Fq::from_repr(x)
@@ -215,11 +206,7 @@ impl EncodedPoint for G1Compressed {
             // is at infinity.
             res.0[0] |= 1 << 6;
         } else {
-            {
-                let mut writer = &mut res.0[..];
-
-                affine.x.into_repr().write_be(&mut writer).unwrap();
-            }
+            repr_to_be(&affine.x.into_repr(), &mut res.0[..]);
 
             let mut negy = affine.y;
             negy.negate();
@@ -238,6 +225,73 @@ impl EncodedPoint for G1Compressed {
     }
 }
 
+/// A point in the EIP-2537 encoding: the x and y coordinates, each padded to
+/// 64 bytes, with no flag bits. The point at infinity is encoded as all zeros.
+#[derive(Copy, Clone)]
+pub struct G1Eip2537([u8; 128]);
+
+impl AsRef<[u8]> for G1Eip2537 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G1Eip2537 {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G1Eip2537 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G1Eip2537 {
+    type Affine = G1Affine;
+
+    fn empty() -> Self {
+        G1Eip2537([0; 128])
+    }
+    fn size() -> usize {
+        128
+    }
+    fn into_affine(&self) -> Result<G1Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        if !affine.is_on_curve() {
+            Err(GroupDecodingError::NotOnCurve)
+        } else if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G1Affine, GroupDecodingError> {
+        // (0, 0) is not on the curve, so all zeros can only mean infinity
+        if self.0.iter().all(|b| *b == 0) {
+            return Ok(G1Affine::zero());
+        }
+
+        Ok(G1Affine {
+            x: fq_from_eip2537(&self.0[..64], "x coordinate")?,
+            y: fq_from_eip2537(&self.0[64..], "y coordinate")?,
+            infinity: false,
+        })
+    }
+    fn from_affine(affine: G1Affine) -> Self {
+        let mut res = Self::empty();
+
+        if !affine.is_zero() {
+            res.0[..64].copy_from_slice(&affine.x.to_eip2537());
+            res.0[64..].copy_from_slice(&affine.y.to_eip2537());
+        }
+
+        res
+    }
+}
+
 impl G1Affine {
     fn scale_by_cofactor(&self) -> G1 {
         // G1 cofactor = (x - 1)^2 / 3  = 76329603384216526031706109802092473003
@@ -292,18 +346,7 @@ impl G1 {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct G1Prepared(pub(crate) G1Affine);
-
-impl G1Prepared {
-    pub fn is_zero(&self) -> bool {
-        self.0.is_zero()
-    }
-
-    pub fn from_affine(p: G1Affine) -> Self {
-        G1Prepared(p)
-    }
-}
+pub type G1Prepared = ::bls12::G1Prepared<Bls12_381Parameters>;
 
 mod subgroup_check {
 
@@ -324,7 +367,7 @@ mod subgroup_check {
 
     #[test]
     fn test_g1_subgroup_check() {
-        use bls12_381::ClearH;
+        use osswu_map::ClearH;
         let mut rng = rand_xorshift::XorShiftRng::from_seed([
             0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
             0xbc, 0xe5,
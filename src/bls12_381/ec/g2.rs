@@ -1,7 +1,9 @@
-use super::super::{Bls12, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
+use super::super::{Bls12, Bls12_381Parameters, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
+use super::fq_from_eip2537;
 use super::g1::G1Affine;
+use core::fmt;
+use encoding_macros::{repr_from_be, repr_to_be};
 use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
-use std::fmt;
 use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, SubgroupCheck};
 
 curve_impl!(
@@ -13,9 +15,13 @@ curve_impl!(
     Fr,
     G2Uncompressed,
     G2Compressed,
-    G1Affine
+    G1Affine,
+    Bls12,
+    Fq12
 );
 
+group_bytes_impl!(G2, G2Affine, G2Compressed, 96, G2Uncompressed, 192);
+
 #[derive(Copy, Clone)]
 pub struct G2Uncompressed([u8; 192]);
 
@@ -87,19 +93,10 @@ impl EncodedPoint for G2Uncompressed {
             // Unset the three most significant bits.
             copy[0] &= 0x1f;
 
-            let mut x_c0 = FqRepr([0; 6]);
-            let mut x_c1 = FqRepr([0; 6]);
-            let mut y_c0 = FqRepr([0; 6]);
-            let mut y_c1 = FqRepr([0; 6]);
-
-            {
-                let mut reader = &copy[..];
-
-                x_c1.read_be(&mut reader).unwrap();
-                x_c0.read_be(&mut reader).unwrap();
-                y_c1.read_be(&mut reader).unwrap();
-                y_c0.read_be(&mut reader).unwrap();
-            }
+            let x_c1: FqRepr = repr_from_be(&copy[..48]);
+            let x_c0: FqRepr = repr_from_be(&copy[48..96]);
+            let y_c1: FqRepr = repr_from_be(&copy[96..144]);
+            let y_c0: FqRepr = repr_from_be(&copy[144..]);
 
             Ok(G2Affine {
                 x: Fq2 {
@@ -130,12 +127,10 @@ impl EncodedPoint for G2Uncompressed {
             // is at infinity.
             res.0[0] |= 1 << 6;
         } else {
-            let mut writer = &mut res.0[..];
-
-            affine.x.c1.into_repr().write_be(&mut writer).unwrap();
-            affine.x.c0.into_repr().write_be(&mut writer).unwrap();
-            affine.y.c1.into_repr().write_be(&mut writer).unwrap();
-            affine.y.c0.into_repr().write_be(&mut writer).unwrap();
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..48]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[48..96]);
+            repr_to_be(&affine.y.c1.into_repr(), &mut res.0[96..144]);
+            repr_to_be(&affine.y.c0.into_repr(), &mut res.0[144..]);
         }
 
         res
@@ -211,15 +206,8 @@ impl EncodedPoint for G2Compressed {
             // Unset the three most significant bits.
             copy[0] &= 0x1f;
 
-            let mut x_c1 = FqRepr([0; 6]);
-            let mut x_c0 = FqRepr([0; 6]);
-
-            {
-                let mut reader = &copy[..];
-
-                x_c1.read_be(&mut reader).unwrap();
-                x_c0.read_be(&mut reader).unwrap();
-            }
+            let x_c1: FqRepr = repr_from_be(&copy[..48]);
+            let x_c0: FqRepr = repr_from_be(&copy[48..]);
 
             // Interpret as Fq element.
             let x = Fq2 {
@@ -242,12 +230,8 @@ impl EncodedPoint for G2Compressed {
             // is at infinity.
             res.0[0] |= 1 << 6;
         } else {
-            {
-                let mut writer = &mut res.0[..];
-
-                affine.x.c1.into_repr().write_be(&mut writer).unwrap();
-                affine.x.c0.into_repr().write_be(&mut writer).unwrap();
-            }
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..48]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[48..]);
 
             let mut negy = affine.y;
             negy.negate();
@@ -266,6 +250,79 @@ impl EncodedPoint for G2Compressed {
     }
 }
 
+/// A point in the EIP-2537 encoding: the x and y coordinates in the 128-byte
+/// encoding of `Fq2`, with no flag bits. The point at infinity is encoded as all zeros.
+#[derive(Copy, Clone)]
+pub struct G2Eip2537([u8; 256]);
+
+impl AsRef<[u8]> for G2Eip2537 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G2Eip2537 {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G2Eip2537 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G2Eip2537 {
+    type Affine = G2Affine;
+
+    fn empty() -> Self {
+        G2Eip2537([0; 256])
+    }
+    fn size() -> usize {
+        256
+    }
+    fn into_affine(&self) -> Result<G2Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        if !affine.is_on_curve() {
+            Err(GroupDecodingError::NotOnCurve)
+        } else if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G2Affine, GroupDecodingError> {
+        // (0, 0) is not on the curve, so all zeros can only mean infinity
+        if self.0.iter().all(|b| *b == 0) {
+            return Ok(G2Affine::zero());
+        }
+
+        Ok(G2Affine {
+            x: Fq2 {
+                c0: fq_from_eip2537(&self.0[..64], "x coordinate (c0)")?,
+                c1: fq_from_eip2537(&self.0[64..128], "x coordinate (c1)")?,
+            },
+            y: Fq2 {
+                c0: fq_from_eip2537(&self.0[128..192], "y coordinate (c0)")?,
+                c1: fq_from_eip2537(&self.0[192..], "y coordinate (c1)")?,
+            },
+            infinity: false,
+        })
+    }
+    fn from_affine(affine: G2Affine) -> Self {
+        let mut res = Self::empty();
+
+        if !affine.is_zero() {
+            res.0[..128].copy_from_slice(&affine.x.to_eip2537());
+            res.0[128..].copy_from_slice(&affine.y.to_eip2537());
+        }
+
+        res
+    }
+}
+
 impl G2Affine {
     fn get_generator() -> Self {
         G2Affine {
@@ -338,11 +395,7 @@ impl G2 {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct G2Prepared {
-    pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
-    pub(crate) infinity: bool,
-}
+pub type G2Prepared = ::bls12::G2Prepared<Bls12_381Parameters>;
 
 mod subgroup_check {
     use super::G2Affine;
@@ -360,7 +413,8 @@ mod subgroup_check {
 
     #[test]
     fn test_g2_subgroup_check() {
-        use bls12_381::{ClearH, G2};
+        use bls12_381::G2;
+        use osswu_map::ClearH;
         use CurveProjective;
         let mut rng = rand_xorshift::XorShiftRng::from_seed([
             0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
@@ -0,0 +1,226 @@
+/*!
+ This module implements the BLS12-381 precompiles of EIP-2537 on raw bytes:
+ G1ADD, G1MSM, G2ADD, G2MSM, PAIRING_CHECK, MAP_FP_TO_G1 and MAP_FP2_TO_G2.
+
+ Inputs and outputs use the EIP-2537 encodings of `Fq`, `Fq2`, `G1Eip2537`
+ and `G2Eip2537`; scalars are 32-byte big-endian integers, which need not be
+ reduced modulo the group order. An error means that the call fails and
+ consumes all of its gas, as it does for inputs of the wrong length, field
+ elements that are not canonical, points that are not on the curve and,
+ for the multi-scalar multiplications and the pairing, points that are not
+ in the prime-order subgroup. The addition precompiles deliberately skip the
+ subgroup check. Gas is left to the caller.
+*/
+
+use super::{Bls12, Fq, Fq12, Fq2, Fr, FrRepr, G1Affine, G1Eip2537, G2Affine, G2Eip2537, G1, G2};
+use alloc::vec::Vec;
+use core::fmt;
+use encoding_macros::repr_from_be;
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use map_to_curve::MapToCurve;
+#[cfg(feature = "std")]
+use std::error::Error;
+use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+
+/// The size of an encoded scalar in the multi-scalar multiplication inputs.
+const SCALAR_SIZE: usize = 32;
+
+/// An error that makes a precompile call fail.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PrecompileError {
+    /// The input length is not valid for the precompile.
+    InvalidInputLength,
+    /// The 16 padding bytes of a field element were not zero.
+    NonZeroPadding,
+    /// A field element was not smaller than the modulus.
+    NotInField,
+    /// A point was not on the curve.
+    NotOnCurve,
+    /// A point was not in the prime-order subgroup.
+    NotInSubgroup,
+}
+
+#[cfg(feature = "std")]
+impl Error for PrecompileError {
+    fn description(&self) -> &str {
+        match *self {
+            PrecompileError::InvalidInputLength => "invalid input length",
+            PrecompileError::NonZeroPadding => "field element has nonzero top bytes",
+            PrecompileError::NotInField => "field element is not smaller than the modulus",
+            PrecompileError::NotOnCurve => "point is not on the curve",
+            PrecompileError::NotInSubgroup => "point is not in the prime-order subgroup",
+        }
+    }
+}
+
+impl fmt::Display for PrecompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl From<GroupDecodingError> for PrecompileError {
+    fn from(err: GroupDecodingError) -> Self {
+        match err {
+            GroupDecodingError::NotOnCurve => PrecompileError::NotOnCurve,
+            GroupDecodingError::NotInSubgroup => PrecompileError::NotInSubgroup,
+            GroupDecodingError::UnexpectedInformation => PrecompileError::NonZeroPadding,
+            // the EIP-2537 encodings have no compression mode to get wrong
+            GroupDecodingError::CoordinateDecodingError(..)
+            | GroupDecodingError::UnexpectedCompressionMode => PrecompileError::NotInField,
+        }
+    }
+}
+
+/// Decodes a point, checking that it is on the curve but not that it is in the subgroup.
+fn decode_on_curve<E: EncodedPoint>(
+    bytes: &[u8],
+    is_on_curve: fn(&E::Affine) -> bool,
+) -> Result<E::Affine, PrecompileError> {
+    let mut encoded = E::empty();
+    encoded.as_mut().copy_from_slice(bytes);
+    let p = encoded.into_affine_unchecked()?;
+    if is_on_curve(&p) {
+        Ok(p)
+    } else {
+        Err(PrecompileError::NotOnCurve)
+    }
+}
+
+/// Decodes a point, checking that it is in the prime-order subgroup.
+fn decode_in_subgroup<E: EncodedPoint>(bytes: &[u8]) -> Result<E::Affine, PrecompileError> {
+    let mut encoded = E::empty();
+    encoded.as_mut().copy_from_slice(bytes);
+    Ok(encoded.into_affine()?)
+}
+
+/// Decodes a base field element in the 64-byte encoding.
+fn decode_fq(bytes: &[u8]) -> Result<Fq, PrecompileError> {
+    if bytes[..16].iter().any(|b| *b != 0) {
+        return Err(PrecompileError::NonZeroPadding);
+    }
+    let mut buf = [0u8; 64];
+    buf.copy_from_slice(bytes);
+    Fq::from_eip2537(&buf).ok_or(PrecompileError::NotInField)
+}
+
+/// Decodes a 32-byte big-endian scalar and reduces it modulo the group order,
+/// which does not change its product with a point in the subgroup.
+fn decode_scalar(bytes: &[u8]) -> [u64; 4] {
+    let mut repr: FrRepr = repr_from_be(bytes);
+    // 2^256 < 3r, so at most two subtractions are needed
+    while repr >= Fr::char() {
+        repr.sub_noborrow(&Fr::char());
+    }
+    repr.0
+}
+
+fn encode<E: EncodedPoint>(p: E::Affine) -> Vec<u8> {
+    E::from_affine(p).as_ref().to_vec()
+}
+
+/// Adds two points that are on the curve but not necessarily in the subgroup.
+fn add<E: EncodedPoint>(
+    input: &[u8],
+    is_on_curve: fn(&E::Affine) -> bool,
+) -> Result<Vec<u8>, PrecompileError> {
+    let size = E::size();
+    if input.len() != 2 * size {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let p = decode_on_curve::<E>(&input[..size], is_on_curve)?;
+    let q = decode_on_curve::<E>(&input[size..], is_on_curve)?;
+    let mut sum = p.into_projective();
+    sum.add_assign_mixed(&q);
+    Ok(encode::<E>(sum.into_affine()))
+}
+
+/// Computes the multi-scalar multiplication of `k >= 1` pairs of a point and a scalar.
+fn msm<E: EncodedPoint>(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let pair_size = E::size() + SCALAR_SIZE;
+    if input.is_empty() || !input.len().is_multiple_of(pair_size) {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let mut points = Vec::with_capacity(input.len() / pair_size);
+    let mut scalars = Vec::with_capacity(input.len() / pair_size);
+    for pair in input.chunks(pair_size) {
+        points.push(decode_in_subgroup::<E>(&pair[..E::size()])?);
+        scalars.push(decode_scalar(&pair[E::size()..]));
+    }
+    let scalar_refs: Vec<&[u64; 4]> = scalars.iter().collect();
+    let window = E::Affine::find_pippinger_window(points.len());
+    let res = E::Affine::sum_of_products_pippinger(&points, &scalar_refs, window);
+    Ok(encode::<E>(res.into_affine()))
+}
+
+/// G1ADD: adds two points of G1, given as 256 bytes, and returns their sum in 128 bytes.
+/// The points must be on the curve but need not be in the subgroup.
+pub fn g1_add(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    add::<G1Eip2537>(input, G1Affine::is_on_curve)
+}
+
+/// G2ADD: adds two points of G2, given as 512 bytes, and returns their sum in 256 bytes.
+/// The points must be on the curve but need not be in the subgroup.
+pub fn g2_add(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    add::<G2Eip2537>(input, G2Affine::is_on_curve)
+}
+
+/// G1MSM: given `k >= 1` pairs of a point of G1 and a scalar, 160 bytes each,
+/// returns the sum of the products in 128 bytes.
+pub fn g1_msm(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    msm::<G1Eip2537>(input)
+}
+
+/// G2MSM: given `k >= 1` pairs of a point of G2 and a scalar, 288 bytes each,
+/// returns the sum of the products in 256 bytes.
+pub fn g2_msm(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    msm::<G2Eip2537>(input)
+}
+
+/// PAIRING_CHECK: given `k >= 1` pairs of a point of G1 and a point of G2, 384 bytes each,
+/// returns 1 as a 32-byte big-endian integer if the product of their pairings is one,
+/// and 0 otherwise. Pairs with a point at infinity contribute one to the product.
+pub fn pairing_check(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let pair_size = G1Eip2537::size() + G2Eip2537::size();
+    if input.is_empty() || !input.len().is_multiple_of(pair_size) {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let mut ps = Vec::with_capacity(input.len() / pair_size);
+    let mut qs = Vec::with_capacity(input.len() / pair_size);
+    for pair in input.chunks(pair_size) {
+        ps.push(decode_in_subgroup::<G1Eip2537>(&pair[..G1Eip2537::size()])?);
+        qs.push(decode_in_subgroup::<G2Eip2537>(&pair[G1Eip2537::size()..])?);
+    }
+    let mut out = vec![0u8; 32];
+    if Bls12::pairing_multi_product(&ps, &qs) == Fq12::one() {
+        out[31] = 1;
+    }
+    Ok(out)
+}
+
+/// MAP_FP_TO_G1: maps an element of Fq, given as 64 bytes, to G1 with the simplified
+/// SWU map and cofactor clearing of RFC 9380, and returns the point in 128 bytes.
+pub fn map_fp_to_g1(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != 64 {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let u = decode_fq(input)?;
+    Ok(encode::<G1Eip2537>(
+        <G1 as MapToCurve<G1>>::map_to_curve(&u).into_affine(),
+    ))
+}
+
+/// MAP_FP2_TO_G2: maps an element of Fq2, given as 128 bytes, to G2 with the simplified
+/// SWU map and cofactor clearing of RFC 9380, and returns the point in 256 bytes.
+pub fn map_fp2_to_g2(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != 128 {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let u = Fq2 {
+        c0: decode_fq(&input[..64])?,
+        c1: decode_fq(&input[64..])?,
+    };
+    Ok(encode::<G2Eip2537>(
+        <G2 as MapToCurve<G2>>::map_to_curve(&u).into_affine(),
+    ))
+}
@@ -1,9 +1,14 @@
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+use super::asm;
 use super::fq2::Fq2;
+use super::inversion::chain_pm2;
+use super::osswu_map::chain::chain_pm3div4;
 use digest::generic_array::{typenum::U64, GenericArray};
-use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+use ff::{Field, LegendreSymbol, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
 use hash_to_field::BaseFromRO;
+use jacobi::jacobi_vartime;
+use safegcd::{inverse_vartime, ModInfo};
 use signum::{Sgn0Result, Signum0};
-use std::io::{Cursor, Read};
 
 // B coefficient of BLS12-381 curve, 4.
 pub const B_COEFF: Fq = Fq(FqRepr([
@@ -437,7 +442,18 @@ pub const FROBENIUS_COEFF_FQ12_C1: [Fq2; 12] = [
     },
 ];
 
+// sqrt(-Z) for Z = 11, the constant of the SSWU map to G1
+const SQRT_M_Z: Fq = Fq(FqRepr([
+    0xf37b0ced8fb71e24,
+    0xf02dc8a4535a8779,
+    0x732ed835f7eb14ea,
+    0x524ca41ecb2bce0d,
+    0x095e3801e90b5fc1,
+    0x0252ad055472a90e,
+]));
+
 // -((2**384) mod q) mod q
+#[cfg(test)]
 pub const NEGATIVE_ONE: Fq = Fq(FqRepr([
     0x43f5fffffffcaaae,
     0x32b7fff2ed47fffd,
@@ -461,13 +477,176 @@ pub const P_M1_OVER2: Fq = Fq(FqRepr([
 #[derive(PrimeField, Zeroize)]
 #[PrimeFieldModulus = "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787"]
 #[PrimeFieldGenerator = "2"]
+#[cfg_attr(
+    all(feature = "asm", target_arch = "x86_64"),
+    PrimeFieldKernels = "kernels"
+)]
 pub struct Fq(pub(super) FqRepr);
 
+prime_field_bytes_impl!(Fq, FqRepr, 48);
+
+impl Fq {
+    /// Returns the EIP-2537 encoding of the element: 16 zero bytes followed
+    /// by the big-endian encoding.
+    pub fn to_eip2537(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[16..].copy_from_slice(&self.to_bytes());
+        out
+    }
+
+    /// Decodes an EIP-2537 encoding, returning None unless the padding is zero
+    /// and the element is smaller than the modulus.
+    pub fn from_eip2537(bytes: &[u8; 64]) -> Option<Fq> {
+        if bytes[..16].iter().any(|b| *b != 0) {
+            return None;
+        }
+        let mut buf = [0u8; 48];
+        buf.copy_from_slice(&bytes[16..]);
+        Fq::from_bytes(&buf)
+    }
+}
+
+// R^3 mod p, with R = 2^384
+const R3: FqRepr = FqRepr([
+    0xed48ac6bd94ca1e0,
+    0x315f831e03a7adf8,
+    0x9a53352a615e29dd,
+    0x34c04e5e921e1761,
+    0x2512d43565724728,
+    0x0aa6346091755d4d,
+]);
+
+const MODINFO: ModInfo<7> = ModInfo::new(&MODULUS.0);
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+// The modulus followed by -p^-1 mod 2^64, for the assembly kernels
+const MODULUS_INV: [u64; 7] = [
+    MODULUS.0[0],
+    MODULUS.0[1],
+    MODULUS.0[2],
+    MODULUS.0[3],
+    MODULUS.0[4],
+    MODULUS.0[5],
+    INV,
+];
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+/// The assembly kernels called by the derived `Field` impl
+mod kernels {
+    use super::asm;
+    use super::MODULUS_INV;
+
+    #[inline(always)]
+    pub(super) fn available() -> bool {
+        asm::has_adx()
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn mul_assign(a: &mut [u64; 6], b: &[u64; 6]) {
+        asm::mont_mul_6(a.as_mut_ptr(), b.as_ptr(), &MODULUS_INV)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn square(a: &mut [u64; 6]) {
+        asm::mont_sqr_6(a.as_mut_ptr(), &MODULUS_INV)
+    }
+}
+
 /// # Safety
 pub const unsafe fn transmute(r: FqRepr) -> Fq {
     Fq(r)
 }
 
+impl Fq {
+    /// Returns `b` if `choice` is set and `a` otherwise, without branching
+    /// on `choice`.
+    pub(crate) fn cmov(a: &Fq, b: &Fq, choice: bool) -> Fq {
+        let mask = 0u64.wrapping_sub(choice as u64);
+        let mut res = *a;
+        for (r, b) in (res.0).0.iter_mut().zip((b.0).0.iter()) {
+            *r ^= mask & (*r ^ b);
+        }
+        res
+    }
+
+    /// Compares two elements without branching on their limbs.
+    pub(crate) fn ct_eq(&self, other: &Fq) -> bool {
+        let diff = (self.0)
+            .0
+            .iter()
+            .zip((other.0).0.iter())
+            .fold(0u64, |acc, (a, b)| acc | (a ^ b));
+        // the top bit of diff | -diff is set exactly when diff is nonzero
+        (diff | diff.wrapping_neg()) >> 63 == 0
+    }
+
+    /// Computes the square root of a ratio with the optimized procedure for
+    /// q = 3 mod 4 of RFC 9380, section F.2.1.2, in constant time.
+    /// Returns (true, sqrt(u / v)) if u / v is square, and (false, sqrt(Z * u / v))
+    /// otherwise, where Z = 11 is the constant of the SSWU map to G1.
+    /// Requires v != 0; for v = 0 the result is (u == 0, 0).
+    pub fn sqrt_ratio(u: &Fq, v: &Fq) -> (bool, Fq) {
+        // y1 = u v (u v^3) ^ ((p - 3) / 4)
+        let mut tv2 = *u;
+        tv2.mul_assign(v); // u v
+        let mut tv1 = *v;
+        tv1.square(); // v^2
+        tv1.mul_assign(&tv2); // u v^3
+        let tmp = tv1;
+        chain_pm3div4(&mut tv1, &tmp); // (u v^3) ^ ((p - 3) / 4)
+        let mut y1 = tv1;
+        y1.mul_assign(&tv2);
+
+        // if u / v is not square, y1^2 v = -u, so (sqrt(-Z) y1)^2 = Z u / v
+        let mut y2 = y1;
+        y2.mul_assign(&SQRT_M_Z);
+
+        let mut tv3 = y1;
+        tv3.square();
+        tv3.mul_assign(v);
+        let is_qr = tv3.ct_eq(u);
+        (is_qr, Fq::cmov(&y2, &y1, is_qr))
+    }
+
+    /// Inverts the element by raising it to the power p - 2, using an addition
+    /// chain whose sequence of operations does not depend on the input.
+    /// Returns None for zero.
+    pub fn inverse_ct(&self) -> Option<Fq> {
+        if self.is_zero() {
+            None
+        } else {
+            let mut res = *self;
+            chain_pm2(&mut res, self);
+            Some(res)
+        }
+    }
+
+    /// Inverts the element with the safegcd algorithm of Bernstein and Yang.
+    /// This is faster than the other inversion methods, but its running time
+    /// depends on the input, so it must only be used on public data.
+    /// Returns None for zero.
+    pub fn inverse_vartime(&self) -> Option<Fq> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // The representation of a is a R, whose inverse a^-1 R^-1 is turned
+        // back into the representation a^-1 R by a multiplication with R^3.
+        let mut res = Fq(FqRepr::default());
+        inverse_vartime(&(self.0).0, &MODINFO, &mut (res.0).0);
+        res.mul_assign(&Fq(R3));
+        Some(res)
+    }
+
+    /// Computes the Legendre symbol with the binary Jacobi symbol algorithm.
+    /// This gives the same result as `SqrtField::legendre`, but is faster and
+    /// its running time depends on the input, so it must only be used on
+    /// public data.
+    pub fn legendre_vartime(&self) -> LegendreSymbol {
+        jacobi_vartime(self.into_repr(), MODULUS)
+    }
+}
+
 impl BaseFromRO for Fq {
     type BaseLength = U64;
 
@@ -485,15 +664,13 @@ impl BaseFromRO for Fq {
         ]));
 
         // unwraps are safe here: we only use 32 bytes at a time, which is strictly less than p
-        let mut repr = FqRepr::default();
-        repr.read_be(Cursor::new([0; 16]).chain(Cursor::new(&okm[..32])))
-            .unwrap();
-        let mut elm = Fq::from_repr(repr).unwrap();
+        let mut buf = [0u8; 48];
+        buf[16..].copy_from_slice(&okm[..32]);
+        let mut elm = Fq::from_bytes(&buf).unwrap();
         elm.mul_assign(&F_2_256);
 
-        repr.read_be(Cursor::new([0; 16]).chain(Cursor::new(&okm[32..])))
-            .unwrap();
-        let elm2 = Fq::from_repr(repr).unwrap();
+        buf[16..].copy_from_slice(&okm[32..]);
+        let elm2 = Fq::from_bytes(&buf).unwrap();
         elm.add_assign(&elm2);
         elm
     }
@@ -1241,7 +1418,7 @@ use rand_core::SeedableRng;
 
 #[test]
 fn test_fq_repr_ordering() {
-    use std::cmp::Ordering;
+    use core::cmp::Ordering;
 
     fn assert_equality(a: FqRepr, b: FqRepr) {
         assert_eq!(a, b);
@@ -2041,6 +2218,75 @@ fn test_fq_inverse() {
     }
 }
 
+#[test]
+fn test_fq_inverse_ct_vartime() {
+    assert!(Fq::zero().inverse_ct().is_none());
+    assert!(Fq::zero().inverse_vartime().is_none());
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut minus_one = Fq::one();
+    minus_one.negate();
+    let mut inputs = vec![Fq::one(), minus_one];
+    inputs.extend((0..1000).map(|_| Fq::random(&mut rng)));
+
+    for a in inputs {
+        // All three inversion methods agree
+        let ainv = a.inverse();
+        assert_eq!(a.inverse_ct(), ainv);
+        assert_eq!(a.inverse_vartime(), ainv);
+    }
+}
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+#[test]
+fn test_fq_mul_asm() {
+    if !asm::has_adx() {
+        return;
+    }
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut minus_one = Fq::one();
+    minus_one.negate();
+    // the largest representation, which maximizes the intermediate values
+    let mut max = MODULUS;
+    max.0[0] -= 1;
+    let max = Fq(max);
+    let mut inputs = vec![Fq::zero(), Fq::one(), minus_one, max];
+    inputs.extend((0..100).map(|_| Fq::random(&mut rng)));
+
+    for a in inputs.iter() {
+        for b in inputs.iter() {
+            // Compare the kernel with the portable implementation
+            let mut expected = *a;
+            expected.mul_assign_portable(b);
+            let mut c = *a;
+            unsafe { asm::mont_mul_6((c.0).0.as_mut_ptr(), (b.0).0.as_ptr(), &MODULUS_INV) };
+            assert_eq!(c, expected);
+            // and with the derived method, which dispatches to it
+            let mut c = *a;
+            c.mul_assign(b);
+            assert_eq!(c, expected);
+        }
+
+        let mut expected = *a;
+        expected.square_portable();
+        let mut c = *a;
+        unsafe { asm::mont_sqr_6((c.0).0.as_mut_ptr(), &MODULUS_INV) };
+        assert_eq!(c, expected);
+        let mut c = *a;
+        c.square();
+        assert_eq!(c, expected);
+    }
+}
+
 #[test]
 fn test_fq_double() {
     let mut rng = rand_xorshift::XorShiftRng::from_seed([
@@ -2141,6 +2387,59 @@ fn test_fq_sqrt() {
     }
 }
 
+#[test]
+fn test_fq_sqrt_ratio() {
+    use ff::{LegendreSymbol, SqrtField};
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let z = Fq::from_repr(FqRepr::from(11)).unwrap();
+
+    let mut m_z = z;
+    m_z.negate();
+    let mut tmp = SQRT_M_Z;
+    tmp.square();
+    assert_eq!(tmp, m_z);
+
+    assert_eq!(Fq::sqrt_ratio(&Fq::one(), &Fq::zero()), (false, Fq::zero()));
+    assert_eq!(Fq::sqrt_ratio(&Fq::zero(), &Fq::one()), (true, Fq::zero()));
+
+    for _ in 0..1000 {
+        let a = Fq::random(&mut rng);
+        let v = Fq::random(&mut rng);
+
+        // u = a^2 * v, so sqrt(u / v) = +-a
+        let mut u = a;
+        u.square();
+        u.mul_assign(&v);
+        let (is_square, mut b) = Fq::sqrt_ratio(&u, &v);
+        assert!(is_square);
+        if b != a {
+            b.negate();
+        }
+        assert_eq!(a, b);
+
+        // u / v is square iff u * v is, and otherwise b^2 = Z * u / v
+        let u = Fq::random(&mut rng);
+        let mut uv = u;
+        uv.mul_assign(&v);
+        let (is_square, mut b) = Fq::sqrt_ratio(&u, &v);
+        assert_eq!(
+            is_square,
+            uv.legendre() != LegendreSymbol::QuadraticNonResidue
+        );
+        b.square();
+        b.mul_assign(&v);
+        let mut expected = u;
+        if !is_square {
+            expected.mul_assign(&z);
+        }
+        assert_eq!(b, expected);
+    }
+}
+
 #[test]
 fn test_fq_from_into_repr() {
     // q + 1 should not be in the field
@@ -2207,6 +2506,8 @@ fn test_fq_from_into_repr() {
 
 #[test]
 fn test_fq_repr_display() {
+    use std::string::ToString;
+
     assert_eq!(
         format!("{}", FqRepr([0xa956babf9301ea24, 0x39a8f184f3535c7b, 0xb38d35b3f6779585, 0x676cc4eef4c46f2c, 0xb1d4aad87651e694, 0x1947f0d5f4fe325a])),
         "0x1947f0d5f4fe325ab1d4aad87651e694676cc4eef4c46f2cb38d35b3f677958539a8f184f3535c7ba956babf9301ea24".to_string()
@@ -2227,6 +2528,8 @@ fn test_fq_repr_display() {
 
 #[test]
 fn test_fq_display() {
+    use std::string::ToString;
+
     assert_eq!(
         format!("{}", Fq::from_repr(FqRepr([0xa956babf9301ea24, 0x39a8f184f3535c7b, 0xb38d35b3f6779585, 0x676cc4eef4c46f2c, 0xb1d4aad87651e694, 0x1947f0d5f4fe325a])).unwrap()),
         "Fq(0x1947f0d5f4fe325ab1d4aad87651e694676cc4eef4c46f2cb38d35b3f677958539a8f184f3535c7ba956babf9301ea24)".to_string()
@@ -2328,6 +2631,33 @@ fn test_fq_legendre() {
     assert_eq!(QuadraticResidue, Fq::from_repr(e).unwrap().legendre());
 }
 
+#[test]
+fn test_fq_legendre_vartime() {
+    use ff::SqrtField;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut minus_one = Fq::one();
+    minus_one.negate();
+    let mut inputs = vec![Fq::zero(), Fq::one(), minus_one];
+    inputs.extend((1..64).map(|i| Fq::from_repr(FqRepr::from(i)).unwrap()));
+    for _ in 0..1000 {
+        // a square, and (with overwhelming probability) a nonsquare times it
+        let mut a = Fq::random(&mut rng);
+        a.square();
+        inputs.push(a);
+        a.mul_assign(&Fq::random(&mut rng));
+        inputs.push(a);
+    }
+
+    for a in inputs {
+        assert_eq!(a.legendre_vartime(), a.legendre());
+    }
+}
+
 #[test]
 fn test_fq_hash_to_field_xof_shake128() {
     use hash_to_field::{hash_to_field, ExpandMsgXof};
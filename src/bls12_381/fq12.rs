@@ -1,6 +1,7 @@
 use super::fq::FROBENIUS_COEFF_FQ12_C1;
 use super::fq2::Fq2;
 use super::fq6::Fq6;
+use bls12::Fq12Ops;
 use ff::Field;
 use rand_core::RngCore;
 
@@ -11,8 +12,10 @@ pub struct Fq12 {
     pub c1: Fq6,
 }
 
-impl ::std::fmt::Display for Fq12 {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+extension_field_bytes_impl!(Fq12, 576, Fq6, 288, c0, c1);
+
+impl ::core::fmt::Display for Fq12 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "Fq12({} + {} * w)", self.c0, self.c1)
     }
 }
@@ -37,10 +40,42 @@ impl Fq12 {
         self.c0.mul_by_nonresidue();
         self.c0.add_assign(&aa);
     }
+
+    pub fn mul_by_034(&mut self, c0: &Fq2, c3: &Fq2, c4: &Fq2) {
+        let mut aa = self.c0;
+        aa.c0.mul_assign(c0);
+        aa.c1.mul_assign(c0);
+        aa.c2.mul_assign(c0);
+        let mut bb = self.c1;
+        bb.mul_by_01(c3, c4);
+        let mut o = *c0;
+        o.add_assign(c3);
+        self.c1.add_assign(&self.c0);
+        self.c1.mul_by_01(&o, c4);
+        self.c1.sub_assign(&aa);
+        self.c1.sub_assign(&bb);
+        self.c0 = bb;
+        self.c0.mul_by_nonresidue();
+        self.c0.add_assign(&aa);
+    }
+}
+
+impl Fq12Ops<Fq2> for Fq12 {
+    fn conjugate(&mut self) {
+        Fq12::conjugate(self);
+    }
+
+    fn mul_by_014(&mut self, c0: &Fq2, c1: &Fq2, c4: &Fq2) {
+        Fq12::mul_by_014(self, c0, c1, c4);
+    }
+
+    fn mul_by_034(&mut self, c0: &Fq2, c3: &Fq2, c4: &Fq2) {
+        Fq12::mul_by_034(self, c0, c3, c4);
+    }
 }
 
 impl Field for Fq12 {
-    fn random<R: RngCore + ?std::marker::Sized>(rng: &mut R) -> Self {
+    fn random<R: RngCore + ?core::marker::Sized>(rng: &mut R) -> Self {
         Fq12 {
             c0: Fq6::random(rng),
             c1: Fq6::random(rng),
@@ -180,6 +215,37 @@ fn test_fq12_mul_by_014() {
     }
 }
 
+#[test]
+fn test_fq12_mul_by_034() {
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..1000 {
+        let c0 = Fq2::random(&mut rng);
+        let c3 = Fq2::random(&mut rng);
+        let c4 = Fq2::random(&mut rng);
+        let mut a = Fq12::random(&mut rng);
+        let mut b = a;
+
+        a.mul_by_034(&c0, &c3, &c4);
+        b.mul_assign(&Fq12 {
+            c0: Fq6 {
+                c0,
+                c1: Fq2::zero(),
+                c2: Fq2::zero(),
+            },
+            c1: Fq6 {
+                c0: c3,
+                c1: c4,
+                c2: Fq2::zero(),
+            },
+        });
+
+        assert_eq!(a, b);
+    }
+}
+
 #[test]
 fn fq12_field_tests() {
     use ff::PrimeField;
@@ -1,12 +1,75 @@
-use super::fq::{Fq, FROBENIUS_COEFF_FQ2_C1, NEGATIVE_ONE};
+use super::fq::{Fq, FqRepr, FROBENIUS_COEFF_FQ2_C1};
+use super::osswu_map::chain::chain_p2m9div16;
+use bls12::Fq2Ops;
+use core::cmp::Ordering;
 use digest::generic_array::{
     typenum::{U128, U64},
     GenericArray,
 };
-use ff::{Field, SqrtField};
+use ff::{Field, LegendreSymbol, SqrtField};
 use hash_to_field::{BaseFromRO, FromRO};
 use signum::{Sgn0Result, Signum0};
-use std::cmp::Ordering;
+
+/// Z = -(2 + u), the constant of the SSWU map to G2
+#[cfg(test)]
+const SSWU_Z: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x87ebfffffff9555c,
+        0x656fffe5da8ffffa,
+        0x0fd0749345d33ad2,
+        0xd951e663066576f4,
+        0xde291a3d41e980d3,
+        0x0815664c7dfe040d,
+    ])),
+    c1: Fq(FqRepr([
+        0x43f5fffffffcaaae,
+        0x32b7fff2ed47fffd,
+        0x07e83a49a2e99d69,
+        0xeca8f3318332bb7a,
+        0xef148d1ea0f4c069,
+        0x040ab3263eff0206,
+    ])),
+};
+
+/// Z^c2 for Z = SSWU_Z and c2 = (p^2 - 1) / 8, the constant c6 of `sqrt_ratio`
+const SQRT_RATIO_C6: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x7bcfa7a25aa30fda,
+        0xdc17dec12a927e7c,
+        0x2f088dd86b4ebef1,
+        0xd1ca2087da74d4a7,
+        0x2da2596696cebc1d,
+        0x0e2b7eedbbfd87d2,
+    ])),
+    c1: Fq(FqRepr([
+        0x7bcfa7a25aa30fda,
+        0xdc17dec12a927e7c,
+        0x2f088dd86b4ebef1,
+        0xd1ca2087da74d4a7,
+        0x2da2596696cebc1d,
+        0x0e2b7eedbbfd87d2,
+    ])),
+};
+
+/// Z^((c2 + 1) / 2), the constant c7 of `sqrt_ratio`
+const SQRT_RATIO_C7: Fq2 = Fq2 {
+    c0: Fq(FqRepr([
+        0x1aab5a8f05eb0ad5,
+        0x7f978a137f5c75a8,
+        0x88dddbddb2dcb26e,
+        0x5f39d438d31d1798,
+        0x8ffe34a7d8ef2b8e,
+        0x000fd871abca7e2f,
+    ])),
+    c1: Fq(FqRepr([
+        0xe970a0b7810e8983,
+        0x8d515f4ef7bdacaa,
+        0x18b052103a1fcfce,
+        0x2fc57aed4654434a,
+        0x0ebb355a46c49672,
+        0x12c4c8c52d4b5b10,
+    ])),
+};
 
 /// An element of Fq2, represented by c0 + c1 * u.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Zeroize)]
@@ -15,8 +78,33 @@ pub struct Fq2 {
     pub c1: Fq,
 }
 
-impl ::std::fmt::Display for Fq2 {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+extension_field_bytes_impl!(Fq2, 96, Fq, 48, c0, c1);
+
+impl Fq2 {
+    /// Returns the EIP-2537 encoding of the element: the encodings of c0 and c1,
+    /// each padded to 64 bytes.
+    pub fn to_eip2537(&self) -> [u8; 128] {
+        let mut out = [0u8; 128];
+        out[..64].copy_from_slice(&self.c0.to_eip2537());
+        out[64..].copy_from_slice(&self.c1.to_eip2537());
+        out
+    }
+
+    /// Decodes an EIP-2537 encoding, returning None if either coefficient is invalid.
+    pub fn from_eip2537(bytes: &[u8; 128]) -> Option<Fq2> {
+        let mut c0 = [0u8; 64];
+        let mut c1 = [0u8; 64];
+        c0.copy_from_slice(&bytes[..64]);
+        c1.copy_from_slice(&bytes[64..]);
+        Some(Fq2 {
+            c0: Fq::from_eip2537(&c0)?,
+            c1: Fq::from_eip2537(&c1)?,
+        })
+    }
+}
+
+impl ::core::fmt::Display for Fq2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "Fq2({} + {} * u)", self.c0, self.c1)
     }
 }
@@ -58,10 +146,95 @@ impl Fq2 {
 
         t1
     }
+
+    /// Computes the Legendre symbol as that of the norm, using the
+    /// variable-time `Fq::legendre_vartime`. This must only be used on
+    /// public data.
+    pub fn legendre_vartime(&self) -> LegendreSymbol {
+        self.norm().legendre_vartime()
+    }
+
+    /// Returns `b` if `choice` is set and `a` otherwise, without branching
+    /// on `choice`.
+    pub(crate) fn cmov(a: &Fq2, b: &Fq2, choice: bool) -> Fq2 {
+        Fq2 {
+            c0: Fq::cmov(&a.c0, &b.c0, choice),
+            c1: Fq::cmov(&a.c1, &b.c1, choice),
+        }
+    }
+
+    /// Compares two elements without branching on their limbs.
+    pub(crate) fn ct_eq(&self, other: &Fq2) -> bool {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+
+    /// Computes the square root of a ratio with the generic procedure of
+    /// RFC 9380, section F.2.1.1, in constant time. Here p^2 - 1 = 2^c1 * c2
+    /// with c1 = 3 and c2 odd.
+    /// Returns (true, sqrt(u / v)) if u / v is square, and (false, sqrt(Z * u / v))
+    /// otherwise, where Z = -(2 + u) is the constant of the SSWU map to G2.
+    /// Requires v != 0; for v = 0 the result is (u == 0, 0).
+    pub fn sqrt_ratio(u: &Fq2, v: &Fq2) -> (bool, Fq2) {
+        let mut tv1 = SQRT_RATIO_C6;
+        let mut tv2 = *v;
+        tv2.square();
+        tv2.mul_assign(v);
+        tv2.square();
+        tv2.mul_assign(v); // v^7
+        let mut tv3 = tv2;
+        tv3.square();
+        tv3.mul_assign(v); // v^15
+        let mut tv5 = *u;
+        tv5.mul_assign(&tv3);
+        let tmp = tv5;
+        chain_p2m9div16(&mut tv5, &tmp); // (u v^15) ^ c3, c3 = (c2 - 1) / 2
+        tv5.mul_assign(&tv2);
+        tv2 = tv5;
+        tv2.mul_assign(v);
+        tv3 = tv5;
+        tv3.mul_assign(u);
+        let mut tv4 = tv3;
+        tv4.mul_assign(&tv2);
+        tv5 = tv4;
+        tv5.square();
+        tv5.square(); // tv4 ^ (2^(c1 - 1))
+                      // the RFC leaves u = 0 unhandled, where tv4 = 0 but 0 is square
+        let is_qr = tv5.ct_eq(&Fq2::one()) | u.ct_eq(&Fq2::zero());
+        tv2 = tv3;
+        tv2.mul_assign(&SQRT_RATIO_C7);
+        tv5 = tv4;
+        tv5.mul_assign(&tv1);
+        tv3 = Fq2::cmov(&tv2, &tv3, is_qr);
+        tv4 = Fq2::cmov(&tv5, &tv4, is_qr);
+
+        for k in (2..=3).rev() {
+            tv5 = tv4;
+            for _ in 2..k {
+                tv5.square(); // tv4 ^ (2^(k - 2))
+            }
+            let e1 = tv5.ct_eq(&Fq2::one());
+            tv2 = tv3;
+            tv2.mul_assign(&tv1);
+            tv1.square();
+            tv5 = tv4;
+            tv5.mul_assign(&tv1);
+            tv3 = Fq2::cmov(&tv2, &tv3, e1);
+            tv4 = Fq2::cmov(&tv5, &tv4, e1);
+        }
+
+        (is_qr, tv3)
+    }
+}
+
+impl Fq2Ops<Fq> for Fq2 {
+    fn mul_by_fq(&mut self, other: &Fq) {
+        self.c0.mul_assign(other);
+        self.c1.mul_assign(other);
+    }
 }
 
 impl Field for Fq2 {
-    fn random<R: rand_core::RngCore + ?std::marker::Sized>(rng: &mut R) -> Self {
+    fn random<R: rand_core::RngCore + ?core::marker::Sized>(rng: &mut R) -> Self {
         Fq2 {
             c0: Fq::random(rng),
             c1: Fq::random(rng),
@@ -166,58 +339,9 @@ impl SqrtField for Fq2 {
     }
 
     fn sqrt(&self) -> Option<Self> {
-        // Algorithm 9, https://eprint.iacr.org/2012/685.pdf
-
-        if self.is_zero() {
-            Some(Self::zero())
-        } else {
-            // a1 = self^((q - 3) / 4)
-            let mut a1 = self.pow([
-                0xee7fbfffffffeaaa,
-                0x7aaffffac54ffff,
-                0xd9cc34a83dac3d89,
-                0xd91dd2e13ce144af,
-                0x92c6e9ed90d2eb35,
-                0x680447a8e5ff9a6,
-            ]);
-            let mut alpha = a1;
-            alpha.square();
-            alpha.mul_assign(self);
-            let mut a0 = alpha;
-            a0.frobenius_map(1);
-            a0.mul_assign(&alpha);
-
-            let neg1 = Fq2 {
-                c0: NEGATIVE_ONE,
-                c1: Fq::zero(),
-            };
-
-            if a0 == neg1 {
-                None
-            } else {
-                a1.mul_assign(self);
-
-                if alpha == neg1 {
-                    a1.mul_assign(&Fq2 {
-                        c0: Fq::zero(),
-                        c1: Fq::one(),
-                    });
-                } else {
-                    alpha.add_assign(&Fq2::one());
-                    // alpha = alpha^((q - 1) / 2)
-                    alpha = alpha.pow([
-                        0xdcff7fffffffd555,
-                        0xf55ffff58a9ffff,
-                        0xb39869507b587b12,
-                        0xb23ba5c279c2895f,
-                        0x258dd3db21a5d66b,
-                        0xd0088f51cbff34d,
-                    ]);
-                    a1.mul_assign(&alpha);
-                }
-
-                Some(a1)
-            }
+        match Fq2::sqrt_ratio(self, &Fq2::one()) {
+            (true, y) => Some(y),
+            (false, _) => None,
         }
     }
 }
@@ -864,7 +988,17 @@ fn test_fq2_sqrt() {
     use super::fq::FqRepr;
     use ff::PrimeField;
 
-    assert_eq!(
+    // The addition chain need not pick the same root as the baseline
+    // Algorithm 9, so negate to match.
+    fn assert_sqrt(a: Fq2, expected: Fq2) {
+        let mut root = a.sqrt().unwrap();
+        if root != expected {
+            root.negate();
+        }
+        assert_eq!(root, expected);
+    }
+
+    assert_sqrt(
         Fq2 {
             c0: Fq::from_repr(FqRepr([
                 0x476b4c309720e227,
@@ -872,7 +1006,7 @@ fn test_fq2_sqrt() {
                 0xa57e6fc1bab51fd9,
                 0xdb4a116b5bf74aa1,
                 0x1e58b2159dfe10e2,
-                0x7ca7da1f13606ac
+                0x7ca7da1f13606ac,
             ]))
             .unwrap(),
             c1: Fq::from_repr(FqRepr([
@@ -881,12 +1015,10 @@ fn test_fq2_sqrt() {
                 0x4cec2dca577a3eb6,
                 0x212611bca4e99121,
                 0x8ee5394d77afb3d,
-                0xec92336650e49d5
+                0xec92336650e49d5,
             ]))
             .unwrap(),
-        }
-        .sqrt()
-        .unwrap(),
+        },
         Fq2 {
             c0: Fq::from_repr(FqRepr([
                 0x40b299b2704258c5,
@@ -894,7 +1026,7 @@ fn test_fq2_sqrt() {
                 0x6d2ddbe552203e82,
                 0x8d7f1f723d02c1d3,
                 0x881b3e01b611c070,
-                0x10f6963bbad2ebc5
+                0x10f6963bbad2ebc5,
             ]))
             .unwrap(),
             c1: Fq::from_repr(FqRepr([
@@ -903,13 +1035,13 @@ fn test_fq2_sqrt() {
                 0x28a20faed211efe7,
                 0x6b852aeaf2afcb1b,
                 0xa4c93b08105d71a9,
-                0x8d7cfff94216330
+                0x8d7cfff94216330,
             ]))
             .unwrap(),
-        }
+        },
     );
 
-    assert_eq!(
+    assert_sqrt(
         Fq2 {
             c0: Fq::from_repr(FqRepr([
                 0xb9f78429d1517a6b,
@@ -917,13 +1049,11 @@ fn test_fq2_sqrt() {
                 0x6730d2a0f6b0f624,
                 0x64774b84f38512bf,
                 0x4b1ba7b6434bacd7,
-                0x1a0111ea397fe69a
+                0x1a0111ea397fe69a,
             ]))
             .unwrap(),
             c1: Fq::zero(),
-        }
-        .sqrt()
-        .unwrap(),
+        },
         Fq2 {
             c0: Fq::zero(),
             c1: Fq::from_repr(FqRepr([
@@ -932,11 +1062,63 @@ fn test_fq2_sqrt() {
                 0x6730d2a0f6b0f624,
                 0x64774b84f38512bf,
                 0x4b1ba7b6434bacd7,
-                0x1a0111ea397fe69a
+                0x1a0111ea397fe69a,
             ]))
             .unwrap(),
-        }
+        },
+    );
+}
+
+#[test]
+fn test_fq2_sqrt_ratio() {
+    use ff::LegendreSymbol;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(
+        Fq2::sqrt_ratio(&Fq2::one(), &Fq2::zero()),
+        (false, Fq2::zero())
+    );
+    assert_eq!(
+        Fq2::sqrt_ratio(&Fq2::zero(), &Fq2::one()),
+        (true, Fq2::zero())
     );
+
+    for _ in 0..1000 {
+        let a = Fq2::random(&mut rng);
+        let v = Fq2::random(&mut rng);
+
+        // u = a^2 * v, so sqrt(u / v) = +-a
+        let mut u = a;
+        u.square();
+        u.mul_assign(&v);
+        let (is_square, mut b) = Fq2::sqrt_ratio(&u, &v);
+        assert!(is_square);
+        if b != a {
+            b.negate();
+        }
+        assert_eq!(a, b);
+
+        // u / v is square iff u * v is, and otherwise b^2 = Z * u / v
+        let u = Fq2::random(&mut rng);
+        let mut uv = u;
+        uv.mul_assign(&v);
+        let (is_square, mut b) = Fq2::sqrt_ratio(&u, &v);
+        assert_eq!(
+            is_square,
+            uv.legendre() != LegendreSymbol::QuadraticNonResidue
+        );
+        b.square();
+        b.mul_assign(&v);
+        let mut expected = u;
+        if !is_square {
+            expected.mul_assign(&SSWU_Z);
+        }
+        assert_eq!(b, expected);
+    }
 }
 
 #[test]
@@ -952,6 +1134,32 @@ fn test_fq2_legendre() {
     assert_eq!(QuadraticNonResidue, m1.legendre());
 }
 
+#[test]
+fn test_fq2_legendre_vartime() {
+    use ff::SqrtField;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut nqr = Fq2::one();
+    nqr.mul_by_nonresidue();
+    let mut inputs = vec![Fq2::zero(), Fq2::one(), nqr];
+    for _ in 0..1000 {
+        let mut a = Fq2::random(&mut rng);
+        a.square();
+        inputs.push(a);
+        a.mul_assign(&nqr);
+        inputs.push(a);
+        inputs.push(Fq2::random(&mut rng));
+    }
+
+    for a in inputs {
+        assert_eq!(a.legendre_vartime(), a.legendre());
+    }
+}
+
 #[cfg(test)]
 use rand_core::SeedableRng;
 //use rand::{SeedableRng, XorShiftRng};
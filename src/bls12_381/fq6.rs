@@ -11,8 +11,10 @@ pub struct Fq6 {
     pub c2: Fq2,
 }
 
-impl ::std::fmt::Display for Fq6 {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+extension_field_bytes_impl!(Fq6, 288, Fq2, 96, c0, c1, c2);
+
+impl ::core::fmt::Display for Fq6 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "Fq6({} + {} * v, {} * v^2)", self.c0, self.c1, self.c2)
     }
 }
@@ -20,7 +22,7 @@ impl ::std::fmt::Display for Fq6 {
 impl Fq6 {
     /// Multiply by quadratic nonresidue v.
     pub fn mul_by_nonresidue(&mut self) {
-        use std::mem::swap;
+        use core::mem::swap;
         swap(&mut self.c0, &mut self.c1);
         swap(&mut self.c0, &mut self.c2);
 
@@ -100,7 +102,7 @@ impl Fq6 {
 }
 
 impl Field for Fq6 {
-    fn random<R: RngCore + ?std::marker::Sized>(rng: &mut R) -> Self {
+    fn random<R: RngCore + ?core::marker::Sized>(rng: &mut R) -> Self {
         Fq6 {
             c0: Fq2::random(rng),
             c1: Fq2::random(rng),
@@ -1,25 +1,103 @@
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+use super::asm;
+use super::inversion::chain_rm2;
 use digest::generic_array::{typenum::U48, GenericArray};
 use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
 use hash_to_field::BaseFromRO;
-use std::io::{Cursor, Read};
+use safegcd::{inverse_vartime, ModInfo};
+use signum::{Sgn0Result, Signum0};
 
 #[derive(PrimeField, Zeroize)]
 #[PrimeFieldModulus = "52435875175126190479447740508185965837690552500527637822603658699938581184513"]
 #[PrimeFieldGenerator = "7"]
+#[cfg_attr(
+    all(feature = "asm", target_arch = "x86_64"),
+    PrimeFieldKernels = "kernels"
+)]
 pub struct Fr(FrRepr);
 
+prime_field_bytes_impl!(Fr, FrRepr, 32);
+
 /// set the default value for Fr to 0
-impl ::std::default::Default for Fr {
+impl ::core::default::Default for Fr {
     fn default() -> Self {
         Fr::zero()
     }
 }
 
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+// The modulus followed by -r^-1 mod 2^64, for the assembly kernels
+const MODULUS_INV: [u64; 5] = [MODULUS.0[0], MODULUS.0[1], MODULUS.0[2], MODULUS.0[3], INV];
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+/// The assembly kernels called by the derived `Field` impl
+mod kernels {
+    use super::asm;
+    use super::MODULUS_INV;
+
+    #[inline(always)]
+    pub(super) fn available() -> bool {
+        asm::has_adx()
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn mul_assign(a: &mut [u64; 4], b: &[u64; 4]) {
+        asm::mont_mul_4(a.as_mut_ptr(), b.as_ptr(), &MODULUS_INV)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn square(a: &mut [u64; 4]) {
+        asm::mont_sqr_4(a.as_mut_ptr(), &MODULUS_INV)
+    }
+}
+
 /// # Safety
 pub const unsafe fn transmute(r: FrRepr) -> Fr {
     Fr(r)
 }
 
+// R^3 mod r, with R = 2^256
+const R3: FrRepr = FrRepr([
+    0xc62c1807439b73af,
+    0x1b3e0d188cf06990,
+    0x73d13c71c7b5f418,
+    0x6e2a5bb9c8db33e9,
+]);
+
+const MODINFO: ModInfo<5> = ModInfo::new(&MODULUS.0);
+
+impl Fr {
+    /// Inverts the element by raising it to the power r - 2, using an addition
+    /// chain whose sequence of operations does not depend on the input.
+    /// Returns None for zero.
+    pub fn inverse_ct(&self) -> Option<Fr> {
+        if self.is_zero() {
+            None
+        } else {
+            let mut res = *self;
+            chain_rm2(&mut res, self);
+            Some(res)
+        }
+    }
+
+    /// Inverts the element with the safegcd algorithm of Bernstein and Yang.
+    /// This is faster than the other inversion methods, but its running time
+    /// depends on the input, so it must only be used on public data.
+    /// Returns None for zero.
+    pub fn inverse_vartime(&self) -> Option<Fr> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // The representation of a is a R, whose inverse a^-1 R^-1 is turned
+        // back into the representation a^-1 R by a multiplication with R^3.
+        let mut res = Fr(FrRepr::default());
+        inverse_vartime(&(self.0).0, &MODINFO, &mut (res.0).0);
+        res.mul_assign(&Fr(R3));
+        Some(res)
+    }
+}
+
 impl BaseFromRO for Fr {
     type BaseLength = U48;
 
@@ -32,27 +110,44 @@ impl BaseFromRO for Fr {
         ]));
 
         // unwraps are safe here: we only use 24 bytes at a time, which is strictly less than p
-        let mut repr = FrRepr::default();
-        repr.read_be(Cursor::new([0; 8]).chain(Cursor::new(&okm[..24])))
-            .unwrap();
-        let mut elm = Fr::from_repr(repr).unwrap();
+        let mut buf = [0u8; 32];
+        buf[8..].copy_from_slice(&okm[..24]);
+        let mut elm = Fr::from_bytes(&buf).unwrap();
         elm.mul_assign(&F_2_192);
 
-        repr.read_be(Cursor::new([0; 8]).chain(Cursor::new(&okm[24..])))
-            .unwrap();
-        elm.add_assign(&Fr::from_repr(repr).unwrap());
+        buf[8..].copy_from_slice(&okm[24..]);
+        elm.add_assign(&Fr::from_bytes(&buf).unwrap());
         elm
     }
 }
 
+impl Signum0 for Fr {
+    fn sgn0(&self) -> Sgn0Result {
+        if self.into_repr().is_odd() {
+            Sgn0Result::Negative
+        } else {
+            Sgn0Result::NonNegative
+        }
+    }
+}
+
 #[cfg(test)]
 use rand_core::SeedableRng;
 
+#[test]
+fn test_fr_sgn0() {
+    assert_eq!(Fr::zero().sgn0(), Sgn0Result::NonNegative);
+    assert_eq!(Fr::one().sgn0(), Sgn0Result::Negative);
+    let mut m1 = Fr::one();
+    m1.negate();
+    assert_eq!(m1.sgn0(), Sgn0Result::NonNegative);
+}
+
 #[test]
 fn test_fr_repr_ordering() {
     fn assert_equality(a: FrRepr, b: FrRepr) {
         assert_eq!(a, b);
-        assert!(a.cmp(&b) == ::std::cmp::Ordering::Equal);
+        assert!(a.cmp(&b) == ::core::cmp::Ordering::Equal);
     }
 
     fn assert_lt(a: FrRepr, b: FrRepr) {
@@ -776,6 +871,75 @@ fn test_fr_inverse() {
     }
 }
 
+#[test]
+fn test_fr_inverse_ct_vartime() {
+    assert!(Fr::zero().inverse_ct().is_none());
+    assert!(Fr::zero().inverse_vartime().is_none());
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut minus_one = Fr::one();
+    minus_one.negate();
+    let mut inputs = vec![Fr::one(), minus_one];
+    inputs.extend((0..1000).map(|_| Fr::random(&mut rng)));
+
+    for a in inputs {
+        // All three inversion methods agree
+        let ainv = a.inverse();
+        assert_eq!(a.inverse_ct(), ainv);
+        assert_eq!(a.inverse_vartime(), ainv);
+    }
+}
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+#[test]
+fn test_fr_mul_asm() {
+    if !asm::has_adx() {
+        return;
+    }
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut minus_one = Fr::one();
+    minus_one.negate();
+    // the largest representation, which maximizes the intermediate values
+    let mut max = MODULUS;
+    max.0[0] -= 1;
+    let max = Fr(max);
+    let mut inputs = vec![Fr::zero(), Fr::one(), minus_one, max];
+    inputs.extend((0..100).map(|_| Fr::random(&mut rng)));
+
+    for a in inputs.iter() {
+        for b in inputs.iter() {
+            // Compare the kernel with the portable implementation
+            let mut expected = *a;
+            expected.mul_assign_portable(b);
+            let mut c = *a;
+            unsafe { asm::mont_mul_4((c.0).0.as_mut_ptr(), (b.0).0.as_ptr(), &MODULUS_INV) };
+            assert_eq!(c, expected);
+            // and with the derived method, which dispatches to it
+            let mut c = *a;
+            c.mul_assign(b);
+            assert_eq!(c, expected);
+        }
+
+        let mut expected = *a;
+        expected.square_portable();
+        let mut c = *a;
+        unsafe { asm::mont_sqr_4((c.0).0.as_mut_ptr(), &MODULUS_INV) };
+        assert_eq!(c, expected);
+        let mut c = *a;
+        c.square();
+        assert_eq!(c, expected);
+    }
+}
+
 #[test]
 fn test_fr_double() {
     let mut rng = rand_xorshift::XorShiftRng::from_seed([
@@ -933,6 +1097,8 @@ fn test_fr_from_into_repr() {
 
 #[test]
 fn test_fr_repr_display() {
+    use std::string::ToString;
+
     assert_eq!(
         format!(
             "{}",
@@ -977,6 +1143,8 @@ fn test_fr_repr_display() {
 
 #[test]
 fn test_fr_display() {
+    use std::string::ToString;
+
     assert_eq!(
         format!(
             "{}",
@@ -0,0 +1,161 @@
+/*!
+Addition chains for inversion in Fq and Fr by Fermat's little theorem.
+
+The chains use a sliding window of width 5 over the exponent, with a table
+of the odd powers x, x^3, ..., x^31. The sequence of squarings and
+multiplications only depends on the (public) modulus, so inversion by these
+chains runs in constant time.
+*/
+
+use bls12_381::{Fq, Fr};
+use ff::Field;
+
+/// Squares `res` `n` times, then multiplies it by `m`.
+#[inline(always)]
+fn sqr_mul<F: Field>(res: &mut F, n: usize, m: &F) {
+    for _ in 0..n {
+        res.square();
+    }
+    res.mul_assign(m);
+}
+
+/// Addition chain implementing exponentiation by p - 2.
+pub(super) fn chain_pm2(res: &mut Fq, x: &Fq) {
+    // t[i] = x^(2i + 1)
+    let mut x2 = *x;
+    x2.square();
+    let mut t = [*x; 16];
+    let mut acc = *x;
+    for ti in t.iter_mut().skip(1) {
+        acc.mul_assign(&x2);
+        *ti = acc;
+    }
+
+    *res = t[6];
+    sqr_mul(res, 13, &t[8]);
+    sqr_mul(res, 7, &t[7]);
+    sqr_mul(res, 4, &t[2]);
+    sqr_mul(res, 6, &t[3]);
+    sqr_mul(res, 7, &t[11]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[12]);
+    sqr_mul(res, 3, &t[2]);
+    sqr_mul(res, 6, &t[6]);
+    sqr_mul(res, 6, &t[4]);
+    sqr_mul(res, 3, &t[1]);
+    sqr_mul(res, 8, &t[13]);
+    sqr_mul(res, 3, &t[2]);
+    sqr_mul(res, 6, &t[7]);
+    sqr_mul(res, 6, &t[13]);
+    sqr_mul(res, 3, &t[0]);
+    sqr_mul(res, 8, &t[6]);
+    sqr_mul(res, 7, &t[11]);
+    sqr_mul(res, 5, &t[5]);
+    sqr_mul(res, 6, &t[6]);
+    sqr_mul(res, 6, &t[14]);
+    sqr_mul(res, 4, &t[4]);
+    sqr_mul(res, 8, &t[14]);
+    sqr_mul(res, 4, &t[6]);
+    sqr_mul(res, 7, &t[11]);
+    sqr_mul(res, 9, &t[9]);
+    sqr_mul(res, 5, &t[12]);
+    sqr_mul(res, 2, &t[1]);
+    sqr_mul(res, 7, &t[2]);
+    sqr_mul(res, 7, &t[4]);
+    sqr_mul(res, 6, &t[11]);
+    sqr_mul(res, 5, &t[14]);
+    sqr_mul(res, 5, &t[9]);
+    sqr_mul(res, 5, &t[9]);
+    sqr_mul(res, 8, &t[6]);
+    sqr_mul(res, 7, &t[10]);
+    sqr_mul(res, 9, &t[7]);
+    sqr_mul(res, 5, &t[6]);
+    sqr_mul(res, 3, &t[1]);
+    sqr_mul(res, 8, &t[7]);
+    sqr_mul(res, 3, &t[1]);
+    sqr_mul(res, 7, &t[4]);
+    sqr_mul(res, 9, &t[7]);
+    sqr_mul(res, 6, &t[10]);
+    sqr_mul(res, 6, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 4, &t[6]);
+    sqr_mul(res, 3, &t[1]);
+    sqr_mul(res, 8, &t[10]);
+    sqr_mul(res, 7, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 4, &t[7]);
+    sqr_mul(res, 4, &t[3]);
+    sqr_mul(res, 7, &t[15]);
+    sqr_mul(res, 5, &t[14]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 4, &t[6]);
+    sqr_mul(res, 6, &t[10]);
+    sqr_mul(res, 4, &t[2]);
+    sqr_mul(res, 3, &t[0]);
+}
+
+/// Addition chain implementing exponentiation by r - 2.
+pub(super) fn chain_rm2(res: &mut Fr, x: &Fr) {
+    // t[i] = x^(2i + 1)
+    let mut x2 = *x;
+    x2.square();
+    let mut t = [*x; 16];
+    let mut acc = *x;
+    for ti in t.iter_mut().skip(1) {
+        acc.mul_assign(&x2);
+        *ti = acc;
+    }
+
+    *res = t[3];
+    sqr_mul(res, 7, &t[15]);
+    sqr_mul(res, 6, &t[13]);
+    sqr_mul(res, 6, &t[9]);
+    sqr_mul(res, 5, &t[10]);
+    sqr_mul(res, 7, &t[12]);
+    sqr_mul(res, 6, &t[9]);
+    sqr_mul(res, 7, &t[14]);
+    sqr_mul(res, 6, &t[15]);
+    sqr_mul(res, 4, &t[2]);
+    sqr_mul(res, 3, &t[0]);
+    sqr_mul(res, 10, &t[12]);
+    sqr_mul(res, 5, &t[9]);
+    sqr_mul(res, 5, &t[9]);
+    sqr_mul(res, 4, &t[5]);
+    sqr_mul(res, 8, &t[0]);
+    sqr_mul(res, 12, &t[9]);
+    sqr_mul(res, 2, &t[0]);
+    sqr_mul(res, 9, &t[14]);
+    sqr_mul(res, 1, &t[0]);
+    sqr_mul(res, 13, &t[10]);
+    sqr_mul(res, 6, &t[9]);
+    sqr_mul(res, 5, &t[11]);
+    sqr_mul(res, 4, &t[5]);
+    sqr_mul(res, 5, &t[4]);
+    sqr_mul(res, 13, &t[11]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[12]);
+    sqr_mul(res, 6, &t[13]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[13]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 4, &t[7]);
+    sqr_mul(res, 6, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 5, &t[15]);
+    sqr_mul(res, 2, &t[1]);
+}
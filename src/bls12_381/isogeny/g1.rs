@@ -2,8 +2,8 @@
 Constants for G1 isogeny.
 */
 
-use super::{eval_iso, IsogenyMap};
 use bls12_381::{Fq, FqRepr, G1};
+use osswu_map::{eval_iso, IsogenyMap};
 
 /// Coefficients of the 11-isogeny x map's numerator
 const XNUM: [Fq; 12] = [
@@ -463,6 +463,6 @@ const YDEN: [Fq; 16] = [
 
 impl IsogenyMap for G1 {
     fn isogeny_map(&mut self) {
-        eval_iso(self, [&XNUM[..], &XDEN[..], &YNUM[..], &YDEN[..]]);
+        eval_iso::<_, { YNUM.len() }>(self, [&XNUM[..], &XDEN[..], &YNUM[..], &YDEN[..]]);
     }
 }
@@ -2,8 +2,8 @@
 Constants for G2 isogeny.
 */
 
-use super::{eval_iso, IsogenyMap};
 use bls12_381::{Fq, Fq2, FqRepr, G2};
+use osswu_map::{eval_iso, IsogenyMap};
 
 /// Coefficients of the 3-isogeny x map's numerator
 const XNUM: [Fq2; 4] = [
@@ -293,6 +293,6 @@ const YDEN: [Fq2; 4] = [
 
 impl IsogenyMap for G2 {
     fn isogeny_map(&mut self) {
-        eval_iso(self, [&XNUM[..], &XDEN[..], &YNUM[..], &YDEN[..]]);
+        eval_iso::<_, { YNUM.len() }>(self, [&XNUM[..], &XDEN[..], &YNUM[..], &YDEN[..]]);
     }
 }
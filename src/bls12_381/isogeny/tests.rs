@@ -2,10 +2,10 @@
 Tests for isogenies.
 */
 
-use super::IsogenyMap;
 use bls12_381::transmute::{g1_projective, g2_projective};
 use bls12_381::{Fq, Fq2, FqRepr};
 use ff::{Field, PrimeField};
+use osswu_map::IsogenyMap;
 use CurveProjective;
 
 #[test]
@@ -2,10 +2,10 @@
 Constants for OSSWU map for G1
 */
 
-use super::chain::chain_pm3div4;
-use super::{osswu_help, OSSWUMap};
+use super::osswu_help;
 use bls12_381::{Fq, FqRepr, G1};
 use ff::Field;
+use osswu_map::OSSWUMap;
 use signum::Signum0;
 
 pub(super) const ELLP_A: Fq = Fq(FqRepr([
@@ -35,48 +35,21 @@ const XI: Fq = Fq(FqRepr([
     0x78c712fbe0ab6e8u64,
 ]));
 
-const SQRT_M_XI_CUBED: Fq = Fq(FqRepr([
-    0x43b571cad3215f1fu64,
-    0xccb460ef1c702dc2u64,
-    0x742d884f4f97100bu64,
-    0xdb2c3e3238a3382bu64,
-    0xe40f3fa13fce8f88u64,
-    0x73a2af9892a2ffu64,
-]));
-
 impl OSSWUMap for G1 {
     fn osswu_map(u: &Fq) -> G1 {
         // compute x0 and g(x0)
-        let [usq, xi_usq, _, x0_num, x0_den, gx0_num, gx0_den] =
-            osswu_help(u, &XI, &ELLP_A, &ELLP_B);
-
-        // compute g(X0(u)) ^ ((p - 3) // 4)
-        let sqrt_candidate = {
-            let mut tmp1 = gx0_num;
-            tmp1.mul_assign(&gx0_den); // u * v
-            let mut tmp2 = gx0_den;
-            tmp2.square(); // v^2
-            tmp2.mul_assign(&tmp1); // u * v^3
-            let tmp3 = tmp2;
-            chain_pm3div4(&mut tmp2, &tmp3); // (u v^3) ^ ((p - 3) // 4)
-            tmp2.mul_assign(&tmp1); // u v (u v^3) ^ ((p - 3) // 4)
-            tmp2
-        };
+        let [xi_usq, x0_num, x0_den, gx0_num, gx0_den] = osswu_help(u, &XI, &ELLP_A, &ELLP_B);
 
         // select correct values for y and for x numerator
-        let (mut x_num, mut y) = {
-            let mut test_cand = sqrt_candidate;
-            test_cand.square();
-            test_cand.mul_assign(&gx0_den);
-            if test_cand == gx0_num {
-                (x0_num, sqrt_candidate) // g(x0) is square
-            } else {
-                let mut x1_num = x0_num; // g(x1) is square
-                x1_num.mul_assign(&xi_usq); // x1 = xi u^2 g(x0)
-                let mut y1 = usq; // y1 = sqrt(-xi**3) * u^3 g(x0) ^ ((p - 1) // 4)
+        let (mut x_num, mut y) = match Fq::sqrt_ratio(&gx0_num, &gx0_den) {
+            (true, y0) => (x0_num, y0), // g(x0) is square
+            (false, y0) => {
+                // g(x1) is square, with x1 = xi u^2 x0 and g(x1) = xi^3 u^6 g(x0)
+                let mut x1_num = x0_num;
+                x1_num.mul_assign(&xi_usq);
+                let mut y1 = xi_usq; // y1 = xi u^3 sqrt(xi g(x0))
                 y1.mul_assign(u);
-                y1.mul_assign(&sqrt_candidate);
-                y1.mul_assign(&SQRT_M_XI_CUBED);
+                y1.mul_assign(&y0);
                 (x1_num, y1)
             }
         };
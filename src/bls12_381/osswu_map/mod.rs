@@ -3,37 +3,29 @@ Optimized Simplified SWU maps for G1 and G2
 see: https://eprint.iacr.org/2019/403
 */
 
-mod chain;
+pub(super) mod chain;
 mod g1;
 mod g2;
 #[cfg(test)]
 mod tests;
 
 use ff::Field;
-use CurveProjective;
-
-/// Trait for mapping from base field element to curve point
-pub trait OSSWUMap: CurveProjective {
-    /// Evaluate optimized simplified SWU map on supplied base field element
-    fn osswu_map(u: &<Self as CurveProjective>::Base) -> Self;
-}
 
 #[inline(always)]
-fn osswu_help<F: Field>(u: &F, xi: &F, ellp_a: &F, ellp_b: &F) -> [F; 7] {
+fn osswu_help<F: Field>(u: &F, xi: &F, ellp_a: &F, ellp_b: &F) -> [F; 5] {
     let usq = {
         let mut tmp = *u;
         tmp.square();
         tmp
     };
 
-    let (nd_common, xi_usq, xi2_u4) = {
+    let (nd_common, xi_usq) = {
         let mut tmp = usq;
         tmp.mul_assign(xi); // xi * u^2
         let tmp2 = tmp;
         tmp.square(); // xi^2 * u^4
-        let tmp3 = tmp;
         tmp.add_assign(&tmp2); // xi^2 * u^4 + xi * u^2
-        (tmp, tmp2, tmp3)
+        (tmp, tmp2)
     };
 
     let x0_num = {
@@ -77,5 +69,5 @@ fn osswu_help<F: Field>(u: &F, xi: &F, ellp_a: &F, ellp_b: &F) -> [F; 7] {
         tmp1
     };
 
-    [usq, xi_usq, xi2_u4, x0_num, x0_den, gx0_num, gx0_den]
+    [xi_usq, x0_num, x0_den, gx0_num, gx0_den]
 }
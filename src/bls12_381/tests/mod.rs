@@ -1,5 +1,6 @@
 use super::*;
 use rand_core::SeedableRng;
+use std::string::{String, ToString};
 use *;
 
 #[test]
@@ -145,6 +146,8 @@ fn test_g2_compressed_valid_vectors() {
     test_vectors::<G2, G2Compressed>(include_bytes!("g2_compressed_valid_test_vectors.dat"));
 }
 
+// these build their inputs with `PrimeFieldRepr::write_be`, which needs `std`
+#[cfg(feature = "std")]
 #[test]
 fn test_g1_uncompressed_invalid_vectors() {
     {
@@ -259,6 +262,8 @@ fn test_g1_uncompressed_invalid_vectors() {
     }
 }
 
+// these build their inputs with `PrimeFieldRepr::write_be`, which needs `std`
+#[cfg(feature = "std")]
 #[test]
 fn test_g2_uncompressed_invalid_vectors() {
     {
@@ -401,6 +406,8 @@ fn test_g2_uncompressed_invalid_vectors() {
     }
 }
 
+// these build their inputs with `PrimeFieldRepr::write_be`, which needs `std`
+#[cfg(feature = "std")]
 #[test]
 fn test_g1_compressed_invalid_vectors() {
     {
@@ -517,6 +524,8 @@ fn test_g1_compressed_invalid_vectors() {
     }
 }
 
+// these build their inputs with `PrimeFieldRepr::write_be`, which needs `std`
+#[cfg(feature = "std")]
 #[test]
 fn test_g2_compressed_invalid_vectors() {
     {
@@ -658,3 +667,310 @@ fn test_g2_compressed_invalid_vectors() {
         }
     }
 }
+
+/// A test vector from EIP-2537: an input and either the expected output or the expected error.
+pub(crate) struct Eip2537Vector {
+    pub name: String,
+    pub input: Vec<u8>,
+    pub expected: Result<Vec<u8>, String>,
+}
+
+/// Parses test vectors in the JSON format of the EIP-2537 reference vectors.
+pub(crate) fn eip2537_vectors(json: &str) -> Vec<Eip2537Vector> {
+    use tests::hash_to_curve::from_hex;
+
+    let vectors: serde_json::Value = serde_json::from_str(json).unwrap();
+    vectors
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| Eip2537Vector {
+            name: v["Name"].as_str().unwrap().to_string(),
+            input: from_hex(v["Input"].as_str().unwrap()),
+            expected: match v["Expected"].as_str() {
+                Some(out) => Ok(from_hex(out)),
+                None => Err(v["ExpectedError"].as_str().unwrap().to_string()),
+            },
+        })
+        .collect()
+}
+
+#[test]
+fn test_eip2537_field_encodings() {
+    assert_eq!(Fq::one().to_eip2537()[..63], [0u8; 63][..]);
+    assert_eq!(Fq::one().to_eip2537()[63], 1);
+
+    // the inputs of the mapping precompiles are field elements
+    for v in eip2537_vectors(include_str!("eip2537/map_fp_to_G1_bls.json")) {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&v.input);
+        let x = Fq::from_eip2537(&bytes).unwrap();
+        assert_eq!(&x.to_eip2537()[..], &v.input[..], "{}", v.name);
+    }
+    for v in eip2537_vectors(include_str!("eip2537/map_fp2_to_G2_bls.json")) {
+        let mut bytes = [0u8; 128];
+        bytes.copy_from_slice(&v.input);
+        let x = Fq2::from_eip2537(&bytes).unwrap();
+        assert_eq!(&x.to_eip2537()[..], &v.input[..], "{}", v.name);
+        assert_eq!(&x.c0.to_eip2537()[..], &v.input[..64], "{}", v.name);
+    }
+
+    // nonzero padding and unreduced elements are rejected
+    for v in eip2537_vectors(include_str!("eip2537/fail-map_fp_to_G1_bls.json")) {
+        if v.input.len() == 64 {
+            let mut bytes = [0u8; 64];
+            bytes.copy_from_slice(&v.input);
+            assert_eq!(Fq::from_eip2537(&bytes), None, "{}", v.name);
+        }
+    }
+    for v in eip2537_vectors(include_str!("eip2537/fail-map_fp2_to_G2_bls.json")) {
+        if v.input.len() == 128 {
+            let mut bytes = [0u8; 128];
+            bytes.copy_from_slice(&v.input);
+            assert_eq!(Fq2::from_eip2537(&bytes), None, "{}", v.name);
+        }
+    }
+}
+
+/// Checks that the points in the inputs and outputs of the addition vectors `valid`
+/// are decoded and encoded again unchanged, and that the malformed points in the
+/// vectors `invalid` are rejected for the expected reason.
+fn eip2537_point_tests<E: EncodedPoint>(valid: &str, invalid: &str) {
+    for v in eip2537_vectors(valid) {
+        let mut bytes = v.input.clone();
+        bytes.extend(v.expected.unwrap());
+        for chunk in bytes.chunks(E::size()) {
+            let mut encoded = E::empty();
+            encoded.as_mut().copy_from_slice(chunk);
+            let p = encoded.into_affine_unchecked().unwrap();
+            assert_eq!(E::from_affine(p).as_ref(), chunk, "{}", v.name);
+            match encoded.into_affine() {
+                Ok(q) => assert_eq!(p, q, "{}", v.name),
+                Err(GroupDecodingError::NotInSubgroup) => {
+                    assert!(v.name.contains("wrong_order"), "{}", v.name)
+                }
+                Err(e) => panic!("{}: {}", v.name, e),
+            }
+        }
+    }
+
+    for v in eip2537_vectors(invalid) {
+        // inputs of the wrong length are rejected by the precompiles, not the encoding
+        if v.input.len() != 2 * E::size() {
+            continue;
+        }
+        let err = v
+            .input
+            .chunks(E::size())
+            .find_map(|chunk| {
+                let mut encoded = E::empty();
+                encoded.as_mut().copy_from_slice(chunk);
+                encoded.into_affine().err()
+            })
+            .unwrap();
+        let expected = v.expected.unwrap_err();
+        match err {
+            GroupDecodingError::NotOnCurve => assert!(expected.contains("not on curve")),
+            GroupDecodingError::CoordinateDecodingError(..) => {
+                assert!(expected.contains("fp.Element"))
+            }
+            GroupDecodingError::UnexpectedInformation => assert!(expected.contains("top bytes")),
+            e => panic!("{}: {}", v.name, e),
+        }
+    }
+}
+
+#[test]
+fn test_eip2537_g1_encoding() {
+    let valid = include_str!("eip2537/add_G1_bls.json");
+    eip2537_point_tests::<G1Eip2537>(valid, include_str!("eip2537/fail-add_G1_bls.json"));
+
+    // the first vector adds the generator to another point
+    let v = &eip2537_vectors(valid)[0];
+    assert_eq!(
+        G1Eip2537::from_affine(G1Affine::one()).as_ref(),
+        &v.input[..128]
+    );
+    assert!(G1Eip2537::from_affine(G1Affine::zero())
+        .as_ref()
+        .iter()
+        .all(|b| *b == 0));
+}
+
+#[test]
+fn test_eip2537_g2_encoding() {
+    let valid = include_str!("eip2537/add_G2_bls.json");
+    eip2537_point_tests::<G2Eip2537>(valid, include_str!("eip2537/fail-add_G2_bls.json"));
+
+    // the first vector adds the generator to another point
+    let v = &eip2537_vectors(valid)[0];
+    assert_eq!(
+        G2Eip2537::from_affine(G2Affine::one()).as_ref(),
+        &v.input[..256]
+    );
+    assert!(G2Eip2537::from_affine(G2Affine::zero())
+        .as_ref()
+        .iter()
+        .all(|b| *b == 0));
+}
+
+/// Runs a precompile on the vectors `valid`, which it must reproduce, and on the
+/// vectors `invalid`, which it must reject with the expected error.
+fn eip2537_precompile_tests(
+    precompile: fn(&[u8]) -> Result<Vec<u8>, eip2537::PrecompileError>,
+    valid: &str,
+    invalid: &str,
+) {
+    use self::eip2537::PrecompileError;
+
+    for v in eip2537_vectors(valid) {
+        assert_eq!(precompile(&v.input), Ok(v.expected.unwrap()), "{}", v.name);
+    }
+    for v in eip2537_vectors(invalid) {
+        let msg = v.expected.unwrap_err();
+        let expected = if msg.contains("input length") {
+            PrecompileError::InvalidInputLength
+        } else if msg.contains("top bytes") {
+            PrecompileError::NonZeroPadding
+        } else if msg.contains("fp.Element") {
+            PrecompileError::NotInField
+        } else if msg.contains("not on curve") {
+            PrecompileError::NotOnCurve
+        } else if msg.contains("subgroup") {
+            PrecompileError::NotInSubgroup
+        } else {
+            panic!("{}: unknown error {}", v.name, msg)
+        };
+        assert_eq!(precompile(&v.input), Err(expected), "{}", v.name);
+    }
+}
+
+#[test]
+fn test_eip2537_g1_add() {
+    eip2537_precompile_tests(
+        eip2537::g1_add,
+        include_str!("eip2537/add_G1_bls.json"),
+        include_str!("eip2537/fail-add_G1_bls.json"),
+    );
+}
+
+#[test]
+fn test_eip2537_g2_add() {
+    eip2537_precompile_tests(
+        eip2537::g2_add,
+        include_str!("eip2537/add_G2_bls.json"),
+        include_str!("eip2537/fail-add_G2_bls.json"),
+    );
+}
+
+#[test]
+fn test_eip2537_g1_msm() {
+    eip2537_precompile_tests(
+        eip2537::g1_msm,
+        include_str!("eip2537/multiexp_G1_bls.json"),
+        include_str!("eip2537/fail-multiexp_G1_bls.json"),
+    );
+    // multiplication by a single scalar is an MSM of length one
+    eip2537_precompile_tests(
+        eip2537::g1_msm,
+        include_str!("eip2537/mul_G1_bls.json"),
+        include_str!("eip2537/fail-mul_G1_bls.json"),
+    );
+}
+
+#[test]
+fn test_eip2537_g2_msm() {
+    eip2537_precompile_tests(
+        eip2537::g2_msm,
+        include_str!("eip2537/multiexp_G2_bls.json"),
+        include_str!("eip2537/fail-multiexp_G2_bls.json"),
+    );
+    eip2537_precompile_tests(
+        eip2537::g2_msm,
+        include_str!("eip2537/mul_G2_bls.json"),
+        include_str!("eip2537/fail-mul_G2_bls.json"),
+    );
+}
+
+#[test]
+fn test_eip2537_pairing_check() {
+    eip2537_precompile_tests(
+        eip2537::pairing_check,
+        include_str!("eip2537/pairing_check_bls.json"),
+        include_str!("eip2537/fail-pairing_check_bls.json"),
+    );
+}
+
+#[test]
+fn test_eip2537_map_to_curve() {
+    eip2537_precompile_tests(
+        eip2537::map_fp_to_g1,
+        include_str!("eip2537/map_fp_to_G1_bls.json"),
+        include_str!("eip2537/fail-map_fp_to_G1_bls.json"),
+    );
+    eip2537_precompile_tests(
+        eip2537::map_fp2_to_g2,
+        include_str!("eip2537/map_fp2_to_G2_bls.json"),
+        include_str!("eip2537/fail-map_fp2_to_G2_bls.json"),
+    );
+}
+
+/// Checks that `hex` is the hex string of `bytes` and parses back into `v`,
+/// and that malformed strings are rejected.
+fn hex_tests<T>(v: &T, hex: String, bytes: &[u8])
+where
+    T: ::core::str::FromStr<Err = HexDecodingError> + PartialEq + ::core::fmt::Debug,
+{
+    use encoding_macros::to_hex;
+
+    assert_eq!(hex, to_hex(bytes));
+    assert_eq!(hex.len(), 2 * bytes.len());
+    assert_eq!(hex.parse::<T>().as_ref(), Ok(v));
+    assert_eq!(hex.to_uppercase().parse::<T>().as_ref(), Ok(v));
+
+    assert_eq!(hex[1..].parse::<T>(), Err(HexDecodingError::OddLength));
+    assert_eq!(
+        format!("x{}", &hex[1..]).parse::<T>(),
+        Err(HexDecodingError::InvalidDigit('x'))
+    );
+    assert_eq!(hex[2..].parse::<T>(), Err(HexDecodingError::InvalidLength));
+    assert_eq!(
+        format!("{}00", hex).parse::<T>(),
+        Err(HexDecodingError::InvalidLength)
+    );
+    // all ones is not smaller than the modulus, nor a valid point encoding
+    assert_eq!(
+        "f".repeat(hex.len()).parse::<T>(),
+        Err(HexDecodingError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_hex_encodings() {
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(Fr::one().to_hex(), format!("{}1", "0".repeat(63)));
+    assert_eq!(
+        G1Affine::one().to_hex(),
+        "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+    );
+    assert_eq!(G1Affine::zero().to_hex(), format!("c{}", "0".repeat(95)));
+
+    for _ in 0..10 {
+        let x = Fq::random(&mut rng);
+        hex_tests(&x, x.to_hex(), &x.to_bytes());
+        assert_eq!(Fq::from_hex(&x.to_hex()), Ok(x));
+        let x = Fq2::random(&mut rng);
+        hex_tests(&x, x.to_hex(), &x.to_bytes());
+        let x = Fr::random(&mut rng);
+        hex_tests(&x, x.to_hex(), &x.to_bytes());
+        let p = G1::random(&mut rng).into_affine();
+        hex_tests(&p, p.to_hex(), &p.to_bytes());
+        assert_eq!(G1Affine::from_hex(&p.to_hex()), Ok(p));
+        let p = G2::random(&mut rng).into_affine();
+        hex_tests(&p, p.to_hex(), &p.to_bytes());
+    }
+}
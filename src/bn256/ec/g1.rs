@@ -0,0 +1,324 @@
+use super::super::{Bn256, Fq, Fq12, FqRepr, Fr, FrRepr};
+use super::g2::G2Affine;
+use core::fmt;
+use encoding_macros::{repr_from_be, repr_to_be};
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, SubgroupCheck};
+
+curve_impl!(
+    "G1",
+    G1,
+    G1Affine,
+    G1Prepared,
+    Fq,
+    Fr,
+    G1Uncompressed,
+    G1Compressed,
+    G2Affine,
+    Bn256,
+    Fq12
+);
+
+group_bytes_impl!(G1, G1Affine, G1Compressed, 32, G1Uncompressed, 64);
+
+#[derive(Copy, Clone)]
+pub struct G1Uncompressed([u8; 64]);
+
+impl AsRef<[u8]> for G1Uncompressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G1Uncompressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G1Uncompressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G1Uncompressed {
+    type Affine = G1Affine;
+
+    fn empty() -> Self {
+        G1Uncompressed([0; 64])
+    }
+    fn size() -> usize {
+        64
+    }
+    fn into_affine(&self) -> Result<G1Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        if !affine.is_on_curve() {
+            Err(GroupDecodingError::NotOnCurve)
+        } else {
+            // NB: The cofactor of G1 is one, so every point on the curve is in G1.
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G1Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 7) != 0 {
+            // The bit indicating the y-coordinate should be lexicographically
+            // largest is set, but this is an uncompressed element.
+            return Err(GroupDecodingError::UnexpectedInformation);
+        }
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G1Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            let x: FqRepr = repr_from_be(&copy[..32]);
+            let y: FqRepr = repr_from_be(&copy[32..]);
+
+            Ok(G1Affine {
+                x: Fq::from_repr(x)
+                    .map_err(|e| GroupDecodingError::CoordinateDecodingError("x coordinate", e))?,
+                y: Fq::from_repr(y)
+                    .map_err(|e| GroupDecodingError::CoordinateDecodingError("y coordinate", e))?,
+                infinity: false,
+            })
+        }
+    }
+    fn from_affine(affine: G1Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.into_repr(), &mut res.0[..32]);
+            repr_to_be(&affine.y.into_repr(), &mut res.0[32..]);
+        }
+
+        res
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct G1Compressed([u8; 32]);
+
+impl AsRef<[u8]> for G1Compressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G1Compressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G1Compressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G1Compressed {
+    type Affine = G1Affine;
+
+    fn empty() -> Self {
+        G1Compressed([0; 32])
+    }
+    fn size() -> usize {
+        32
+    }
+    fn into_affine(&self) -> Result<G1Affine, GroupDecodingError> {
+        // NB: Decompression guarantees that it is on the curve already,
+        // and the cofactor of G1 is one.
+        self.into_affine_unchecked()
+    }
+    fn into_affine_unchecked(&self) -> Result<G1Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G1Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            // Determine if the intended y coordinate must be greater
+            // lexicographically.
+            let greatest = copy[0] & (1 << 7) != 0;
+
+            // Unset the two most significant bits.
+            copy[0] &= 0x3f;
+
+            let x: FqRepr = repr_from_be(&copy[..]);
+
+            // Interpret as Fq element.
+            let x = Fq::from_repr(x)
+                .map_err(|e| GroupDecodingError::CoordinateDecodingError("x coordinate", e))?;
+
+            G1Affine::get_point_from_x(x, greatest).ok_or(GroupDecodingError::NotOnCurve)
+        }
+    }
+    fn from_affine(affine: G1Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.into_repr(), &mut res.0[..]);
+
+            let mut negy = affine.y;
+            negy.negate();
+
+            // Set the most significant bit if the correct y-coordinate
+            // is lexicographically largest.
+            if affine.y > negy {
+                res.0[0] |= 1 << 7;
+            }
+        }
+
+        res
+    }
+}
+
+impl G1Affine {
+    fn scale_by_cofactor(&self) -> G1 {
+        // G1 cofactor = 1
+        self.into_projective()
+    }
+
+    fn get_generator() -> Self {
+        G1Affine {
+            x: super::super::fq::G1_GENERATOR_X,
+            y: super::super::fq::G1_GENERATOR_Y,
+            infinity: false,
+        }
+    }
+
+    fn get_coeff_b() -> Fq {
+        super::super::fq::B_COEFF
+    }
+
+    fn perform_pairing(&self, other: &G2Affine) -> Fq12 {
+        super::super::Bn256::pairing(*self, *other)
+    }
+}
+
+impl G1 {
+    fn empirical_recommended_wnaf_for_scalar(scalar: FrRepr) -> usize {
+        let num_bits = scalar.num_bits() as usize;
+
+        if num_bits >= 130 {
+            4
+        } else if num_bits >= 34 {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn empirical_recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize {
+        const RECOMMENDATIONS: [usize; 12] =
+            [1, 3, 7, 20, 43, 120, 273, 563, 1630, 3128, 7933, 62569];
+
+        let mut ret = 4;
+        for r in &RECOMMENDATIONS {
+            if num_scalars > *r {
+                ret += 1;
+            } else {
+                break;
+            }
+        }
+
+        ret
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct G1Prepared(pub(crate) G1Affine);
+
+impl G1Prepared {
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn from_affine(p: G1Affine) -> Self {
+        G1Prepared(p)
+    }
+}
+
+impl SubgroupCheck for G1Affine {
+    /// The cofactor of G1 is one, so every point on the curve is in the subgroup.
+    fn in_subgroup(&self) -> bool {
+        self.is_on_curve()
+    }
+}
+
+#[test]
+fn g1_generator() {
+    let g1 = G1Affine::one();
+    assert!(g1.is_on_curve());
+    assert!(g1.is_in_correct_subgroup_assuming_on_curve());
+    assert_eq!(g1.x, Fq::from_repr(FqRepr::from(1)).unwrap());
+    assert_eq!(g1.y, Fq::from_repr(FqRepr::from(2)).unwrap());
+}
+
+#[test]
+fn g1_test_is_valid() {
+    // Reject point on a twist (b = 4)
+    let p = G1Affine {
+        x: Fq::from_repr(FqRepr::from(0)).unwrap(),
+        y: Fq::from_repr(FqRepr::from(2)).unwrap(),
+        infinity: false,
+    };
+    assert!(!p.is_on_curve());
+    assert!(!p.in_subgroup());
+    assert!(p.into_uncompressed().into_affine().is_err());
+}
+
+#[test]
+fn test_g1_encoding_vectors() {
+    // The generator (1, 2) and its negation (1, q - 2)
+    let mut expected = [0u8; 64];
+    expected[31] = 1;
+    expected[63] = 2;
+    assert_eq!(G1Affine::one().into_uncompressed().as_ref(), &expected[..]);
+
+    let mut compressed = [0u8; 32];
+    compressed[31] = 1;
+    assert_eq!(G1Affine::one().into_compressed().as_ref(), &compressed[..]);
+    let mut neg = G1Affine::one();
+    neg.negate();
+    compressed[0] |= 1 << 7;
+    assert_eq!(neg.into_compressed().as_ref(), &compressed[..]);
+
+    let mut infinity = [0u8; 32];
+    infinity[0] = 1 << 6;
+    assert_eq!(G1Affine::zero().into_compressed().as_ref(), &infinity[..]);
+}
+
+#[test]
+fn g1_curve_tests() {
+    ::tests::curve::curve_tests::<G1>();
+}
@@ -0,0 +1,361 @@
+use super::super::{Bn256, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
+use super::g1::G1Affine;
+use alloc::vec::Vec;
+use core::fmt;
+use encoding_macros::{repr_from_be, repr_to_be};
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, SubgroupCheck};
+
+curve_impl!(
+    "G2",
+    G2,
+    G2Affine,
+    G2Prepared,
+    Fq2,
+    Fr,
+    G2Uncompressed,
+    G2Compressed,
+    G1Affine,
+    Bn256,
+    Fq12
+);
+
+group_bytes_impl!(G2, G2Affine, G2Compressed, 64, G2Uncompressed, 128);
+
+#[derive(Copy, Clone)]
+pub struct G2Uncompressed([u8; 128]);
+
+impl AsRef<[u8]> for G2Uncompressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G2Uncompressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G2Uncompressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G2Uncompressed {
+    type Affine = G2Affine;
+
+    fn empty() -> Self {
+        G2Uncompressed([0; 128])
+    }
+    fn size() -> usize {
+        128
+    }
+    fn into_affine(&self) -> Result<G2Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        if !affine.is_on_curve() {
+            Err(GroupDecodingError::NotOnCurve)
+        } else if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G2Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 7) != 0 {
+            // The bit indicating the y-coordinate should be lexicographically
+            // largest is set, but this is an uncompressed element.
+            return Err(GroupDecodingError::UnexpectedInformation);
+        }
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G2Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            let x_c1: FqRepr = repr_from_be(&copy[..32]);
+            let x_c0: FqRepr = repr_from_be(&copy[32..64]);
+            let y_c1: FqRepr = repr_from_be(&copy[64..96]);
+            let y_c0: FqRepr = repr_from_be(&copy[96..]);
+
+            Ok(G2Affine {
+                x: Fq2 {
+                    c0: Fq::from_repr(x_c0).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("x coordinate (c0)", e)
+                    })?,
+                    c1: Fq::from_repr(x_c1).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("x coordinate (c1)", e)
+                    })?,
+                },
+                y: Fq2 {
+                    c0: Fq::from_repr(y_c0).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("y coordinate (c0)", e)
+                    })?,
+                    c1: Fq::from_repr(y_c1).map_err(|e| {
+                        GroupDecodingError::CoordinateDecodingError("y coordinate (c1)", e)
+                    })?,
+                },
+                infinity: false,
+            })
+        }
+    }
+    fn from_affine(affine: G2Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..32]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[32..64]);
+            repr_to_be(&affine.y.c1.into_repr(), &mut res.0[64..96]);
+            repr_to_be(&affine.y.c0.into_repr(), &mut res.0[96..]);
+        }
+
+        res
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct G2Compressed([u8; 64]);
+
+impl AsRef<[u8]> for G2Compressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for G2Compressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for G2Compressed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl EncodedPoint for G2Compressed {
+    type Affine = G2Affine;
+
+    fn empty() -> Self {
+        G2Compressed([0; 64])
+    }
+    fn size() -> usize {
+        64
+    }
+    fn into_affine(&self) -> Result<G2Affine, GroupDecodingError> {
+        let affine = self.into_affine_unchecked()?;
+
+        // NB: Decompression guarantees that it is on the curve already.
+
+        if !affine.in_subgroup() {
+            Err(GroupDecodingError::NotInSubgroup)
+        } else {
+            Ok(affine)
+        }
+    }
+    fn into_affine_unchecked(&self) -> Result<G2Affine, GroupDecodingError> {
+        // Create a copy of this representation.
+        let mut copy = self.0;
+
+        if copy[0] & (1 << 6) != 0 {
+            // This is the point at infinity, which means that if we mask away
+            // the first two bits, the entire representation should consist
+            // of zeroes.
+            copy[0] &= 0x3f;
+
+            if copy.iter().all(|b| *b == 0) {
+                Ok(G2Affine::zero())
+            } else {
+                Err(GroupDecodingError::UnexpectedInformation)
+            }
+        } else {
+            // Determine if the intended y coordinate must be greater
+            // lexicographically.
+            let greatest = copy[0] & (1 << 7) != 0;
+
+            // Unset the two most significant bits.
+            copy[0] &= 0x3f;
+
+            let x_c1: FqRepr = repr_from_be(&copy[..32]);
+            let x_c0: FqRepr = repr_from_be(&copy[32..]);
+
+            // Interpret as Fq element.
+            let x = Fq2 {
+                c0: Fq::from_repr(x_c0).map_err(|e| {
+                    GroupDecodingError::CoordinateDecodingError("x coordinate (c0)", e)
+                })?,
+                c1: Fq::from_repr(x_c1).map_err(|e| {
+                    GroupDecodingError::CoordinateDecodingError("x coordinate (c1)", e)
+                })?,
+            };
+
+            G2Affine::get_point_from_x(x, greatest).ok_or(GroupDecodingError::NotOnCurve)
+        }
+    }
+    fn from_affine(affine: G2Affine) -> Self {
+        let mut res = Self::empty();
+
+        if affine.is_zero() {
+            // Set the second-most significant bit to indicate this point
+            // is at infinity.
+            res.0[0] |= 1 << 6;
+        } else {
+            repr_to_be(&affine.x.c1.into_repr(), &mut res.0[..32]);
+            repr_to_be(&affine.x.c0.into_repr(), &mut res.0[32..]);
+
+            let mut negy = affine.y;
+            negy.negate();
+
+            // Set the most significant bit if the correct y-coordinate
+            // is lexicographically largest.
+            if affine.y > negy {
+                res.0[0] |= 1 << 7;
+            }
+        }
+
+        res
+    }
+}
+
+impl G2Affine {
+    fn get_generator() -> Self {
+        G2Affine {
+            x: Fq2 {
+                c0: super::super::fq::G2_GENERATOR_X_C0,
+                c1: super::super::fq::G2_GENERATOR_X_C1,
+            },
+            y: Fq2 {
+                c0: super::super::fq::G2_GENERATOR_Y_C0,
+                c1: super::super::fq::G2_GENERATOR_Y_C1,
+            },
+            infinity: false,
+        }
+    }
+
+    fn get_coeff_b() -> Fq2 {
+        super::super::fq::B_COEFF_FQ2
+    }
+
+    fn scale_by_cofactor(&self) -> G2 {
+        // G2 cofactor = 2q - r
+        // 0x30644e72e131a029b85045b68181585e06ceecda572a2489345f2299c0f9fa8d
+        let cofactor = BitIterator::new([
+            0x345f2299c0f9fa8d,
+            0x06ceecda572a2489,
+            0xb85045b68181585e,
+            0x30644e72e131a029,
+        ]);
+        self.mul_bits(cofactor)
+    }
+
+    fn perform_pairing(&self, other: &G1Affine) -> Fq12 {
+        super::super::Bn256::pairing(*other, *self)
+    }
+}
+
+impl G2 {
+    fn empirical_recommended_wnaf_for_scalar(scalar: FrRepr) -> usize {
+        let num_bits = scalar.num_bits() as usize;
+
+        if num_bits >= 103 {
+            4
+        } else if num_bits >= 37 {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn empirical_recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize {
+        const RECOMMENDATIONS: [usize; 11] = [1, 3, 8, 20, 47, 126, 260, 826, 1501, 4555, 84071];
+
+        let mut ret = 4;
+        for r in &RECOMMENDATIONS {
+            if num_scalars > *r {
+                ret += 1;
+            } else {
+                break;
+            }
+        }
+
+        ret
+    }
+}
+
+/// A G2 element prepared for the Miller loop: the coefficients of the line
+/// functions, along with the element itself.
+#[derive(Clone, Debug)]
+pub struct G2Prepared {
+    pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
+    pub(crate) point: G2Affine,
+}
+
+impl SubgroupCheck for G2Affine {
+    fn in_subgroup(&self) -> bool {
+        self.is_on_curve() && self.is_in_correct_subgroup_assuming_on_curve()
+    }
+}
+
+#[test]
+fn g2_generator() {
+    let g2 = G2Affine::one();
+    assert!(g2.is_on_curve());
+    assert!(g2.in_subgroup());
+
+    // Points on the twist are generally not in G2 until the cofactor is cleared.
+    let mut x = Fq2::one();
+    loop {
+        if let Some(p) = G2Affine::get_point_from_x(x, false) {
+            assert!(p.is_on_curve());
+            assert!(!p.in_subgroup());
+            let q = p.scale_by_cofactor().into_affine();
+            assert!(q.in_subgroup());
+            break;
+        }
+        x.add_assign(&Fq2::one());
+    }
+}
+
+#[test]
+fn g2_test_is_valid() {
+    // Reject a point that is not on the curve
+    let mut p = G2Affine::one();
+    p.y.add_assign(&Fq2::one());
+    assert!(!p.is_on_curve());
+    assert!(p.into_uncompressed().into_affine().is_err());
+
+    // Reject a point on the curve outside of G2
+    let mut x = Fq2::one();
+    let p = loop {
+        if let Some(p) = G2Affine::get_point_from_x(x, true) {
+            break p;
+        }
+        x.add_assign(&Fq2::one());
+    };
+    match p.into_compressed().into_affine() {
+        Err(GroupDecodingError::NotInSubgroup) => {}
+        _ => panic!("should have rejected the point"),
+    }
+}
+
+#[test]
+fn g2_curve_tests() {
+    ::tests::curve::curve_tests::<G2>();
+}
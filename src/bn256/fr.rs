@@ -0,0 +1,61 @@
+use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+
+#[derive(PrimeField, Zeroize)]
+#[PrimeFieldModulus = "21888242871839275222246405745257275088548364400416034343698204186575808495617"]
+#[PrimeFieldGenerator = "5"]
+pub struct Fr(FrRepr);
+
+prime_field_bytes_impl!(Fr, FrRepr, 32);
+
+/// set the default value for Fr to 0
+impl ::core::default::Default for Fr {
+    fn default() -> Self {
+        Fr::zero()
+    }
+}
+
+/// # Safety
+pub const unsafe fn transmute(r: FrRepr) -> Fr {
+    Fr(r)
+}
+
+#[test]
+fn test_fr_num_bits() {
+    assert_eq!(Fr::NUM_BITS, 254);
+    assert_eq!(Fr::CAPACITY, 253);
+}
+
+#[test]
+fn test_fr_root_of_unity() {
+    use ff::SqrtField;
+
+    assert_eq!(Fr::S, 28);
+    assert_eq!(
+        Fr::multiplicative_generator(),
+        Fr::from_repr(FrRepr::from(5)).unwrap()
+    );
+    assert_eq!(
+        Fr::multiplicative_generator().pow([
+            0x9b9709143e1f593f,
+            0x181585d2833e8487,
+            0x131a029b85045b68,
+            0x000000030644e72e,
+        ]),
+        Fr::root_of_unity()
+    );
+    assert_eq!(Fr::root_of_unity().pow([1 << Fr::S]), Fr::one());
+    assert!(Fr::multiplicative_generator().sqrt().is_none());
+}
+
+#[test]
+fn fr_field_tests() {
+    ::tests::field::random_field_tests::<Fr>();
+    ::tests::field::random_sqrt_tests::<Fr>();
+    ::tests::field::random_frobenius_tests::<Fr, _>(Fr::char(), 13);
+    ::tests::field::from_str_tests::<Fr>();
+}
+
+#[test]
+fn fr_repr_tests() {
+    ::tests::repr::random_repr_tests::<Fr, FrRepr>();
+}
@@ -0,0 +1,582 @@
+/*!
+ This module implements the BN254 (also known as alt_bn128) pairing-friendly
+ curve, which is used by the Ethereum precompiles of EIP-196 and EIP-197.
+
+ The base field is extended as Fq2 = Fq[u]/(u^2 + 1),
+ Fq6 = Fq2[v]/(v^3 - (u + 9)) and Fq12 = Fq6[w]/(w^2 - v).
+ G1 is `y^2 = x^3 + 3` over Fq, and G2 lives on the D-type sextic twist
+ `y^2 = x^3 + 3 / (u + 9)` over Fq2. The pairing is the optimal ate pairing.
+*/
+
+mod ec;
+mod fq;
+mod fq12;
+mod fq2;
+mod fq6;
+mod fr;
+#[cfg(feature = "std")]
+mod serdes;
+
+pub use self::ec::{
+    G1Affine, G1Compressed, G1Prepared, G1Uncompressed, G2Affine, G2Compressed, G2Prepared,
+    G2Uncompressed, G1, G2,
+};
+pub use self::fq::{Fq, FqRepr};
+pub use self::fq12::Fq12;
+pub use self::fq2::Fq2;
+pub use self::fq6::Fq6;
+pub use self::fr::{Fr, FrRepr};
+
+pub mod transmute {
+    pub use super::ec::g1::transmute_affine as g1_affine;
+    pub use super::ec::g1::transmute_projective as g1_projective;
+    pub use super::ec::g2::transmute_affine as g2_affine;
+    pub use super::ec::g2::transmute_projective as g2_projective;
+    pub use super::fq::transmute as fq;
+    pub use super::fr::transmute as fr;
+}
+
+use super::{CurveAffine, Engine};
+
+use ff::{Field, ScalarEngine};
+
+// The BN parameter u for BN254 is 0x44e992b44a6909f1
+const BN_U: u64 = 4965661367192848881;
+
+// The non-adjacent form of 6u + 2, least significant digit first
+const SIX_U_PLUS_2_NAF: [i8; 66] = [
+    0, 0, 0, 1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 1, 0, 0, -1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 0, 0,
+    -1, 0, 0, 1, 0, -1, 0, 0, 1, 0, 0, 0, 0, 0, -1, 0, 0, -1, 0, 1, 0, -1, 0, 0, 0, -1, 0, -1, 0,
+    0, 0, 1, 0, -1, 0, 1,
+];
+
+#[derive(Clone, Debug)]
+pub struct Bn256;
+
+impl ScalarEngine for Bn256 {
+    type Fr = Fr;
+}
+
+impl Engine for Bn256 {
+    type G1 = G1;
+    type G1Affine = G1Affine;
+    type G2 = G2;
+    type G2Affine = G2Affine;
+    type Fq = Fq;
+    type Fqe = Fq2;
+    type Fqk = Fq12;
+
+    fn miller_loop<'a, I>(i: I) -> Self::Fqk
+    where
+        I: IntoIterator<
+            Item = &'a (
+                &'a <Self::G1Affine as CurveAffine>::Prepared,
+                &'a <Self::G2Affine as CurveAffine>::Prepared,
+            ),
+        >,
+    {
+        let mut pairs = vec![];
+        for &(p, q) in i {
+            if !p.is_zero() && !q.is_zero() {
+                pairs.push((p, q.coeffs.iter()));
+            }
+        }
+
+        // Twisting isomorphism from E to E'
+        fn ell(f: &mut Fq12, coeffs: &(Fq2, Fq2, Fq2), p: &G1Affine) {
+            let mut c0 = coeffs.0;
+            let mut c1 = coeffs.1;
+
+            c0.c0.mul_assign(&p.y);
+            c0.c1.mul_assign(&p.y);
+
+            c1.c0.mul_assign(&p.x);
+            c1.c1.mul_assign(&p.x);
+
+            // Sparse multiplication in Fq12
+            f.mul_by_034(&c0, &c1, &coeffs.2);
+        }
+
+        let mut f = Fq12::one();
+
+        for i in (1..SIX_U_PLUS_2_NAF.len()).rev() {
+            if i != SIX_U_PLUS_2_NAF.len() - 1 {
+                f.square();
+            }
+
+            for &mut (p, ref mut coeffs) in &mut pairs {
+                ell(&mut f, coeffs.next().unwrap(), &p.0);
+            }
+
+            if SIX_U_PLUS_2_NAF[i - 1] != 0 {
+                for &mut (p, ref mut coeffs) in &mut pairs {
+                    ell(&mut f, coeffs.next().unwrap(), &p.0);
+                }
+            }
+        }
+
+        // Lines through pi(Q) and -pi^2(Q)
+        for &mut (p, ref mut coeffs) in &mut pairs {
+            ell(&mut f, coeffs.next().unwrap(), &p.0);
+        }
+        for &mut (p, ref mut coeffs) in &mut pairs {
+            ell(&mut f, coeffs.next().unwrap(), &p.0);
+        }
+
+        f
+    }
+
+    fn final_exponentiation(r: &Fq12) -> Option<Fq12> {
+        let mut f1 = *r;
+        f1.conjugate();
+
+        match r.inverse() {
+            Some(mut f2) => {
+                // Easy part: r^((q^6 - 1) * (q^2 + 1))
+                let mut r = f1;
+                r.mul_assign(&f2);
+                f2 = r;
+                r.frobenius_map(2);
+                r.mul_assign(&f2);
+
+                // Hard part: r^((q^4 - q^2 + 1) / n), following
+                // Scott et al., "On the Final Exponentiation for Calculating
+                // Pairings on Ordinary Elliptic Curves", https://eprint.iacr.org/2008/490.pdf
+                fn exp_by_x(f: &mut Fq12) {
+                    *f = f.pow([BN_U]);
+                }
+
+                let mut fp = r;
+                fp.frobenius_map(1);
+                let mut fp2 = r;
+                fp2.frobenius_map(2);
+                let mut fp3 = fp2;
+                fp3.frobenius_map(1);
+
+                let mut fu = r;
+                exp_by_x(&mut fu);
+                let mut fu2 = fu;
+                exp_by_x(&mut fu2);
+                let mut fu3 = fu2;
+                exp_by_x(&mut fu3);
+
+                let mut y3 = fu;
+                y3.frobenius_map(1);
+                let mut fu2p = fu2;
+                fu2p.frobenius_map(1);
+                let mut fu3p = fu3;
+                fu3p.frobenius_map(1);
+                let mut y2 = fu2;
+                y2.frobenius_map(2);
+
+                let mut y0 = fp;
+                y0.mul_assign(&fp2);
+                y0.mul_assign(&fp3);
+
+                let mut y1 = r;
+                y1.conjugate();
+
+                let mut y5 = fu2;
+                y5.conjugate();
+
+                y3.conjugate();
+
+                let mut y4 = fu;
+                y4.mul_assign(&fu2p);
+                y4.conjugate();
+
+                let mut y6 = fu3;
+                y6.mul_assign(&fu3p);
+                y6.conjugate();
+
+                y6.square();
+                y6.mul_assign(&y4);
+                y6.mul_assign(&y5);
+
+                let mut t1 = y3;
+                t1.mul_assign(&y5);
+                t1.mul_assign(&y6);
+
+                y6.mul_assign(&y2);
+
+                t1.square();
+                t1.mul_assign(&y6);
+                t1.square();
+
+                let mut t0 = t1;
+                t0.mul_assign(&y1);
+
+                t1.mul_assign(&y0);
+
+                t0.square();
+                t0.mul_assign(&t1);
+
+                Some(t0)
+            }
+            None => None,
+        }
+    }
+}
+
+impl G2Prepared {
+    pub fn is_zero(&self) -> bool {
+        self.point.is_zero()
+    }
+
+    pub fn from_affine(q: G2Affine) -> Self {
+        if q.is_zero() {
+            return G2Prepared {
+                coeffs: vec![],
+                point: q,
+            };
+        }
+
+        fn doubling_step(r: &mut G2) -> (Fq2, Fq2, Fq2) {
+            // Adaptation of Algorithm 26, https://eprint.iacr.org/2010/354.pdf
+            let mut tmp0 = r.x;
+            tmp0.square();
+
+            let mut tmp1 = r.y;
+            tmp1.square();
+
+            let mut tmp2 = tmp1;
+            tmp2.square();
+
+            let mut tmp3 = tmp1;
+            tmp3.add_assign(&r.x);
+            tmp3.square();
+            tmp3.sub_assign(&tmp0);
+            tmp3.sub_assign(&tmp2);
+            tmp3.double();
+
+            let mut tmp4 = tmp0;
+            tmp4.double();
+            tmp4.add_assign(&tmp0);
+
+            let mut tmp6 = r.x;
+            tmp6.add_assign(&tmp4);
+
+            let mut tmp5 = tmp4;
+            tmp5.square();
+
+            let mut zsquared = r.z;
+            zsquared.square();
+
+            r.x = tmp5;
+            r.x.sub_assign(&tmp3);
+            r.x.sub_assign(&tmp3);
+
+            r.z.add_assign(&r.y);
+            r.z.square();
+            r.z.sub_assign(&tmp1);
+            r.z.sub_assign(&zsquared);
+
+            r.y = tmp3;
+            r.y.sub_assign(&r.x);
+            r.y.mul_assign(&tmp4);
+
+            tmp2.double();
+            tmp2.double();
+            tmp2.double();
+
+            r.y.sub_assign(&tmp2);
+
+            tmp3 = tmp4;
+            tmp3.mul_assign(&zsquared);
+            tmp3.double();
+            tmp3.negate();
+
+            tmp6.square();
+            tmp6.sub_assign(&tmp0);
+            tmp6.sub_assign(&tmp5);
+
+            tmp1.double();
+            tmp1.double();
+
+            tmp6.sub_assign(&tmp1);
+
+            tmp0 = r.z;
+            tmp0.mul_assign(&zsquared);
+            tmp0.double();
+
+            (tmp0, tmp3, tmp6)
+        }
+
+        fn addition_step(r: &mut G2, q: &G2Affine) -> (Fq2, Fq2, Fq2) {
+            // Adaptation of Algorithm 27, https://eprint.iacr.org/2010/354.pdf
+            let mut zsquared = r.z;
+            zsquared.square();
+
+            let mut ysquared = q.y;
+            ysquared.square();
+
+            let mut t0 = zsquared;
+            t0.mul_assign(&q.x);
+
+            let mut t1 = q.y;
+            t1.add_assign(&r.z);
+            t1.square();
+            t1.sub_assign(&ysquared);
+            t1.sub_assign(&zsquared);
+            t1.mul_assign(&zsquared);
+
+            let mut t2 = t0;
+            t2.sub_assign(&r.x);
+
+            let mut t3 = t2;
+            t3.square();
+
+            let mut t4 = t3;
+            t4.double();
+            t4.double();
+
+            let mut t5 = t4;
+            t5.mul_assign(&t2);
+
+            let mut t6 = t1;
+            t6.sub_assign(&r.y);
+            t6.sub_assign(&r.y);
+
+            let mut t9 = t6;
+            t9.mul_assign(&q.x);
+
+            let mut t7 = t4;
+            t7.mul_assign(&r.x);
+
+            r.x = t6;
+            r.x.square();
+            r.x.sub_assign(&t5);
+            r.x.sub_assign(&t7);
+            r.x.sub_assign(&t7);
+
+            r.z.add_assign(&t2);
+            r.z.square();
+            r.z.sub_assign(&zsquared);
+            r.z.sub_assign(&t3);
+
+            let mut t10 = q.y;
+            t10.add_assign(&r.z);
+
+            let mut t8 = t7;
+            t8.sub_assign(&r.x);
+            t8.mul_assign(&t6);
+
+            t0 = r.y;
+            t0.mul_assign(&t5);
+            t0.double();
+
+            r.y = t8;
+            r.y.sub_assign(&t0);
+
+            t10.square();
+            t10.sub_assign(&ysquared);
+
+            let mut ztsquared = r.z;
+            ztsquared.square();
+
+            t10.sub_assign(&ztsquared);
+
+            t9.double();
+            t9.sub_assign(&t10);
+
+            t10 = r.z;
+            t10.double();
+
+            t6.negate();
+
+            t1 = t6;
+            t1.double();
+
+            (t10, t1, t9)
+        }
+
+        let mut coeffs = vec![];
+        let mut r: G2 = q.into();
+
+        let mut neg_q = q;
+        neg_q.negate();
+
+        for i in (1..SIX_U_PLUS_2_NAF.len()).rev() {
+            coeffs.push(doubling_step(&mut r));
+
+            match SIX_U_PLUS_2_NAF[i - 1] {
+                1 => coeffs.push(addition_step(&mut r, &q)),
+                -1 => coeffs.push(addition_step(&mut r, &neg_q)),
+                _ => {}
+            }
+        }
+
+        // Q1 = pi(Q) and -Q2 = -pi^2(Q), where pi is the Frobenius endomorphism
+        // transported to the twist: (x, y) -> (x^q * xi^((q - 1) / 3), y^q * xi^((q - 1) / 2))
+        let mut q1 = q;
+        q1.x.frobenius_map(1);
+        q1.x.mul_assign(&fq::FROBENIUS_COEFF_FQ6_C1[1]);
+        q1.y.frobenius_map(1);
+        q1.y.mul_assign(&fq::XI_TO_Q_MINUS_1_OVER_2);
+
+        // xi^((q^2 - 1) / 2) = -1, so negating pi^2(Q) leaves y unchanged
+        let mut minus_q2 = q;
+        minus_q2.x.mul_assign(&fq::FROBENIUS_COEFF_FQ6_C1[2]);
+
+        coeffs.push(addition_step(&mut r, &q1));
+        coeffs.push(addition_step(&mut r, &minus_q2));
+
+        G2Prepared { coeffs, point: q }
+    }
+}
+
+#[test]
+fn bn256_engine_tests() {
+    ::tests::engine::engine_tests::<Bn256>();
+}
+
+#[test]
+fn test_bn256_final_exponentiation() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    // (q^12 - 1) / n
+    const EXP: [u64; 44] = [
+        0x86964b64ca86f120,
+        0x40a4efb7e54523a4,
+        0x837fa97896e84abb,
+        0x361102b6b9b2b918,
+        0xc0de81def35692da,
+        0xbe04c7e8a6c3c760,
+        0xd766f9c9d570bb7f,
+        0xc230974d83561841,
+        0x5bba1668c3be69a3,
+        0x7f3811c410526294,
+        0x29baee7ddadda71c,
+        0xbf813b8d145da900,
+        0x641bbadf423f9a2c,
+        0xa80bb4ea44eacc5e,
+        0xcd65664814fde37c,
+        0x4a0364b9580291d2,
+        0xee93dfb10826f0dd,
+        0x6b42db8dc5514724,
+        0xbb10cf430b0f3785,
+        0x40494e406f804216,
+        0x55cfe107acf3aafb,
+        0x2088ec80e0ebae87,
+        0x846a3ed011a337a0,
+        0x48a45a4a1e3a5195,
+        0xe5664568dfc50e16,
+        0xab6a41294c0cc4eb,
+        0x82d0d602d268c7da,
+        0x6668449aed3cc48a,
+        0x5062cd0fb2015dfc,
+        0x7f2940a8b1ddb3d1,
+        0x77f5b63a2a226448,
+        0xfef0781361e443ae,
+        0xf977870e88d5c6c8,
+        0x790364a61f676baa,
+        0x5887e72eceaddea3,
+        0x1377e563a09a1b70,
+        0x0c54efee1bd8c3b2,
+        0x3ec3d15ad524d8f7,
+        0xdaf15466b2383a5d,
+        0xe1e30a73bb94fec0,
+        0x6a1c71015f3f7be2,
+        0x842d43bf6369b1ff,
+        0x20fddadf107d20bc,
+        0x0000002f4b6dc970,
+    ];
+    for _ in 0..3 {
+        let f = Fq12::random(&mut rng);
+        assert_eq!(Bn256::final_exponentiation(&f).unwrap(), f.pow(&EXP[..]));
+    }
+    assert!(Bn256::final_exponentiation(&Fq12::zero()).is_none());
+}
+
+#[test]
+fn test_bn256_pairing_vectors() {
+    use std::vec::Vec;
+    use tests::hash_to_curve::from_hex;
+    use EncodedPoint;
+
+    // Decodes the input of the EIP-197 pairing precompile: a sequence of
+    // 192-byte (G1, G2) pairs laid out like our uncompressed encodings, with
+    // the point at infinity encoded as all zeroes.
+    fn pairing_check(input: &str) -> bool {
+        let input = from_hex(input);
+        assert_eq!(input.len() % 192, 0);
+        let (mut p, mut q) = (Vec::new(), Vec::new());
+        for pair in input.chunks(192) {
+            let mut g1 = G1Uncompressed::empty();
+            g1.as_mut().copy_from_slice(&pair[..64]);
+            let mut g2 = G2Uncompressed::empty();
+            g2.as_mut().copy_from_slice(&pair[64..]);
+            p.push(if pair[..64].iter().all(|b| *b == 0) {
+                G1Affine::zero()
+            } else {
+                g1.into_affine().unwrap()
+            });
+            q.push(if pair[64..].iter().all(|b| *b == 0) {
+                G2Affine::zero()
+            } else {
+                g2.into_affine().unwrap()
+            });
+        }
+        Bn256::pairing_multi_product(&p, &q) == Fq12::one()
+    }
+
+    // The generators used by the precompile
+    assert_eq!(
+        G1Uncompressed::from_affine(G1Affine::one()).as_ref(),
+        &from_hex(
+            "0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002"
+        )[..]
+    );
+    assert_eq!(
+        G2Uncompressed::from_affine(G2Affine::one()).as_ref(),
+        &from_hex(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+             1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+             090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+             12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+        )[..]
+    );
+
+    // go-ethereum's bn256Pairing test vectors
+    // jeff1
+    assert!(pairing_check(
+        "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+         3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+         209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+         04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+         2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+         120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+         111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+         2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+         198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+         1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+         090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+         12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+    ));
+    // empty_data
+    assert!(pairing_check(""));
+    // one_point
+    assert!(!pairing_check(
+        "0000000000000000000000000000000000000000000000000000000000000001\
+         0000000000000000000000000000000000000000000000000000000000000002\
+         198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+         1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+         090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+         12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+    ));
+    // A pair of points at infinity
+    assert!(pairing_check(&"00".repeat(192)));
+
+    // Points that are not on the curve are rejected
+    let mut g1 = G1Uncompressed::empty();
+    g1.as_mut().copy_from_slice(&[0x11; 64]);
+    assert!(g1.into_affine().is_err());
+    let mut g2 = G2Uncompressed::empty();
+    g2.as_mut().copy_from_slice(&[0x11; 128]);
+    assert!(g2.into_affine().is_err());
+}
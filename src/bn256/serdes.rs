@@ -0,0 +1,255 @@
+/*!
+ SerDes implementations for the BN254 types.
+
+ Unlike BLS12-381, the BN254 base field leaves only two spare bits in the
+ most significant byte, so the encodings do not carry a compression flag:
+ the `compressed` argument selects both the size and the format that is
+ expected on the wire.
+*/
+
+use super::{
+    Fq, Fq12, Fq2, Fq6, FqRepr, Fr, FrRepr, G1Affine, G1Compressed, G1Uncompressed, G2Affine,
+    G2Compressed, G2Uncompressed, G1, G2,
+};
+use ff::{PrimeField, PrimeFieldRepr};
+use serdes::{SerDes, SerDesError};
+use std::io::{Read, Write};
+use std::vec::Vec;
+use {CurveAffine, CurveProjective, EncodedPoint};
+
+type Compressed = bool;
+type Result<T> = ::std::result::Result<T, SerDesError>;
+
+fn write_encoded<P: EncodedPoint, W: Write>(writer: &mut W, affine: P::Affine) -> Result<()> {
+    Ok(writer.write_all(P::from_affine(affine).as_ref())?)
+}
+
+fn read_encoded<P: EncodedPoint, R: Read>(reader: &mut R) -> Result<P::Affine> {
+    P::read_affine(reader)
+}
+
+fn read_fq<R: Read>(reader: &mut R) -> Result<Fq> {
+    let mut q = FqRepr::default();
+    q.read_be(reader)?;
+    Ok(Fq::from_repr(q)?)
+}
+
+fn read_fq2<R: Read>(reader: &mut R) -> Result<Fq2> {
+    let c0 = read_fq(reader)?;
+    let c1 = read_fq(reader)?;
+    Ok(Fq2 { c0, c1 })
+}
+
+fn read_fq6<R: Read>(reader: &mut R) -> Result<Fq6> {
+    let c0 = read_fq2(reader)?;
+    let c1 = read_fq2(reader)?;
+    let c2 = read_fq2(reader)?;
+    Ok(Fq6 { c0, c1, c2 })
+}
+
+impl SerDes for Fr {
+    /// The compressed parameter has no effect since Fr element will always be compressed.
+    fn serialize<W: Write>(&self, writer: &mut W, _compressed: Compressed) -> Result<()> {
+        Ok(self.into_repr().write_be(writer)?)
+    }
+
+    /// The compressed parameter has no effect since Fr element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, _compressed: Compressed) -> Result<Self> {
+        let mut r = FrRepr::default();
+        r.read_be(reader)?;
+        Ok(Fr::from_repr(r)?)
+    }
+}
+
+impl SerDes for Fq12 {
+    /// The compressed parameter has no effect since Fq12 element will always be compressed.
+    /// The twelve Fq coefficients are written in the same order as for BLS12-381.
+    fn serialize<W: Write>(&self, writer: &mut W, _compressed: Compressed) -> Result<()> {
+        let mut buf: Vec<u8> = vec![];
+        for c in &[self.c0, self.c1] {
+            for d in &[c.c0, c.c1, c.c2] {
+                d.c0.into_repr().write_be(&mut buf)?;
+                d.c1.into_repr().write_be(&mut buf)?;
+            }
+        }
+        Ok(writer.write_all(&buf)?)
+    }
+
+    /// The compressed parameter has no effect since Fq12 element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, _compressed: Compressed) -> Result<Self> {
+        let c0 = read_fq6(reader)?;
+        let c1 = read_fq6(reader)?;
+        Ok(Fq12 { c0, c1 })
+    }
+}
+
+impl SerDes for G1Affine {
+    /// Convert a G1 point to a blob of 32 (compressed) or 64 (uncompressed) bytes.
+    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
+        if compressed {
+            write_encoded::<G1Compressed, W>(writer, *self)
+        } else {
+            write_encoded::<G1Uncompressed, W>(writer, *self)
+        }
+    }
+
+    /// Deserialize a G1 element from a blob.
+    /// Returns an error if deserialization fails.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
+        if compressed {
+            read_encoded::<G1Compressed, R>(reader)
+        } else {
+            read_encoded::<G1Uncompressed, R>(reader)
+        }
+    }
+}
+
+impl SerDes for G2Affine {
+    /// Convert a G2 point to a blob of 64 (compressed) or 128 (uncompressed) bytes.
+    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
+        if compressed {
+            write_encoded::<G2Compressed, W>(writer, *self)
+        } else {
+            write_encoded::<G2Uncompressed, W>(writer, *self)
+        }
+    }
+
+    /// Deserialize a G2 element from a blob.
+    /// Returns an error if deserialization fails.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
+        if compressed {
+            read_encoded::<G2Compressed, R>(reader)
+        } else {
+            read_encoded::<G2Uncompressed, R>(reader)
+        }
+    }
+}
+
+impl SerDes for G1 {
+    /// Convert a G1 point to a blob.
+    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
+        self.into_affine().serialize(writer, compressed)
+    }
+
+    /// Deserialize a G1 element from a blob.
+    /// Returns an error if deserialization fails.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
+        G1Affine::deserialize(reader, compressed).map(|p| p.into_projective())
+    }
+}
+
+impl SerDes for G2 {
+    /// Convert a G2 point to a blob.
+    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
+        self.into_affine().serialize(writer, compressed)
+    }
+
+    /// Deserialize a G2 element from a blob.
+    /// Returns an error if deserialization fails.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
+        G2Affine::deserialize(reader, compressed).map(|p| p.into_projective())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bn256::Bn256;
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use Engine;
+
+    fn roundtrip<T: SerDes + PartialEq + ::std::fmt::Debug>(t: &T, compressed: bool, len: usize) {
+        let mut buf: Vec<u8> = vec![];
+        assert!(t.serialize(&mut buf, compressed).is_ok());
+        assert_eq!(buf.len(), len, "length of blob is incorrect");
+        let recovered = T::deserialize(&mut buf[..].as_ref(), compressed).unwrap();
+        assert_eq!(*t, recovered);
+    }
+
+    #[test]
+    fn test_bn256_serialization_rand() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for &compressed in &[true, false] {
+            let (g1_len, g2_len) = if compressed { (32, 64) } else { (64, 128) };
+            for g1 in &[G1::zero(), G1::one(), G1::random(&mut rng)] {
+                roundtrip(g1, compressed, g1_len);
+                roundtrip(&g1.into_affine(), compressed, g1_len);
+            }
+            for g2 in &[G2::zero(), G2::one(), G2::random(&mut rng)] {
+                roundtrip(g2, compressed, g2_len);
+                roundtrip(&g2.into_affine(), compressed, g2_len);
+            }
+            roundtrip(&Fr::random(&mut rng), compressed, 32);
+        }
+
+        let gt = Bn256::pairing(G1::random(&mut rng), G2::random(&mut rng));
+        roundtrip(&gt, true, 384);
+    }
+
+    fn serialized<T: SerDes>(t: &T, compressed: bool) -> Vec<u8> {
+        let mut buf: Vec<u8> = vec![];
+        t.serialize(&mut buf, compressed).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_bn256_fixed_size_bytes() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for g1 in &[G1::zero(), G1::one(), G1::random(&mut rng)] {
+            assert_eq!(g1.to_bytes()[..], serialized(g1, true)[..]);
+            assert_eq!(g1.to_uncompressed()[..], serialized(g1, false)[..]);
+            assert_eq!(G1::from_bytes(&g1.to_bytes()), Some(*g1));
+            assert_eq!(G1::from_uncompressed(&g1.to_uncompressed()), Some(*g1));
+        }
+        for g2 in &[G2::zero(), G2::one(), G2::random(&mut rng)] {
+            let q = g2.into_affine();
+            assert_eq!(q.to_bytes()[..], serialized(&q, true)[..]);
+            assert_eq!(q.to_uncompressed()[..], serialized(&q, false)[..]);
+            assert_eq!(G2Affine::from_bytes(&q.to_bytes()), Some(q));
+            assert_eq!(G2Affine::from_uncompressed(&q.to_uncompressed()), Some(q));
+        }
+        let fr = Fr::random(&mut rng);
+        assert_eq!(fr.to_bytes()[..], serialized(&fr, true)[..]);
+        assert_eq!(Fr::from_bytes(&fr.to_bytes()), Some(fr));
+        let gt = Bn256::pairing(G1::random(&mut rng), G2::random(&mut rng));
+        assert_eq!(gt.to_bytes()[..], serialized(&gt, true)[..]);
+        assert_eq!(Fq12::from_bytes(&gt.to_bytes()), Some(gt));
+
+        // the field modulus itself is not a valid Fr element
+        let mut bytes = [0u8; 32];
+        Fr::char().write_be(&mut &mut bytes[..]).unwrap();
+        assert_eq!(Fr::from_bytes(&bytes), None);
+        // (1, 3) is not on the curve
+        let mut bytes = [0u8; 64];
+        bytes[31] = 1;
+        bytes[63] = 3;
+        assert_eq!(G1Affine::from_uncompressed(&bytes), None);
+    }
+
+    #[test]
+    fn test_bn256_deserialization_errors() {
+        // a compressed point is too short to be read as an uncompressed one
+        let mut buf: Vec<u8> = vec![];
+        G1::one().serialize(&mut buf, true).unwrap();
+        assert!(G1::deserialize(&mut buf[..].as_ref(), false).is_err());
+
+        // the field modulus itself is not a valid Fr element
+        let mut buf: Vec<u8> = vec![];
+        Fr::char().write_be(&mut buf).unwrap();
+        assert!(Fr::deserialize(&mut buf[..].as_ref(), true).is_err());
+
+        // (1, 3) is not on the curve
+        let mut buf = [0u8; 64];
+        buf[31] = 1;
+        buf[63] = 3;
+        assert!(G1Affine::deserialize(&mut buf[..].as_ref(), false).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+/*!
+ This module defines macros that implement fixed-size byte encodings as
+ inherent `to_bytes` and `from_bytes` methods, which neither allocate nor go
+ through `std::io`, along with the helpers that convert between `PrimeFieldRepr`
+ limbs and big-endian bytes, which the `EncodedPoint` impls also use.
+
+ Prime field elements are encoded in canonical big-endian form, and extension
+ field elements as the concatenation of the encodings of their coefficients,
+ as in `SerDes`. Group elements use the encodings of their `EncodedPoint`
+ types, with `to_bytes` giving the compressed form.
+
+ The macros also implement `to_hex`, `from_hex` and `FromStr` with the hex
+ strings of the `to_bytes` encodings, which are what the serde impls use for
+ human-readable formats. Note that `FromStr` parses hex, while the decimal
+ `PrimeField::from_str` of the prime fields is unchanged.
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use ff::PrimeFieldRepr;
+use HexDecodingError;
+
+/// Reads a big-endian integer into a representation. Panics unless `bytes`
+/// holds exactly 8 bytes per limb.
+pub(crate) fn repr_from_be<R: PrimeFieldRepr>(bytes: &[u8]) -> R {
+    let mut repr = R::default();
+    assert_eq!(bytes.len(), 8 * repr.as_ref().len());
+    for (limb, chunk) in repr.as_mut().iter_mut().rev().zip(bytes.chunks(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(chunk);
+        *limb = u64::from_be_bytes(buf);
+    }
+    repr
+}
+
+/// Writes a representation as a big-endian integer. Panics unless `out`
+/// holds exactly 8 bytes per limb.
+pub(crate) fn repr_to_be<R: PrimeFieldRepr>(repr: &R, out: &mut [u8]) {
+    assert_eq!(out.len(), 8 * repr.as_ref().len());
+    for (chunk, limb) in out.chunks_mut(8).zip(repr.as_ref().iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+}
+
+/// Encodes bytes as a lowercase hex string.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        s.push(DIGITS[(b >> 4) as usize] as char);
+        s.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+/// Decodes a hex string, accepting both lower and upper case digits.
+pub(crate) fn from_hex(s: &str) -> Result<Vec<u8>, HexDecodingError> {
+    fn digit(c: u8) -> Result<u8, HexDecodingError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(HexDecodingError::InvalidDigit(c as char)),
+        }
+    }
+
+    if s.len() & 1 == 1 {
+        return Err(HexDecodingError::OddLength);
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|p| Ok(digit(p[0])? << 4 | digit(p[1])?))
+        .collect()
+}
+
+/// Decodes a hex string of exactly `out.len()` bytes into `out`.
+pub(crate) fn from_hex_exact(s: &str, out: &mut [u8]) -> Result<(), HexDecodingError> {
+    let bytes = from_hex(s)?;
+    if bytes.len() != out.len() {
+        return Err(HexDecodingError::InvalidLength);
+    }
+    out.copy_from_slice(&bytes);
+    Ok(())
+}
+
+/// Implements `to_hex`, `from_hex` and `FromStr` in terms of `to_bytes` and `from_bytes`.
+macro_rules! hex_impl {
+    ($type:ident, $size:expr) => {
+        impl $type {
+            /// Returns the `to_bytes` encoding as a lowercase hex string.
+            pub fn to_hex(&self) -> ::alloc::string::String {
+                ::encoding_macros::to_hex(&self.to_bytes())
+            }
+
+            /// Decodes the hex string of a `to_bytes` encoding, in lower or upper case.
+            pub fn from_hex(s: &str) -> Result<$type, ::HexDecodingError> {
+                let mut bytes = [0u8; $size];
+                ::encoding_macros::from_hex_exact(s, &mut bytes)?;
+                $type::from_bytes(&bytes).ok_or(::HexDecodingError::InvalidEncoding)
+            }
+        }
+
+        /// Parses the hex string of a `to_bytes` encoding, as `from_hex`.
+        impl ::core::str::FromStr for $type {
+            type Err = ::HexDecodingError;
+
+            fn from_str(s: &str) -> Result<$type, ::HexDecodingError> {
+                $type::from_hex(s)
+            }
+        }
+    };
+}
+
+macro_rules! prime_field_bytes_impl {
+    ($field:ident, $repr:ident, $size:expr) => {
+        impl $field {
+            /// Returns the canonical big-endian encoding of the element.
+            pub fn to_bytes(&self) -> [u8; $size] {
+                let mut out = [0u8; $size];
+                ::encoding_macros::repr_to_be(&::ff::PrimeField::into_repr(self), &mut out);
+                out
+            }
+
+            /// Decodes a big-endian encoding, returning None if it is not
+            /// smaller than the modulus.
+            pub fn from_bytes(bytes: &[u8; $size]) -> Option<$field> {
+                let repr: $repr = ::encoding_macros::repr_from_be(bytes);
+                <$field as ::ff::PrimeField>::from_repr(repr).ok()
+            }
+        }
+
+        hex_impl!($field, $size);
+    };
+}
+
+macro_rules! extension_field_bytes_impl {
+    ($field:ident, $size:expr, $base:ident, $base_size:expr, $($coeff:ident),+) => {
+        impl $field {
+            /// Returns the encodings of the coefficients, concatenated in order.
+            pub fn to_bytes(&self) -> [u8; $size] {
+                let mut out = [0u8; $size];
+                let mut chunks = out.chunks_mut($base_size);
+                $(chunks.next().unwrap().copy_from_slice(&self.$coeff.to_bytes());)+
+                out
+            }
+
+            /// Decodes the concatenated encodings of the coefficients,
+            /// returning None if any of them is invalid.
+            pub fn from_bytes(bytes: &[u8; $size]) -> Option<$field> {
+                let mut chunks = bytes.chunks($base_size);
+                let mut next = || {
+                    let mut buf = [0u8; $base_size];
+                    buf.copy_from_slice(chunks.next().unwrap());
+                    $base::from_bytes(&buf)
+                };
+                Some($field {
+                    $($coeff: next()?,)+
+                })
+            }
+        }
+
+        hex_impl!($field, $size);
+    };
+}
+
+macro_rules! group_bytes_impl {
+    (
+        $projective:ident,
+        $affine:ident,
+        $compressed:ident,
+        $compressed_size:expr,
+        $uncompressed:ident,
+        $uncompressed_size:expr
+    ) => {
+        impl $affine {
+            /// Returns the compressed encoding of the point.
+            pub fn to_bytes(&self) -> [u8; $compressed_size] {
+                <$compressed as ::EncodedPoint>::from_affine(*self).0
+            }
+
+            /// Decodes a compressed encoding, returning None unless it is a
+            /// valid element of the group.
+            pub fn from_bytes(bytes: &[u8; $compressed_size]) -> Option<$affine> {
+                ::EncodedPoint::into_affine(&$compressed(*bytes)).ok()
+            }
+
+            /// Returns the uncompressed encoding of the point.
+            pub fn to_uncompressed(&self) -> [u8; $uncompressed_size] {
+                <$uncompressed as ::EncodedPoint>::from_affine(*self).0
+            }
+
+            /// Decodes an uncompressed encoding, returning None unless it is
+            /// a valid element of the group.
+            pub fn from_uncompressed(bytes: &[u8; $uncompressed_size]) -> Option<$affine> {
+                ::EncodedPoint::into_affine(&$uncompressed(*bytes)).ok()
+            }
+        }
+
+        impl $projective {
+            /// Returns the compressed encoding of the point.
+            pub fn to_bytes(&self) -> [u8; $compressed_size] {
+                ::CurveProjective::into_affine(self).to_bytes()
+            }
+
+            /// Decodes a compressed encoding, returning None unless it is a
+            /// valid element of the group.
+            pub fn from_bytes(bytes: &[u8; $compressed_size]) -> Option<$projective> {
+                $affine::from_bytes(bytes).map(|p| ::CurveAffine::into_projective(&p))
+            }
+
+            /// Returns the uncompressed encoding of the point.
+            pub fn to_uncompressed(&self) -> [u8; $uncompressed_size] {
+                ::CurveProjective::into_affine(self).to_uncompressed()
+            }
+
+            /// Decodes an uncompressed encoding, returning None unless it is
+            /// a valid element of the group.
+            pub fn from_uncompressed(bytes: &[u8; $uncompressed_size]) -> Option<$projective> {
+                $affine::from_uncompressed(bytes).map(|p| ::CurveAffine::into_projective(&p))
+            }
+        }
+
+        hex_impl!($affine, $compressed_size);
+        hex_impl!($projective, $compressed_size);
+    };
+}
@@ -0,0 +1,499 @@
+/*!
+ This module implements radix-2 FFTs over prime fields with large 2-adic
+ subgroups, such as `bls12_381::Fr` (`S = 32`).
+
+ An `EvaluationDomain` of size `n = 2^k` is the subgroup generated by an
+ `n`-th root of unity `omega`. The forward FFT maps the coefficients of a
+ polynomial of degree less than `n` to its evaluations at `omega^i`; the
+ coset FFT instead evaluates at `g * omega^i`, where `g` is the field's
+ multiplicative generator. With the `parallel` feature, large transforms
+ are split across threads.
+*/
+
+use alloc::vec::Vec;
+use ff::PrimeField;
+
+/// A multiplicative subgroup of size `2^log_size` of a prime field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvaluationDomain<F: PrimeField> {
+    size: usize,
+    log_size: u32,
+    omega: F,
+    omega_inv: F,
+    size_inv: F,
+    generator: F,
+    generator_inv: F,
+}
+
+impl<F: PrimeField> EvaluationDomain<F> {
+    /// Returns the smallest domain with at least `min_size` elements,
+    /// or `None` if the field has no subgroup that large.
+    pub fn new(min_size: usize) -> Option<Self> {
+        let size = min_size.checked_next_power_of_two()?;
+        let log_size = size.trailing_zeros();
+        if log_size > F::S {
+            return None;
+        }
+
+        // omega = root_of_unity^(2^(S - log_size)) has order exactly 2^log_size
+        let mut omega = F::root_of_unity();
+        for _ in log_size..F::S {
+            omega.square();
+        }
+        // unwraps are safe: these are all nonzero
+        let size_inv = F::from_repr((size as u64).into())
+            .unwrap()
+            .inverse()
+            .unwrap();
+        Some(EvaluationDomain {
+            size,
+            log_size,
+            omega,
+            omega_inv: omega.inverse().unwrap(),
+            size_inv,
+            generator: F::multiplicative_generator(),
+            generator_inv: F::multiplicative_generator().inverse().unwrap(),
+        })
+    }
+
+    /// Returns the number of elements of the domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the base-2 logarithm of the size of the domain.
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the generator `omega` of the domain.
+    pub fn omega(&self) -> F {
+        self.omega
+    }
+
+    /// Returns the shift `g` of the coset used by `coset_fft` and `coset_ifft`.
+    pub fn coset_shift(&self) -> F {
+        self.generator
+    }
+
+    /// Returns the elements `1, omega, omega^2, ...` of the domain.
+    pub fn elements(&self) -> Vec<F> {
+        powers(&self.omega, F::one(), self.size)
+    }
+
+    /// Evaluates the vanishing polynomial of the domain, `x^n - 1`, at `x`.
+    pub fn evaluate_vanishing_polynomial(&self, x: &F) -> F {
+        let mut res = x.pow([self.size as u64]);
+        res.sub_assign(&F::one());
+        res
+    }
+
+    /// Replaces the coefficients in `a` with the evaluations of the polynomial on the domain.
+    /// `a` is zero-padded to the size of the domain.
+    ///
+    /// # Panics
+    /// Panics if `a` has more elements than the domain.
+    pub fn fft(&self, a: &mut Vec<F>) {
+        self.resize(a);
+        best_fft(a, &self.omega, self.log_size);
+    }
+
+    /// Replaces the evaluations in `a` on the domain with the coefficients of the
+    /// interpolating polynomial. `a` is zero-padded to the size of the domain.
+    pub fn ifft(&self, a: &mut Vec<F>) {
+        self.resize(a);
+        best_fft(a, &self.omega_inv, self.log_size);
+        for v in a.iter_mut() {
+            v.mul_assign(&self.size_inv);
+        }
+    }
+
+    /// Like `fft`, but evaluates on the coset `g * omega^i` of the domain.
+    pub fn coset_fft(&self, a: &mut Vec<F>) {
+        self.resize(a);
+        distribute_powers(a, &self.generator);
+        best_fft(a, &self.omega, self.log_size);
+    }
+
+    /// Inverse of `coset_fft`.
+    pub fn coset_ifft(&self, a: &mut Vec<F>) {
+        self.ifft(a);
+        distribute_powers(a, &self.generator_inv);
+    }
+
+    /// Divides the evaluations `a` on the coset `g * omega^i` by the vanishing polynomial
+    /// of the domain, which is the constant `g^n - 1` on the coset.
+    pub fn divide_by_vanishing_poly_on_coset(&self, a: &mut [F]) {
+        // unwrap is safe: g is not an n-th root of unity
+        let z_inv = self
+            .evaluate_vanishing_polynomial(&self.generator)
+            .inverse()
+            .unwrap();
+        for v in a.iter_mut() {
+            v.mul_assign(&z_inv);
+        }
+    }
+
+    fn resize(&self, a: &mut Vec<F>) {
+        assert!(a.len() <= self.size, "too many elements for the domain");
+        a.resize(self.size, F::zero());
+    }
+}
+
+/// Returns `start, start * x, start * x^2, ...` (`n` elements).
+fn powers<F: PrimeField>(x: &F, start: F, n: usize) -> Vec<F> {
+    let mut res = Vec::with_capacity(n);
+    let mut cur = start;
+    for _ in 0..n {
+        res.push(cur);
+        cur.mul_assign(x);
+    }
+    res
+}
+
+/// Multiplies `a[i]` by `g^i`.
+fn distribute_powers<F: PrimeField>(a: &mut [F], g: &F) {
+    let mut u = F::one();
+    for v in a.iter_mut() {
+        v.mul_assign(&u);
+        u.mul_assign(g);
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn best_fft<F: PrimeField>(a: &mut [F], omega: &F, log_n: u32) {
+    serial_fft(a, omega, log_n);
+}
+
+#[cfg(feature = "parallel")]
+fn best_fft<F: PrimeField>(a: &mut [F], omega: &F, log_n: u32) {
+    let cpus = ::std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    // round down to a power of two
+    let log_cpus = (usize::BITS - 1 - cpus.leading_zeros()) as u32;
+    if log_n <= log_cpus || log_cpus == 0 {
+        serial_fft(a, omega, log_n);
+    } else {
+        parallel_fft(a, omega, log_n, log_cpus);
+    }
+}
+
+/// In-place iterative Cooley-Tukey FFT; `a.len()` must be `2^log_n`
+/// and `omega` a primitive `2^log_n`-th root of unity.
+fn serial_fft<F: PrimeField>(a: &mut [F], omega: &F, log_n: u32) {
+    fn bitreverse(mut n: usize, l: u32) -> usize {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    let n = a.len();
+    assert_eq!(n, 1 << log_n);
+
+    for k in 0..n {
+        let rk = bitreverse(k, log_n);
+        if k < rk {
+            a.swap(rk, k);
+        }
+    }
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        let w_m = omega.pow([(n / (2 * m)) as u64]);
+
+        let mut k = 0;
+        while k < n {
+            let mut w = F::one();
+            for j in 0..m {
+                let mut t = a[k + j + m];
+                t.mul_assign(&w);
+                let mut tmp = a[k + j];
+                tmp.sub_assign(&t);
+                a[k + j + m] = tmp;
+                a[k + j].add_assign(&t);
+                w.mul_assign(&w_m);
+            }
+
+            k += 2 * m;
+        }
+
+        m *= 2;
+    }
+}
+
+/// Splits an FFT of size `2^log_n` into `2^log_cpus` independent FFTs of size
+/// `2^(log_n - log_cpus)`, each computed on its own thread.
+#[cfg(feature = "parallel")]
+fn parallel_fft<F: PrimeField>(a: &mut [F], omega: &F, log_n: u32, log_cpus: u32) {
+    assert!(log_n >= log_cpus);
+
+    let num_cpus = 1 << log_cpus;
+    let log_new_n = log_n - log_cpus;
+    let mut tmp = vec![vec![F::zero(); 1 << log_new_n]; num_cpus];
+    let new_omega = omega.pow([num_cpus as u64]);
+
+    {
+        let a = &*a;
+        ::std::thread::scope(|scope| {
+            for (j, tmp) in tmp.iter_mut().enumerate() {
+                scope.spawn(move || {
+                    // Shuffle into a sub-FFT
+                    let omega_j = omega.pow([j as u64]);
+                    let omega_step = omega.pow([(j as u64) << log_new_n]);
+
+                    let mut elt = F::one();
+                    for (i, t) in tmp.iter_mut().enumerate() {
+                        for s in 0..num_cpus {
+                            let idx = (i + (s << log_new_n)) % (1 << log_n);
+                            let mut v = a[idx];
+                            v.mul_assign(&elt);
+                            t.add_assign(&v);
+                            elt.mul_assign(&omega_step);
+                        }
+                        elt.mul_assign(&omega_j);
+                    }
+
+                    // Perform sub-FFT
+                    serial_fft(tmp, &new_omega, log_new_n);
+                });
+            }
+        });
+    }
+
+    let mask = num_cpus - 1;
+    for (idx, v) in a.iter_mut().enumerate() {
+        *v = tmp[idx & mask][idx >> log_cpus];
+    }
+}
+
+/// Multiplies two polynomials given by their coefficients (in order of increasing degree).
+///
+/// # Panics
+/// Panics if the product has more coefficients than the largest 2-adic subgroup of the field.
+pub fn mul_polynomials<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let res_len = a.len() + b.len() - 1;
+    let domain = EvaluationDomain::new(res_len).expect("product is too large for an FFT");
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    domain.fft(&mut a);
+    domain.fft(&mut b);
+    for (x, y) in a.iter_mut().zip(b.iter()) {
+        x.mul_assign(y);
+    }
+    domain.ifft(&mut a);
+    a.truncate(res_len);
+    a
+}
+
+/// Divides `poly` by the vanishing polynomial `x^n - 1` of `domain`, using a coset FFT.
+/// Returns `None` if `poly` is not divisible, i.e., if it does not vanish on `domain`.
+///
+/// # Panics
+/// Panics if `poly` has more coefficients than the largest 2-adic subgroup of the field.
+pub fn divide_by_vanishing_polynomial<F: PrimeField>(
+    poly: &[F],
+    domain: &EvaluationDomain<F>,
+) -> Option<Vec<F>> {
+    let n = domain.size();
+    if poly.iter().all(|c| c.is_zero()) {
+        return Some(vec![]);
+    }
+    if poly.len() <= n {
+        return None;
+    }
+
+    // Evaluate on a coset of a domain large enough to hold poly, where x^n - 1 never vanishes.
+    let big = EvaluationDomain::<F>::new(poly.len()).expect("polynomial is too large for an FFT");
+    let mut evals = poly.to_vec();
+    big.coset_fft(&mut evals);
+
+    // The values of x^n - 1 on the big coset repeat with period big.size() / n.
+    let period = big.size() / n;
+    let mut z_inv = Vec::with_capacity(period);
+    let g_n = big.coset_shift().pow([n as u64]);
+    let omega_n = big.omega().pow([n as u64]);
+    let mut cur = g_n;
+    for _ in 0..period {
+        let mut z = cur;
+        z.sub_assign(&F::one());
+        // unwrap is safe: (g omega^i)^n = 1 would make g a root of unity
+        z_inv.push(z.inverse().unwrap());
+        cur.mul_assign(&omega_n);
+    }
+    for (i, v) in evals.iter_mut().enumerate() {
+        v.mul_assign(&z_inv[i % period]);
+    }
+    big.coset_ifft(&mut evals);
+    evals.truncate(poly.len() - n);
+
+    // Check that quotient * (x^n - 1) == poly.
+    let mut expect = vec![F::zero(); poly.len()];
+    for (i, q) in evals.iter().enumerate() {
+        expect[i + n].add_assign(q);
+        expect[i].sub_assign(q);
+    }
+    if expect[..] == poly[..] {
+        Some(evals)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Fr;
+    use ff::Field;
+    use rand_core::SeedableRng;
+
+    fn evaluate(poly: &[Fr], x: &Fr) -> Fr {
+        let mut res = Fr::zero();
+        for c in poly.iter().rev() {
+            res.mul_assign(x);
+            res.add_assign(c);
+        }
+        res
+    }
+
+    fn naive_mul(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+        let mut res = vec![Fr::zero(); a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                let mut tmp = *x;
+                tmp.mul_assign(y);
+                res[i + j].add_assign(&tmp);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_fft_domain() {
+        assert!(EvaluationDomain::<Fr>::new(1 << 33).is_none());
+        let d = EvaluationDomain::<Fr>::new(5).unwrap();
+        assert_eq!(d.size(), 8);
+        assert_eq!(d.log_size(), 3);
+        assert_eq!(d.omega().pow([8]), Fr::one());
+        assert_ne!(d.omega().pow([4]), Fr::one());
+        for x in d.elements() {
+            assert!(d.evaluate_vanishing_polynomial(&x).is_zero());
+        }
+        assert_eq!(EvaluationDomain::<Fr>::new(1).unwrap().size(), 1);
+    }
+
+    #[test]
+    fn test_fft_roundtrip() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for log_n in 0..8 {
+            let d = EvaluationDomain::<Fr>::new(1 << log_n).unwrap();
+            let poly: Vec<Fr> = (0..(1 << log_n)).map(|_| Fr::random(&mut rng)).collect();
+
+            let mut evals = poly.clone();
+            d.fft(&mut evals);
+            for (x, y) in d.elements().iter().zip(evals.iter()) {
+                assert_eq!(evaluate(&poly, x), *y);
+            }
+            d.ifft(&mut evals);
+            assert_eq!(evals, poly);
+
+            let mut coset_evals = poly.clone();
+            d.coset_fft(&mut coset_evals);
+            for (x, y) in d.elements().iter().zip(coset_evals.iter()) {
+                let mut gx = *x;
+                gx.mul_assign(&d.coset_shift());
+                assert_eq!(evaluate(&poly, &gx), *y);
+            }
+            d.coset_ifft(&mut coset_evals);
+            assert_eq!(coset_evals, poly);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_fft_consistency() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for log_n in 1..10 {
+            let d = EvaluationDomain::<Fr>::new(1 << log_n).unwrap();
+            let poly: Vec<Fr> = (0..(1 << log_n)).map(|_| Fr::random(&mut rng)).collect();
+            for log_cpus in 0..log_n.min(3) {
+                let mut a = poly.clone();
+                let mut b = poly.clone();
+                serial_fft(&mut a, &d.omega(), log_n);
+                parallel_fft(&mut b, &d.omega(), log_n, log_cpus);
+                assert_eq!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_mul_polynomials() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for &(la, lb) in &[(1, 1), (3, 5), (16, 17), (33, 2)] {
+            let a: Vec<Fr> = (0..la).map(|_| Fr::random(&mut rng)).collect();
+            let b: Vec<Fr> = (0..lb).map(|_| Fr::random(&mut rng)).collect();
+            assert_eq!(mul_polynomials(&a, &b), naive_mul(&a, &b));
+        }
+        assert!(mul_polynomials::<Fr>(&[], &[Fr::one()]).is_empty());
+    }
+
+    #[test]
+    fn test_fft_divide_by_vanishing() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let d = EvaluationDomain::<Fr>::new(8).unwrap();
+        let mut z = vec![Fr::zero(); 9];
+        z[0].sub_assign(&Fr::one());
+        z[8] = Fr::one();
+
+        let q: Vec<Fr> = (0..11).map(|_| Fr::random(&mut rng)).collect();
+        let mut p = naive_mul(&q, &z);
+        assert_eq!(divide_by_vanishing_polynomial(&p, &d), Some(q));
+
+        p[3].add_assign(&Fr::one());
+        assert_eq!(divide_by_vanishing_polynomial(&p, &d), None);
+        assert_eq!(divide_by_vanishing_polynomial(&p[..8], &d), None);
+
+        // dividing coset evaluations of Z * q by Z on the coset
+        let q: Vec<Fr> = (0..8).map(|_| Fr::random(&mut rng)).collect();
+        let d16 = EvaluationDomain::<Fr>::new(16).unwrap();
+        let mut evals = naive_mul(&q, &z);
+        d16.coset_fft(&mut evals);
+        let mut zevals = z.clone();
+        d16.coset_fft(&mut zevals);
+        for (e, zv) in evals.iter_mut().zip(zevals.iter()) {
+            e.mul_assign(&zv.inverse().unwrap());
+        }
+        d16.coset_ifft(&mut evals);
+        evals.truncate(8);
+        assert_eq!(evals, q);
+
+        let mut qz = naive_mul(&q[..], &[Fr::one()]);
+        d.coset_fft(&mut qz);
+        let mut expect = qz.clone();
+        for (i, v) in expect.iter_mut().enumerate() {
+            let mut x = d.elements()[i];
+            x.mul_assign(&d.coset_shift());
+            v.mul_assign(&d.evaluate_vanishing_polynomial(&x));
+        }
+        d.divide_by_vanishing_poly_on_coset(&mut expect);
+        assert_eq!(expect, qz);
+    }
+}
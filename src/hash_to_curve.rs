@@ -2,9 +2,9 @@
  This module defines a hash_to_curve trait.
 */
 
-use bls12_381::{ClearH, IsogenyMap, OSSWUMap};
 use hash_to_field::{hash_to_field, ExpandMsg, FromRO};
 use map_to_curve::MapToCurve;
+use osswu_map::{ClearH, IsogenyMap, OSSWUMap};
 use CurveProjective;
 use SubgroupCheck;
 
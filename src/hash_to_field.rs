@@ -3,9 +3,11 @@
  for use with BLS signatures.
 */
 
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 use digest::generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
-use digest::{BlockInput, Digest, ExtendableOutput, Input};
-use std::marker::PhantomData;
+use digest::{BlockInput, Digest, ExtendableOutput, Input, XofReader};
+use zeroize::Zeroize;
 
 /// hash_to_field for type T using ExpandMsg variant X
 pub fn hash_to_field<T, X>(msg: &[u8], dst: &[u8], count: usize) -> Vec<T>
@@ -15,7 +17,7 @@ where
 {
     let len_per_elm = <T as FromRO>::Length::to_usize();
     let len_in_bytes = count * len_per_elm;
-    let pseudo_random_bytes = X::expand_message(msg, dst, len_in_bytes);
+    let mut pseudo_random_bytes = X::expand_message(msg, dst, len_in_bytes);
 
     let mut ret = Vec::<T>::with_capacity(count);
     for idx in 0..count {
@@ -23,6 +25,7 @@ where
         let bytes_arr = GenericArray::<u8, <T as FromRO>::Length>::from_slice(bytes_to_convert);
         ret.push(T::from_ro(bytes_arr));
     }
+    pseudo_random_bytes.zeroize();
 
     ret
 }
@@ -68,12 +71,15 @@ where
     HashT: Default + ExtendableOutput + Input,
 {
     fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; len_in_bytes];
         HashT::default()
             .chain(msg)
             .chain([(len_in_bytes >> 8) as u8, len_in_bytes as u8])
             .chain(dst)
             .chain([dst.len() as u8])
-            .vec_result(len_in_bytes)
+            .xof_result()
+            .read(&mut buf);
+        buf
     }
 }
 
@@ -94,7 +100,7 @@ where
         if ell > 255 {
             panic!("ell was too big in expand_message_xmd");
         }
-        let b_0 = HashT::new()
+        let mut b_0 = HashT::new()
             .chain(GenericArray::<u8, <HashT as BlockInput>::BlockSize>::default())
             .chain(msg)
             .chain([(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0u8])
@@ -104,15 +110,13 @@ where
 
         let mut b_vals = Vec::<u8>::with_capacity(ell * b_in_bytes);
         // b_1
-        b_vals.extend_from_slice(
-            HashT::new()
-                .chain(&b_0[..])
-                .chain([1u8])
-                .chain(dst)
-                .chain([dst.len() as u8])
-                .result()
-                .as_ref(),
-        );
+        let mut b_i = HashT::new()
+            .chain(&b_0[..])
+            .chain([1u8])
+            .chain(dst)
+            .chain([dst.len() as u8])
+            .result();
+        b_vals.extend_from_slice(&b_i);
 
         for idx in 1..ell {
             // b_0 XOR b_(idx - 1)
@@ -121,17 +125,20 @@ where
                 .zip(&b_vals[(idx - 1) * b_in_bytes..idx * b_in_bytes])
                 .enumerate()
                 .for_each(|(jdx, (b0val, bi1val))| tmp[jdx] = b0val ^ bi1val);
-            b_vals.extend_from_slice(
-                HashT::new()
-                    .chain(tmp)
-                    .chain([(idx + 1) as u8])
-                    .chain(dst)
-                    .chain([dst.len() as u8])
-                    .result()
-                    .as_ref(),
-            );
+            b_i = HashT::new()
+                .chain(&tmp[..])
+                .chain([(idx + 1) as u8])
+                .chain(dst)
+                .chain([dst.len() as u8])
+                .result();
+            b_vals.extend_from_slice(&b_i);
+            tmp.as_mut_slice().zeroize();
         }
 
+        // the message may be secret, so wipe every intermediate hash that is not returned
+        b_0.as_mut_slice().zeroize();
+        b_i.as_mut_slice().zeroize();
+        b_vals[len_in_bytes..].zeroize();
         b_vals.truncate(len_in_bytes);
         b_vals
     }
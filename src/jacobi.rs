@@ -0,0 +1,50 @@
+/*!
+ Variable-time computation of the Jacobi symbol with the binary algorithm
+ (Cohen, "A Course in Computational Algebraic Number Theory", algorithm 1.4.10,
+ with the modular reduction replaced by a subtraction).
+
+ For a prime modulus the Jacobi symbol is the Legendre symbol, so this decides
+ quadratic residuosity without an exponentiation. The running time depends on
+ the input, so this must only be used on public data.
+*/
+
+use ff::{LegendreSymbol, PrimeFieldRepr};
+
+/// Computes the Jacobi symbol (a / n) for an odd n > a.
+pub(crate) fn jacobi_vartime<R: PrimeFieldRepr>(mut a: R, mut n: R) -> LegendreSymbol {
+    debug_assert!(n.is_odd() && a < n);
+
+    // whether the symbol accumulated so far is -1
+    let mut negative = false;
+    while !a.is_zero() {
+        // (2 / n) = -1 exactly when n = 3, 5 mod 8
+        let zeros = a.as_ref()[0].trailing_zeros();
+        if zeros > 0 {
+            a.shr(zeros);
+            let n8 = n.as_ref()[0] & 7;
+            if zeros & 1 == 1 && (n8 == 3 || n8 == 5) {
+                negative = !negative;
+            }
+            continue;
+        }
+
+        // Both are odd now; by quadratic reciprocity, swapping them flips the
+        // sign exactly when both are 3 mod 4.
+        if a < n {
+            ::core::mem::swap(&mut a, &mut n);
+            if a.as_ref()[0] & 3 == 3 && n.as_ref()[0] & 3 == 3 {
+                negative = !negative;
+            }
+        }
+        // (a / n) = ((a - n) / n)
+        a.sub_noborrow(&n);
+    }
+
+    if n != R::from(1) {
+        LegendreSymbol::Zero
+    } else if negative {
+        LegendreSymbol::QuadraticNonResidue
+    } else {
+        LegendreSymbol::QuadraticResidue
+    }
+}
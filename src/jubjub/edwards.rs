@@ -0,0 +1,707 @@
+/*!
+Points on the twisted Edwards curve -u^2 + v^2 = 1 + d * u^2 * v^2.
+
+`AffinePoint` and `ExtendedPoint` represent arbitrary curve points,
+including those of small order. `SubgroupPoint` wraps an extended point
+that is known to lie in the prime order subgroup, and is the type that
+scalar multiplication by `Fs` is defined on.
+*/
+
+use super::{Fs, FsRepr};
+use bls12_381::{transmute, Fr, FrRepr};
+use core::fmt;
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use rand_core::RngCore;
+use GroupDecodingError;
+
+/// d = -(10240/10241)
+const EDWARDS_D: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0x2a522455b974f6b0,
+        0xfc6cc9ef0d9acab3,
+        0x7a08fb94c27628d1,
+        0x57f8f6a8fe0e262e,
+    ]))
+};
+
+/// 2 * d
+const EDWARDS_D2: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0x54a448ac72e9ed5f,
+        0xa51befdb1b373967,
+        0xc0d81f217b4a799e,
+        0x3c0445fed27ecf14,
+    ]))
+};
+
+/// A generator of the prime order subgroup
+const GENERATOR: AffinePoint = AffinePoint {
+    u: unsafe {
+        transmute::fr(FrRepr([
+            0x264ab2ae27790d7a,
+            0x7715419fe4328d1b,
+            0x26e742fccd3474ae,
+            0x0edae7e0e475434b,
+        ]))
+    },
+    v: unsafe {
+        transmute::fr(FrRepr([
+            0x30b42f35b6518e59,
+            0x599e51c9ec7ab10a,
+            0x3798281a9e12a20f,
+            0x30af1cc0df805b82,
+        ]))
+    },
+};
+
+/// A point in affine coordinates (u, v).
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Zeroize)]
+pub struct AffinePoint {
+    pub(crate) u: Fr,
+    pub(crate) v: Fr,
+}
+
+/// A point in extended twisted Edwards coordinates (U : V : Z : T),
+/// representing (U/Z, V/Z) with T = U * V / Z.
+#[derive(Copy, Clone, Debug, Eq, Zeroize)]
+pub struct ExtendedPoint {
+    u: Fr,
+    v: Fr,
+    z: Fr,
+    t: Fr,
+}
+
+/// A point in the prime order subgroup of Jubjub.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Zeroize)]
+pub struct SubgroupPoint(pub(crate) ExtendedPoint);
+
+impl fmt::Display for AffinePoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AffinePoint(u={}, v={})", self.u, self.v)
+    }
+}
+
+impl fmt::Display for ExtendedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.into_affine())
+    }
+}
+
+impl fmt::Display for SubgroupPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq for ExtendedPoint {
+    fn eq(&self, other: &ExtendedPoint) -> bool {
+        // (U1/Z1, V1/Z1) == (U2/Z2, V2/Z2) iff
+        // U1 * Z2 == U2 * Z1 and V1 * Z2 == V2 * Z1
+        let mut u1 = self.u;
+        u1.mul_assign(&other.z);
+        let mut u2 = other.u;
+        u2.mul_assign(&self.z);
+        let mut v1 = self.v;
+        v1.mul_assign(&other.z);
+        let mut v2 = other.v;
+        v2.mul_assign(&self.z);
+
+        u1 == u2 && v1 == v2
+    }
+}
+
+impl AffinePoint {
+    /// The identity (0, 1).
+    pub fn zero() -> Self {
+        AffinePoint {
+            u: Fr::zero(),
+            v: Fr::one(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.u.is_zero() && self.v == Fr::one()
+    }
+
+    /// Returns the coordinates (u, v) of this point.
+    pub fn as_tuple(&self) -> (Fr, Fr) {
+        (self.u, self.v)
+    }
+
+    /// Constructs a point from its coordinates, checking that it lies on
+    /// the curve. The point is not necessarily in the prime order subgroup.
+    pub fn from_coordinates(u: Fr, v: Fr) -> Result<Self, GroupDecodingError> {
+        let p = AffinePoint { u, v };
+        if p.is_on_curve() {
+            Ok(p)
+        } else {
+            Err(GroupDecodingError::NotOnCurve)
+        }
+    }
+
+    pub fn negate(&mut self) {
+        self.u.negate();
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        let mut u2 = self.u;
+        u2.square();
+        let mut v2 = self.v;
+        v2.square();
+
+        // v^2 - u^2
+        let mut lhs = v2;
+        lhs.sub_assign(&u2);
+
+        // 1 + d * u^2 * v^2
+        let mut rhs = u2;
+        rhs.mul_assign(&v2);
+        rhs.mul_assign(&EDWARDS_D);
+        rhs.add_assign(&Fr::one());
+
+        lhs == rhs
+    }
+
+    /// Attempts to construct a point given its v-coordinate. The u-coordinate
+    /// is chosen to be odd if `sign` is set and even otherwise. Returns None
+    /// if there is no such point.
+    pub fn get_point_from_v(v: Fr, sign: bool) -> Option<Self> {
+        // u^2 = (v^2 - 1) / (d * v^2 + 1)
+        let mut v2 = v;
+        v2.square();
+        let mut num = v2;
+        num.sub_assign(&Fr::one());
+        let mut den = v2;
+        den.mul_assign(&EDWARDS_D);
+        den.add_assign(&Fr::one());
+
+        // d is not a square, so the denominator never vanishes
+        den.inverse().and_then(|den_inv| {
+            num.mul_assign(&den_inv);
+            num.sqrt().and_then(|mut u| {
+                if is_odd(&u) != sign {
+                    if u.is_zero() {
+                        return None;
+                    }
+                    u.negate();
+                }
+                Some(AffinePoint { u, v })
+            })
+        })
+    }
+
+    /// Encodes the point as 32 bytes: the little-endian encoding of v,
+    /// with the most significant bit set to the parity of u.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut res = self.v.to_bytes();
+        res.reverse();
+        if is_odd(&self.u) {
+            res[31] |= 0x80;
+        }
+        res
+    }
+
+    /// Decodes a point produced by `to_bytes`, returning None unless it lies
+    /// on the curve. The point is not checked to lie in the prime order
+    /// subgroup.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let mut buf = *bytes;
+        let sign = buf[31] & 0x80 != 0;
+        buf[31] &= 0x7f;
+        buf.reverse();
+
+        // u = 0 has no odd representative, so the sign bit must then be clear
+        AffinePoint::get_point_from_v(Fr::from_bytes(&buf)?, sign)
+    }
+
+    pub fn into_extended(&self) -> ExtendedPoint {
+        let mut t = self.u;
+        t.mul_assign(&self.v);
+        ExtendedPoint {
+            u: self.u,
+            v: self.v,
+            z: Fr::one(),
+            t,
+        }
+    }
+}
+
+impl ExtendedPoint {
+    /// The identity (0 : 1 : 1 : 0).
+    pub fn zero() -> Self {
+        ExtendedPoint {
+            u: Fr::zero(),
+            v: Fr::one(),
+            z: Fr::one(),
+            t: Fr::zero(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.u.is_zero() && self.v == self.z
+    }
+
+    pub fn into_affine(&self) -> AffinePoint {
+        // Z never vanishes since the addition law is complete
+        let zinv = self.z.inverse().unwrap();
+        let mut u = self.u;
+        u.mul_assign(&zinv);
+        let mut v = self.v;
+        v.mul_assign(&zinv);
+        AffinePoint { u, v }
+    }
+
+    pub fn negate(&mut self) {
+        self.u.negate();
+        self.t.negate();
+    }
+
+    pub fn double(&mut self) {
+        // http://hyperelliptic.org/EFD/g1p/auto-twisted-extended.html#doubling-dbl-2008-hwcd
+        // with a = -1
+
+        // A = U1^2
+        let mut a = self.u;
+        a.square();
+        // B = V1^2
+        let mut b = self.v;
+        b.square();
+        // C = 2 * Z1^2
+        let mut c = self.z;
+        c.square();
+        c.double();
+        // D = a * A = -A
+        let mut d = a;
+        d.negate();
+        // E = (U1 + V1)^2 - A - B
+        let mut e = self.u;
+        e.add_assign(&self.v);
+        e.square();
+        e.sub_assign(&a);
+        e.sub_assign(&b);
+        // G = D + B
+        let mut g = d;
+        g.add_assign(&b);
+        // F = G - C
+        let mut f = g;
+        f.sub_assign(&c);
+        // H = D - B
+        let mut h = d;
+        h.sub_assign(&b);
+
+        self.set_from_efgh(&e, &f, &g, &h);
+    }
+
+    pub fn add_assign(&mut self, other: &ExtendedPoint) {
+        // http://hyperelliptic.org/EFD/g1p/auto-twisted-extended.html#addition-add-2008-hwcd-3
+        // with a = -1 and k = 2d; this addition law is complete
+
+        // A = (V1 - U1) * (V2 - U2)
+        let mut a = self.v;
+        a.sub_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.sub_assign(&other.u);
+        a.mul_assign(&tmp);
+        // B = (V1 + U1) * (V2 + U2)
+        let mut b = self.v;
+        b.add_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.add_assign(&other.u);
+        b.mul_assign(&tmp);
+        // C = T1 * k * T2
+        let mut c = self.t;
+        c.mul_assign(&EDWARDS_D2);
+        c.mul_assign(&other.t);
+        // D = 2 * Z1 * Z2
+        let mut d = self.z;
+        d.mul_assign(&other.z);
+        d.double();
+
+        self.add_from_abcd(&a, &b, &c, &d);
+    }
+
+    pub fn add_assign_mixed(&mut self, other: &AffinePoint) {
+        // As above, with Z2 = 1 and T2 = U2 * V2
+
+        // A = (V1 - U1) * (V2 - U2)
+        let mut a = self.v;
+        a.sub_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.sub_assign(&other.u);
+        a.mul_assign(&tmp);
+        // B = (V1 + U1) * (V2 + U2)
+        let mut b = self.v;
+        b.add_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.add_assign(&other.u);
+        b.mul_assign(&tmp);
+        // C = T1 * k * U2 * V2
+        let mut c = self.t;
+        c.mul_assign(&EDWARDS_D2);
+        c.mul_assign(&other.u);
+        c.mul_assign(&other.v);
+        // D = 2 * Z1
+        let mut d = self.z;
+        d.double();
+
+        self.add_from_abcd(&a, &b, &c, &d);
+    }
+
+    pub fn sub_assign(&mut self, other: &ExtendedPoint) {
+        let mut tmp = *other;
+        tmp.negate();
+        self.add_assign(&tmp);
+    }
+
+    /// Multiplies the point by the cofactor 8.
+    pub fn mul_by_cofactor(&self) -> ExtendedPoint {
+        let mut res = *self;
+        res.double();
+        res.double();
+        res.double();
+        res
+    }
+
+    /// Returns true if the point lies in the torsion subgroup of order 8.
+    pub fn is_small_order(&self) -> bool {
+        self.mul_by_cofactor().is_zero()
+    }
+
+    /// Returns true if the point lies in the prime order subgroup.
+    pub fn is_torsion_free(&self) -> bool {
+        self.mul_bits(BitIterator::new(Fs::char())).is_zero()
+    }
+
+    /// Returns the point as a `SubgroupPoint`, if it lies in the prime
+    /// order subgroup.
+    pub fn into_subgroup(&self) -> Option<SubgroupPoint> {
+        if self.is_torsion_free() {
+            Some(SubgroupPoint(*self))
+        } else {
+            None
+        }
+    }
+
+    fn mul_bits<S: AsRef<[u64]>>(&self, bits: BitIterator<S>) -> ExtendedPoint {
+        let mut res = ExtendedPoint::zero();
+        for i in bits {
+            res.double();
+            if i {
+                res.add_assign(self)
+            }
+        }
+        res
+    }
+
+    // E = B - A, F = D - C, G = D + C, H = B + A
+    fn add_from_abcd(&mut self, a: &Fr, b: &Fr, c: &Fr, d: &Fr) {
+        let mut e = *b;
+        e.sub_assign(a);
+        let mut f = *d;
+        f.sub_assign(c);
+        let mut g = *d;
+        g.add_assign(c);
+        let mut h = *b;
+        h.add_assign(a);
+
+        self.set_from_efgh(&e, &f, &g, &h);
+    }
+
+    // U3 = E * F, V3 = G * H, T3 = E * H, Z3 = F * G
+    fn set_from_efgh(&mut self, e: &Fr, f: &Fr, g: &Fr, h: &Fr) {
+        self.u = *e;
+        self.u.mul_assign(f);
+        self.v = *g;
+        self.v.mul_assign(h);
+        self.t = *e;
+        self.t.mul_assign(h);
+        self.z = *f;
+        self.z.mul_assign(g);
+    }
+}
+
+impl SubgroupPoint {
+    pub fn zero() -> Self {
+        SubgroupPoint(ExtendedPoint::zero())
+    }
+
+    /// The fixed generator of the prime order subgroup.
+    pub fn one() -> Self {
+        SubgroupPoint(GENERATOR.into_extended())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        loop {
+            let v = Fr::random(rng);
+            let sign = rng.next_u32() & 1 == 1;
+
+            if let Some(p) = AffinePoint::get_point_from_v(v, sign) {
+                let p = p.into_extended().mul_by_cofactor();
+                if !p.is_zero() {
+                    return SubgroupPoint(p);
+                }
+            }
+        }
+    }
+
+    pub fn double(&mut self) {
+        self.0.double();
+    }
+
+    pub fn negate(&mut self) {
+        self.0.negate();
+    }
+
+    pub fn add_assign(&mut self, other: &SubgroupPoint) {
+        self.0.add_assign(&other.0);
+    }
+
+    pub fn sub_assign(&mut self, other: &SubgroupPoint) {
+        self.0.sub_assign(&other.0);
+    }
+
+    pub fn mul_assign<S: Into<FsRepr>>(&mut self, other: S) {
+        self.0 = self.0.mul_bits(BitIterator::new(other.into()));
+    }
+
+    pub fn into_affine(&self) -> AffinePoint {
+        self.0.into_affine()
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.into_affine().to_bytes()
+    }
+
+    /// Decodes a point produced by `to_bytes`, returning None unless it lies
+    /// in the prime order subgroup.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        AffinePoint::from_bytes(bytes)?
+            .into_extended()
+            .into_subgroup()
+    }
+}
+
+impl From<AffinePoint> for ExtendedPoint {
+    fn from(p: AffinePoint) -> ExtendedPoint {
+        p.into_extended()
+    }
+}
+
+impl From<SubgroupPoint> for ExtendedPoint {
+    fn from(p: SubgroupPoint) -> ExtendedPoint {
+        p.0
+    }
+}
+
+fn is_odd(f: &Fr) -> bool {
+    f.into_repr().is_odd()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+
+    /// A point of order 8 * s, with v = 11
+    const FULL_GENERATOR: AffinePoint = AffinePoint {
+        u: unsafe {
+            transmute::fr(FrRepr([
+                0x50c87a58c166eca5,
+                0x8046fd74c0051afc,
+                0x406355ee695b0493,
+                0x0d5a8d931bdc7e0a,
+            ]))
+        },
+        v: unsafe {
+            transmute::fr(FrRepr([
+                0x00000017ffffffe8,
+                0x26389fb800276018,
+                0x3293bf3f18d3bf80,
+                0x21b85034193c413b,
+            ]))
+        },
+    };
+
+    fn test_rng() -> rand_xorshift::XorShiftRng {
+        rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_generators() {
+        assert!(FULL_GENERATOR.is_on_curve());
+        assert!(GENERATOR.is_on_curve());
+        assert_eq!(FULL_GENERATOR.v, Fr::from_repr(FrRepr::from(11)).unwrap());
+
+        let full = FULL_GENERATOR.into_extended();
+        assert_eq!(full.mul_by_cofactor(), GENERATOR.into_extended());
+        assert!(!full.is_torsion_free());
+        assert!(!full.is_small_order());
+
+        let g = SubgroupPoint::one();
+        assert!(!g.is_zero());
+        assert!(g.0.is_torsion_free());
+        let mut tmp = g;
+        tmp.mul_assign(Fs::char());
+        assert!(tmp.is_zero());
+    }
+
+    #[test]
+    fn test_edwards_d() {
+        // d * 10241 == -10240
+        let mut lhs = EDWARDS_D;
+        lhs.mul_assign(&Fr::from_repr(FrRepr::from(10241)).unwrap());
+        let mut rhs = Fr::from_repr(FrRepr::from(10240)).unwrap();
+        rhs.negate();
+        assert_eq!(lhs, rhs);
+
+        let mut d2 = EDWARDS_D;
+        d2.double();
+        assert_eq!(d2, EDWARDS_D2);
+    }
+
+    #[test]
+    fn test_small_order() {
+        // (0, -1) has order 2
+        let mut p = AffinePoint::zero();
+        p.v.negate();
+        assert!(p.is_on_curve());
+        let p = p.into_extended();
+        assert!(p.is_small_order());
+        assert!(!p.is_zero());
+        let mut tmp = p;
+        tmp.double();
+        assert!(tmp.is_zero());
+        assert!(!p.is_torsion_free());
+        assert!(p.into_subgroup().is_none());
+    }
+
+    #[test]
+    fn test_addition_consistency() {
+        let mut rng = test_rng();
+        for _ in 0..100 {
+            let a = SubgroupPoint::random(&mut rng);
+            let b = SubgroupPoint::random(&mut rng);
+
+            // a + a == 2a
+            let mut a_plus_a = a;
+            a_plus_a.add_assign(&a);
+            let mut a_doubled = a;
+            a_doubled.double();
+            assert_eq!(a_plus_a, a_doubled);
+
+            // (a + b) - b == a
+            let mut tmp = a;
+            tmp.add_assign(&b);
+            tmp.sub_assign(&b);
+            assert_eq!(tmp, a);
+
+            // mixed and extended addition agree
+            let mut mixed = a.0;
+            mixed.add_assign_mixed(&b.into_affine());
+            let mut ext = a;
+            ext.add_assign(&b);
+            assert_eq!(mixed, ext.0);
+            assert!(mixed.into_affine().is_on_curve());
+
+            // a - a == 0
+            let mut tmp = a;
+            tmp.sub_assign(&a);
+            assert!(tmp.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_mul_linearity() {
+        let mut rng = test_rng();
+        for _ in 0..20 {
+            let p = SubgroupPoint::random(&mut rng);
+            let a = Fs::random(&mut rng);
+            let b = Fs::random(&mut rng);
+
+            // a * P + b * P == (a + b) * P
+            let mut lhs = p;
+            lhs.mul_assign(a);
+            let mut tmp = p;
+            tmp.mul_assign(b);
+            lhs.add_assign(&tmp);
+
+            let mut sum = a;
+            sum.add_assign(&b);
+            let mut rhs = p;
+            rhs.mul_assign(sum);
+            assert_eq!(lhs, rhs);
+
+            // a * (b * P) == (a * b) * P
+            let mut lhs = p;
+            lhs.mul_assign(b);
+            lhs.mul_assign(a);
+            let mut prod = a;
+            prod.mul_assign(&b);
+            let mut rhs = p;
+            rhs.mul_assign(prod);
+            assert_eq!(lhs, rhs);
+        }
+    }
+
+    #[test]
+    fn test_compression() {
+        let mut rng = test_rng();
+        let mut points = vec![SubgroupPoint::zero(), SubgroupPoint::one()];
+        for _ in 0..100 {
+            points.push(SubgroupPoint::random(&mut rng));
+        }
+        for p in points {
+            let bytes = p.to_bytes();
+            assert_eq!(SubgroupPoint::from_bytes(&bytes).unwrap(), p);
+
+            // the negation differs only in the sign bit, unless u = 0
+            let mut neg = p;
+            neg.negate();
+            let neg_bytes = neg.to_bytes();
+            if p.is_zero() {
+                assert_eq!(bytes, neg_bytes);
+            } else {
+                assert_eq!(bytes[31] ^ neg_bytes[31], 0x80);
+                assert_eq!(bytes[..31], neg_bytes[..31]);
+            }
+        }
+
+        // points outside the subgroup decode as curve points only
+        let full = FULL_GENERATOR.to_bytes();
+        assert_eq!(AffinePoint::from_bytes(&full).unwrap(), FULL_GENERATOR);
+        assert!(SubgroupPoint::from_bytes(&full).is_none());
+    }
+
+    #[test]
+    fn test_decompression_errors() {
+        // the identity with the sign bit set
+        let mut bytes = AffinePoint::zero().to_bytes();
+        bytes[31] |= 0x80;
+        assert!(AffinePoint::from_bytes(&bytes).is_none());
+
+        // v = r is not a canonical field element
+        let mut bytes = [0u8; 32];
+        ::encoding_macros::repr_to_be(&Fr::char(), &mut bytes);
+        bytes.reverse();
+        assert!(AffinePoint::from_bytes(&bytes).is_none());
+
+        // find a v that does not correspond to any point
+        let mut v = Fr::one();
+        loop {
+            v.add_assign(&Fr::one());
+            if AffinePoint::get_point_from_v(v, false).is_none() {
+                break;
+            }
+        }
+        let mut bytes = v.to_bytes();
+        bytes.reverse();
+        assert!(AffinePoint::from_bytes(&bytes).is_none());
+    }
+}
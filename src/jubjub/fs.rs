@@ -0,0 +1,78 @@
+use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+
+/// The scalar field of the prime order subgroup of Jubjub.
+#[derive(PrimeField, Zeroize)]
+#[PrimeFieldModulus = "6554484396890773809930967563523245729705921265872317281365359162392183254199"]
+#[PrimeFieldGenerator = "6"]
+pub struct Fs(FsRepr);
+
+prime_field_bytes_impl!(Fs, FsRepr, 32);
+
+/// set the default value for Fs to 0
+impl ::core::default::Default for Fs {
+    fn default() -> Self {
+        Fs::zero()
+    }
+}
+
+#[test]
+fn test_fs_num_bits() {
+    assert_eq!(Fs::NUM_BITS, 252);
+    assert_eq!(Fs::CAPACITY, 251);
+}
+
+#[test]
+fn test_fs_root_of_unity() {
+    use ff::SqrtField;
+
+    assert_eq!(Fs::S, 1);
+    assert_eq!(
+        Fs::multiplicative_generator(),
+        Fs::from_repr(FsRepr::from(6)).unwrap()
+    );
+    assert_eq!(
+        Fs::multiplicative_generator().pow([
+            0x684b872f6b7b965b,
+            0x53341049e6640841,
+            0x83339d80809a1d80,
+            0x073eda753299d7d4,
+        ]),
+        Fs::root_of_unity()
+    );
+    assert_eq!(Fs::root_of_unity().pow([1 << Fs::S]), Fs::one());
+    assert!(Fs::multiplicative_generator().sqrt().is_none());
+}
+
+#[test]
+fn fs_field_tests() {
+    ::tests::field::random_field_tests::<Fs>();
+    ::tests::field::random_sqrt_tests::<Fs>();
+    ::tests::field::random_frobenius_tests::<Fs, _>(Fs::char(), 13);
+    ::tests::field::from_str_tests::<Fs>();
+}
+
+#[test]
+fn fs_repr_tests() {
+    ::tests::repr::random_repr_tests::<Fs, FsRepr>();
+}
+
+#[test]
+fn test_fs_fixed_size_bytes() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..100 {
+        let a = Fs::random(&mut rng);
+        assert_eq!(Fs::from_bytes(&a.to_bytes()), Some(a));
+    }
+
+    // the encoding is big-endian, and the modulus is rejected
+    let mut bytes = [0u8; 32];
+    bytes[31] = 1;
+    assert_eq!(Fs::from_bytes(&bytes), Some(Fs::one()));
+    ::encoding_macros::repr_to_be(&Fs::char(), &mut bytes);
+    assert_eq!(Fs::from_bytes(&bytes), None);
+}
@@ -0,0 +1,201 @@
+/*!
+Hashing to the prime order subgroup of Jubjub.
+
+This follows the Elligator 2 method of section 6.7.1 of
+https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-07: field elements
+are mapped to the birationally equivalent Montgomery curve
+K * t^2 = s^3 + J * s^2 + s with J = 40962 and K = -40964, then sent to the
+twisted Edwards form via the rational map of appendix D.1, and finally
+multiplied by the cofactor 8.
+*/
+
+use super::{ExtendedPoint, SubgroupPoint};
+use bls12_381::{transmute, Fr, FrRepr};
+use ff::{Field, SqrtField};
+use hash_to_curve::HashToCurve;
+use hash_to_field::{hash_to_field, ExpandMsg};
+use signum::{Sgn0Result, Signum0};
+
+/// K = -40964
+const ELL2_K: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0xfffe9ea40001615c,
+        0x974f1411bc43aea3,
+        0x2bacb82ba108fa62,
+        0x5d21ce451e599495,
+    ]))
+};
+
+/// J / K
+const ELL2_J_OVER_K: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0x95291229dcba7b59,
+        0x51f408fa86cbc158,
+        0x703e55d26adcec6e,
+        0x1fea22a7a8a4905f,
+    ]))
+};
+
+/// 1 / K^2
+const ELL2_INV_K_SQUARED: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0x777e475efa15a33e,
+        0x8c3871d074fc8a2c,
+        0xfa3ee3c5446bf103,
+        0x0b159b3894a73664,
+    ]))
+};
+
+/// Z = 5, the smallest non-square of the form +-n
+const ELL2_Z: Fr = unsafe {
+    transmute::fr(FrRepr([
+        0x0000000afffffff5,
+        0x66d9f3df00120c0b,
+        0xcc83b7a7960bb7c5,
+        0x04c9cf6d363b9de5,
+    ]))
+};
+
+/// g(x) = x^3 + (J / K) * x^2 + x / K^2
+fn ell2_g(x: &Fr) -> Fr {
+    let mut res = *x;
+    res.add_assign(&ELL2_J_OVER_K);
+    res.mul_assign(x);
+    res.add_assign(&ELL2_INV_K_SQUARED);
+    res.mul_assign(x);
+    res
+}
+
+/// Maps a field element to a point on Jubjub, which is not necessarily in
+/// the prime order subgroup.
+pub(crate) fn map_to_curve(u: &Fr) -> ExtendedPoint {
+    // x1 = -(J / K) / (1 + Z * u^2), or -(J / K) if the denominator is zero
+    let mut tv1 = *u;
+    tv1.square();
+    tv1.mul_assign(&ELL2_Z);
+    tv1.add_assign(&Fr::one());
+    let mut x1 = ELL2_J_OVER_K;
+    x1.negate();
+    if let Some(inv) = tv1.inverse() {
+        x1.mul_assign(&inv);
+    }
+
+    // x2 = -x1 - J / K
+    let mut x2 = x1;
+    x2.add_assign(&ELL2_J_OVER_K);
+    x2.negate();
+
+    // exactly one of g(x1) and g(x2) is square, since Z is not
+    let (x, mut y, sgn) = match ell2_g(&x1).sqrt() {
+        Some(y) => (x1, y, Sgn0Result::Negative),
+        None => (x2, ell2_g(&x2).sqrt().unwrap(), Sgn0Result::NonNegative),
+    };
+    let flip = y.sgn0() ^ sgn;
+    y.negate_if(flip);
+
+    // (s, t) on the Montgomery curve
+    let mut s = x;
+    s.mul_assign(&ELL2_K);
+    let mut t = y;
+    t.mul_assign(&ELL2_K);
+
+    // u = s / t and v = (s - 1) / (s + 1), or the identity if either
+    // denominator vanishes
+    let mut s_plus_1 = s;
+    s_plus_1.add_assign(&Fr::one());
+    let mut den = t;
+    den.mul_assign(&s_plus_1);
+    match den.inverse() {
+        None => ExtendedPoint::zero(),
+        Some(den_inv) => {
+            let mut u = s;
+            u.mul_assign(&s_plus_1);
+            u.mul_assign(&den_inv);
+            let mut v = s;
+            v.sub_assign(&Fr::one());
+            v.mul_assign(&t);
+            v.mul_assign(&den_inv);
+            super::AffinePoint { u, v }.into_extended()
+        }
+    }
+}
+
+impl<X: ExpandMsg> HashToCurve<X> for SubgroupPoint {
+    fn hash_to_curve<Mt: AsRef<[u8]>, Dt: AsRef<[u8]>>(msg: Mt, dst: Dt) -> SubgroupPoint {
+        let u = hash_to_field::<Fr, X>(msg.as_ref(), dst.as_ref(), 2);
+        let mut p = map_to_curve(&u[0]);
+        p.add_assign(&map_to_curve(&u[1]));
+        SubgroupPoint(p.mul_by_cofactor())
+    }
+
+    fn encode_to_curve<Mt: AsRef<[u8]>, Dt: AsRef<[u8]>>(msg: Mt, dst: Dt) -> SubgroupPoint {
+        let u = hash_to_field::<Fr, X>(msg.as_ref(), dst.as_ref(), 1);
+        SubgroupPoint(map_to_curve(&u[0]).mul_by_cofactor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::PrimeField;
+    use hash_to_field::ExpandMsgXmd;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn test_ell2_constants() {
+        let k = Fr::from_repr(FrRepr::from(40964)).unwrap();
+        let mut neg_k = k;
+        neg_k.negate();
+        assert_eq!(ELL2_K, neg_k);
+
+        let mut j = ELL2_J_OVER_K;
+        j.mul_assign(&ELL2_K);
+        assert_eq!(j, Fr::from_repr(FrRepr::from(40962)).unwrap());
+
+        let mut one = ELL2_INV_K_SQUARED;
+        one.mul_assign(&k);
+        one.mul_assign(&k);
+        assert_eq!(one, Fr::one());
+
+        assert_eq!(ELL2_Z, Fr::from_repr(FrRepr::from(5)).unwrap());
+        assert!(ELL2_Z.sqrt().is_none());
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let mut inputs = vec![Fr::zero(), Fr::one()];
+        for _ in 0..100 {
+            inputs.push(Fr::random(&mut rng));
+        }
+        for u in inputs {
+            let p = map_to_curve(&u);
+            assert!(p.into_affine().is_on_curve());
+            // u and -u map to the same point
+            let mut neg_u = u;
+            neg_u.negate();
+            assert_eq!(map_to_curve(&neg_u), p);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"JUBJUB_XMD:SHA-256_ELL2_RO_TESTGEN";
+        for msg in &["", "abc", "abcdef0123456789"] {
+            let p =
+                <SubgroupPoint as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(msg, dst);
+            assert!(!p.is_zero());
+            assert!(p.0.is_torsion_free());
+
+            let q = <SubgroupPoint as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::encode_to_curve(
+                msg, dst,
+            );
+            assert!(!q.is_zero());
+            assert!(q.0.is_torsion_free());
+            assert_ne!(p, q);
+        }
+    }
+}
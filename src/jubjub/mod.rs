@@ -0,0 +1,20 @@
+/*!
+ This module implements Jubjub, the twisted Edwards curve
+ `-u^2 + v^2 = 1 + d * u^2 * v^2` with d = -(10240/10241), defined over the
+ scalar field of BLS12-381. This makes it suitable for use inside circuits
+ that are proven over BLS12-381.
+
+ The curve has order 8 * s for the prime s, and `Fs` is the scalar field of
+ its prime order subgroup. Points are encoded in 32 bytes as the
+ little-endian v-coordinate, with the most significant bit holding the
+ parity of u.
+*/
+
+mod edwards;
+mod fs;
+mod hash_to_curve;
+mod pedersen;
+
+pub use self::edwards::{AffinePoint, ExtendedPoint, SubgroupPoint};
+pub use self::fs::{Fs, FsRepr};
+pub use self::pedersen::pedersen_hash;
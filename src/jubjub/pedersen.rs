@@ -0,0 +1,159 @@
+/*!
+Pedersen hash over Jubjub, in the style of the Sapling protocol.
+
+The input bits are split into 3-bit chunks, padding the last chunk with
+zeros. Each chunk (b0, b1, b2) is encoded as the signed digit
+(1 - 2 * b2) * (1 + b0 + 2 * b1), and groups of 63 consecutive chunks form
+a segment whose scalar is the sum of its digits weighted by powers of 16.
+The hash is the sum over all segments of the segment scalar times an
+independent generator, obtained by hashing the segment index to the curve.
+
+Since the final chunk is padded with zeros, inputs that differ only by
+trailing zero bits within that chunk hash to the same point. Callers that
+hash variable length inputs should fix the length or encode it.
+*/
+
+use super::{Fs, FsRepr, SubgroupPoint};
+use ff::{Field, PrimeField};
+use hash_to_curve::HashToCurve;
+use hash_to_field::ExpandMsg;
+
+/// The number of 3-bit chunks in each segment
+const CHUNKS_PER_SEGMENT: usize = 63;
+
+/// Returns the generator for the given segment, hashed to the curve under `dst`.
+fn segment_generator<X: ExpandMsg>(dst: &[u8], segment: u32) -> SubgroupPoint {
+    <SubgroupPoint as HashToCurve<X>>::hash_to_curve(segment.to_le_bytes(), dst)
+}
+
+/// Computes the Pedersen hash of a sequence of bits, using generators
+/// derived from `dst`.
+pub fn pedersen_hash<X, D, I>(dst: D, bits: I) -> SubgroupPoint
+where
+    X: ExpandMsg,
+    D: AsRef<[u8]>,
+    I: IntoIterator<Item = bool>,
+{
+    let sixteen = Fs::from_repr(FsRepr::from(16)).unwrap();
+    let mut bits = bits.into_iter().peekable();
+    let mut result = SubgroupPoint::zero();
+    let mut segment = 0u32;
+
+    while bits.peek().is_some() {
+        let mut acc = Fs::zero();
+        let mut weight = Fs::one();
+        let mut chunks = 0;
+
+        while chunks < CHUNKS_PER_SEGMENT {
+            let b0 = match bits.next() {
+                Some(b) => b,
+                None => break,
+            };
+            let b1 = bits.next().unwrap_or(false);
+            let b2 = bits.next().unwrap_or(false);
+
+            let mut digit = weight;
+            if b0 {
+                digit.add_assign(&weight);
+            }
+            if b1 {
+                digit.add_assign(&weight);
+                digit.add_assign(&weight);
+            }
+            if b2 {
+                digit.negate();
+            }
+            acc.add_assign(&digit);
+
+            weight.mul_assign(&sixteen);
+            chunks += 1;
+        }
+
+        let mut tmp = segment_generator::<X>(dst.as_ref(), segment);
+        tmp.mul_assign(acc);
+        result.add_assign(&tmp);
+        segment += 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hash_to_field::ExpandMsgXmd;
+    use rand_core::{RngCore, SeedableRng};
+    use std::vec::Vec;
+
+    type X = ExpandMsgXmd<sha2::Sha256>;
+    const DST: &[u8] = b"JUBJUB_PEDERSEN_TESTGEN";
+
+    fn fs(n: u64) -> Fs {
+        Fs::from_repr(FsRepr::from(n)).unwrap()
+    }
+
+    #[test]
+    fn test_pedersen_hash_small() {
+        assert!(pedersen_hash::<X, _, _>(DST, vec![]).is_zero());
+
+        // a single chunk (b0, b1, b2) = (1, 1, 0) encodes to 4
+        let mut expected = segment_generator::<X>(DST, 0);
+        expected.mul_assign(fs(4));
+        assert_eq!(
+            pedersen_hash::<X, _, _>(DST, vec![true, true, false]),
+            expected
+        );
+        // the final chunk is padded with zeros
+        assert_eq!(pedersen_hash::<X, _, _>(DST, vec![true, true]), expected);
+
+        // (1, 0, 1), (0, 0, 0) encodes to -2 + 16
+        let mut expected = segment_generator::<X>(DST, 0);
+        expected.mul_assign(fs(14));
+        assert_eq!(
+            pedersen_hash::<X, _, _>(DST, vec![true, false, true, false, false, false]),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_pedersen_hash_segments() {
+        // 63 chunks of (0, 0, 0) fill the first segment, then (0, 1, 0)
+        // encodes to 3 in the second
+        let mut bits = vec![false; 3 * CHUNKS_PER_SEGMENT];
+        bits.extend(&[false, true, false]);
+
+        let mut expected = segment_generator::<X>(DST, 0);
+        let mut sum = Fs::zero();
+        let mut weight = Fs::one();
+        for _ in 0..CHUNKS_PER_SEGMENT {
+            sum.add_assign(&weight);
+            weight.mul_assign(&fs(16));
+        }
+        expected.mul_assign(sum);
+        let mut tmp = segment_generator::<X>(DST, 1);
+        tmp.mul_assign(fs(3));
+        expected.add_assign(&tmp);
+
+        assert_eq!(pedersen_hash::<X, _, _>(DST, bits), expected);
+    }
+
+    #[test]
+    fn test_pedersen_hash_distinct() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..10 {
+            let bits: Vec<bool> = (0..300).map(|_| rng.next_u32() & 1 == 1).collect();
+            let h = pedersen_hash::<X, _, _>(DST, bits.iter().cloned());
+            assert!(h.0.is_torsion_free());
+
+            let mut flipped = bits.clone();
+            flipped[rng.next_u32() as usize % 300] ^= true;
+            assert_ne!(h, pedersen_hash::<X, _, _>(DST, flipped));
+
+            // the generators depend on the domain separation tag
+            assert_ne!(h, pedersen_hash::<X, _, _>(b"OTHER_DST", bits));
+        }
+    }
+}
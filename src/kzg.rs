@@ -0,0 +1,334 @@
+/*!
+ This module implements the KZG polynomial commitment scheme
+ (Kate, Zaverucha, Goldberg, "Constant-Size Commitments to Polynomials
+ and Their Applications", ASIACRYPT 2010) over any pairing `Engine`.
+
+ Polynomials are represented with `polynomial::Polynomial`. A commitment
+ to `p` is `[p(tau)]_1`; a proof that `p(z) = y` is `[q(tau)]_1` for
+ `q(x) = (p(x) - y) / (x - z)`. Batch openings at `k` points use the
+ quotient by the vanishing polynomial of the points and require `k + 1`
+ powers of tau in G2.
+*/
+
+use alloc::vec::Vec;
+use core::fmt;
+use ff::Field;
+use polynomial::Polynomial;
+use rand_core::RngCore;
+use secret::{wipe, SecretScalar};
+#[cfg(feature = "std")]
+use serdes::{SerDes, SerDesError};
+use shamir::scalar_to_limbs;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+use {CurveAffine, CurveProjective, Engine};
+
+/// An error that may occur when committing to or opening a polynomial.
+#[derive(Debug, PartialEq, Eq)]
+pub enum KzgError {
+    /// The polynomial has more coefficients than there are powers of tau in G1.
+    PolynomialTooLarge,
+    /// There are more opening points than there are powers of tau in G2 (minus one).
+    TooManyPoints,
+    /// The same opening point was given more than once.
+    DuplicatePoint,
+}
+
+#[cfg(feature = "std")]
+impl Error for KzgError {
+    fn description(&self) -> &str {
+        match *self {
+            KzgError::PolynomialTooLarge => "polynomial degree exceeds the reference string",
+            KzgError::TooManyPoints => "number of opening points exceeds the reference string",
+            KzgError::DuplicatePoint => "opening point is repeated",
+        }
+    }
+}
+
+impl fmt::Display for KzgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Structured reference string: `g1_powers[i] = tau^i * G1` and `g2_powers[i] = tau^i * G2`.
+#[derive(Clone, Debug)]
+pub struct Srs<E: Engine> {
+    pub g1_powers: Vec<E::G1Affine>,
+    pub g2_powers: Vec<E::G2Affine>,
+}
+
+/// Computes `sum_i scalars[i] * bases[i]` with Pippinger's algorithm.
+fn msm<G: CurveAffine>(bases: &[G], scalars: &[G::Scalar]) -> G::Projective {
+    let limbs: Vec<[u64; 4]> = scalars.iter().map(scalar_to_limbs).collect();
+    let limb_refs: Vec<&[u64; 4]> = limbs.iter().collect();
+    G::sum_of_products_pippinger(bases, &limb_refs, G::find_pippinger_window(limbs.len()))
+}
+
+impl<E: Engine> Srs<E> {
+    /// Generates a reference string for polynomials with at most `max_coeffs` coefficients
+    /// and batch openings at up to `max_points` points, from a random trapdoor `tau`.
+    ///
+    /// This is a trusted setup: whoever knows `tau` can forge proofs. It is intended for
+    /// testing; in production the reference string should come from a ceremony.
+    pub fn setup<R: RngCore>(max_coeffs: usize, max_points: usize, rng: &mut R) -> Self {
+        let tau = SecretScalar::<E::Fr>::random(rng);
+        Self::from_tau(&tau, max_coeffs, max_points)
+    }
+
+    /// Generates a reference string from the trapdoor `tau`.
+    pub fn from_tau(tau: &E::Fr, max_coeffs: usize, max_points: usize) -> Self {
+        fn powers<G: CurveProjective>(tau: &G::Scalar, n: usize) -> Vec<G::Affine> {
+            let mut res = Vec::with_capacity(n);
+            let mut cur = G::Scalar::one();
+            for _ in 0..n {
+                let mut tmp = G::one();
+                tmp.mul_assign(cur);
+                res.push(tmp);
+                cur.mul_assign(tau);
+            }
+            // the powers of tau are as secret as tau itself
+            wipe(&mut cur);
+            G::batch_normalization(&mut res);
+            res.iter().map(|p| p.into_affine()).collect()
+        }
+
+        Srs {
+            g1_powers: powers::<E::G1>(tau, max_coeffs),
+            g2_powers: powers::<E::G2>(tau, max_points + 1),
+        }
+    }
+
+    /// Commits to the polynomial `poly`.
+    pub fn commit(&self, poly: &Polynomial<E::Fr>) -> Result<E::G1Affine, KzgError> {
+        let coeffs = poly.coeffs();
+        if coeffs.len() > self.g1_powers.len() {
+            return Err(KzgError::PolynomialTooLarge);
+        }
+        Ok(msm(&self.g1_powers[..coeffs.len()], coeffs).into_affine())
+    }
+
+    /// Opens `poly` at `z`, returning the value `poly(z)` and the proof.
+    pub fn open(
+        &self,
+        poly: &Polynomial<E::Fr>,
+        z: &E::Fr,
+    ) -> Result<(E::Fr, E::G1Affine), KzgError> {
+        let (quotient, value) = poly.divide_by_linear(z);
+        Ok((value, self.commit(&quotient)?))
+    }
+
+    /// Verifies a proof that the polynomial committed to by `commitment` takes `value` at `z`,
+    /// i.e., that `e(C - value * G1, G2) == e(proof, (tau - z) * G2)`.
+    pub fn verify(
+        &self,
+        commitment: &E::G1Affine,
+        z: &E::Fr,
+        value: &E::Fr,
+        proof: &E::G1Affine,
+    ) -> bool {
+        if self.g2_powers.len() < 2 {
+            return false;
+        }
+        let mut lhs = commitment.into_projective();
+        lhs.sub_assign(&E::G1Affine::one().mul(*value));
+        let mut rhs = self.g2_powers[1].into_projective();
+        rhs.sub_assign(&E::G2Affine::one().mul(*z));
+        let mut neg_proof = *proof;
+        neg_proof.negate();
+
+        E::pairing_product(
+            lhs.into_affine(),
+            E::G2Affine::one(),
+            neg_proof,
+            rhs.into_affine(),
+        ) == E::Fqk::one()
+    }
+
+    /// Opens `poly` at all of `points` with a single proof, returning the values and the proof.
+    pub fn open_batch(
+        &self,
+        poly: &Polynomial<E::Fr>,
+        points: &[E::Fr],
+    ) -> Result<(Vec<E::Fr>, E::G1Affine), KzgError> {
+        if points.len() >= self.g2_powers.len() {
+            return Err(KzgError::TooManyPoints);
+        }
+        for (i, z) in points.iter().enumerate() {
+            if points[..i].contains(z) {
+                return Err(KzgError::DuplicatePoint);
+            }
+        }
+        let values = poly.evaluate_batch(points);
+        // unwrap is safe: the vanishing polynomial is monic, hence nonzero, and
+        // inverting its leading coefficient reveals nothing about `poly`
+        let (quotient, _) = poly.div_rem(&Polynomial::from_roots(points)).unwrap();
+        Ok((values, self.commit(&quotient)?))
+    }
+
+    /// Verifies a batch proof that the polynomial committed to by `commitment` takes
+    /// `values[i]` at `points[i]`, i.e., that `e(C - [I(tau)]_1, G2) == e(proof, [Z(tau)]_2)`,
+    /// where `I` interpolates the values and `Z` vanishes on the points.
+    pub fn verify_batch(
+        &self,
+        commitment: &E::G1Affine,
+        points: &[E::Fr],
+        values: &[E::Fr],
+        proof: &E::G1Affine,
+    ) -> bool {
+        if points.len() != values.len() || points.len() >= self.g2_powers.len() {
+            return false;
+        }
+        let interpolant = match Polynomial::interpolate(points, values) {
+            Some(i) => i.into_coeffs(),
+            None => return false,
+        };
+        if interpolant.len() > self.g1_powers.len() {
+            return false;
+        }
+        let vanishing = Polynomial::from_roots(points).into_coeffs();
+
+        let mut lhs = commitment.into_projective();
+        lhs.sub_assign(&msm(&self.g1_powers[..interpolant.len()], &interpolant));
+        let rhs = msm(&self.g2_powers[..vanishing.len()], &vanishing);
+        let mut neg_proof = *proof;
+        neg_proof.negate();
+
+        E::pairing_product(
+            lhs.into_affine(),
+            E::G2Affine::one(),
+            neg_proof,
+            rhs.into_affine(),
+        ) == E::Fqk::one()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Engine> SerDes for Srs<E>
+where
+    E::G1Affine: SerDes,
+    E::G2Affine: SerDes,
+{
+    /// Serializes the number of powers in G1 and in G2 as big-endian u32s,
+    /// followed by the powers themselves.
+    fn serialize<W: Write>(&self, writer: &mut W, compressed: bool) -> Result<(), SerDesError> {
+        writer.write_all(&(self.g1_powers.len() as u32).to_be_bytes())?;
+        writer.write_all(&(self.g2_powers.len() as u32).to_be_bytes())?;
+        for p in &self.g1_powers {
+            p.serialize(writer, compressed)?;
+        }
+        for p in &self.g2_powers {
+            p.serialize(writer, compressed)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a reference string. Every power is checked to be in the
+    /// correct subgroup, but the powers are not checked to be consistent.
+    fn deserialize<R: Read>(reader: &mut R, compressed: bool) -> Result<Self, SerDesError> {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let g1_len = u32::from_be_bytes(len) as usize;
+        reader.read_exact(&mut len)?;
+        let g2_len = u32::from_be_bytes(len) as usize;
+
+        let mut g1_powers = Vec::new();
+        for _ in 0..g1_len {
+            g1_powers.push(E::G1Affine::deserialize(reader, compressed)?);
+        }
+        let mut g2_powers = Vec::new();
+        for _ in 0..g2_len {
+            g2_powers.push(E::G2Affine::deserialize(reader, compressed)?);
+        }
+        Ok(Srs {
+            g1_powers,
+            g2_powers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::{Bls12, Fr};
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn test_kzg_open_verify() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let srs = Srs::<Bls12>::setup(16, 4, &mut rng);
+        let poly = Polynomial::new((0..16).map(|_| Fr::random(&mut rng)).collect());
+        let comm = srs.commit(&poly).unwrap();
+
+        let z = Fr::random(&mut rng);
+        let (value, proof) = srs.open(&poly, &z).unwrap();
+        assert_eq!(value, poly.evaluate(&z));
+        assert!(srs.verify(&comm, &z, &value, &proof));
+
+        let mut bad_value = value;
+        bad_value.add_assign(&Fr::one());
+        assert!(!srs.verify(&comm, &z, &bad_value, &proof));
+        let mut bad_z = z;
+        bad_z.add_assign(&Fr::one());
+        assert!(!srs.verify(&comm, &bad_z, &value, &proof));
+
+        let too_big = Polynomial::new((0..17).map(|_| Fr::random(&mut rng)).collect());
+        assert_eq!(srs.commit(&too_big), Err(KzgError::PolynomialTooLarge));
+    }
+
+    #[test]
+    fn test_kzg_batch_open_verify() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let srs = Srs::<Bls12>::setup(12, 4, &mut rng);
+        let poly = Polynomial::new((0..12).map(|_| Fr::random(&mut rng)).collect());
+        let comm = srs.commit(&poly).unwrap();
+
+        let points: Vec<Fr> = (0..4).map(|_| Fr::random(&mut rng)).collect();
+        let (values, proof) = srs.open_batch(&poly, &points).unwrap();
+        for (z, y) in points.iter().zip(values.iter()) {
+            assert_eq!(*y, poly.evaluate(z));
+        }
+        assert!(srs.verify_batch(&comm, &points, &values, &proof));
+        assert!(srs.verify_batch(&comm, &points[..0], &values[..0], &comm));
+
+        let mut bad_values = values.clone();
+        bad_values[2].add_assign(&Fr::one());
+        assert!(!srs.verify_batch(&comm, &points, &bad_values, &proof));
+        assert!(!srs.verify_batch(&comm, &points[..3], &values[..3], &proof));
+
+        let five: Vec<Fr> = (0..5).map(|_| Fr::random(&mut rng)).collect();
+        assert_eq!(srs.open_batch(&poly, &five), Err(KzgError::TooManyPoints));
+        let dup = [points[0], points[1], points[0]];
+        assert_eq!(srs.open_batch(&poly, &dup), Err(KzgError::DuplicatePoint));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_kzg_srs_serialization() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let srs = Srs::<Bls12>::setup(5, 2, &mut rng);
+        for &compressed in &[true, false] {
+            let mut buf: Vec<u8> = vec![];
+            assert!(srs.serialize(&mut buf, compressed).is_ok());
+            let (g1_size, g2_size) = if compressed { (48, 96) } else { (96, 192) };
+            assert_eq!(buf.len(), 8 + 5 * g1_size + 3 * g2_size);
+            let srs_recover = Srs::<Bls12>::deserialize(&mut buf[..].as_ref(), compressed).unwrap();
+            assert_eq!(srs.g1_powers, srs_recover.g1_powers);
+            assert_eq!(srs.g2_powers, srs_recover.g2_powers);
+            assert!(
+                Srs::<Bls12>::deserialize(&mut buf[..buf.len() - 1].as_ref(), compressed).is_err()
+            );
+        }
+    }
+}
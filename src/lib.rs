@@ -12,11 +12,27 @@
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::cognitive_complexity))]
 // Force public structures to implement Debug
 #![deny(missing_debug_implementations)]
+// Without the `std` feature, only `core` and `alloc` are used.
+#![no_std]
 
+// Tests always run with `std`.
+#[cfg(any(feature = "std", test))]
+#[macro_use]
+extern crate std;
+
+#[macro_use]
+extern crate alloc;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
 extern crate digest;
 extern crate ff_zeroize as ff;
 extern crate rand_core;
 extern crate rand_xorshift;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(test)]
+extern crate serde_json;
 #[cfg(test)]
 extern crate sha2;
 #[cfg(test)]
@@ -27,19 +43,47 @@ extern crate zeroize;
 #[cfg(test)]
 pub mod tests;
 
+#[macro_use]
+mod curve_macros;
+#[macro_use]
+mod encoding_macros;
+
+pub mod batch;
+pub mod bls12;
+pub mod bls12_377;
 pub mod bls12_381;
+pub mod bn256;
+pub mod fft;
 pub mod hash_to_curve;
 pub mod hash_to_field;
+pub mod jubjub;
+pub mod kzg;
 pub mod map_to_curve;
+pub mod polynomial;
+pub mod secret;
+#[cfg(feature = "std")]
 pub mod serdes;
+pub mod shamir;
 pub mod signum;
+pub mod vss;
 
+mod jacobi;
+mod osswu_map;
+mod safegcd;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod wnaf;
 pub use self::wnaf::Wnaf;
 
+use alloc::vec::Vec;
+use core::fmt;
 use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr, ScalarEngine, SqrtField};
+#[cfg(feature = "std")]
+use serdes::SerDesError;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 /// An "engine" is a collection of types (fields, elliptic curve groups, etc.)
 /// with well-defined relationships. In particular, the G1/G2 curve groups are
@@ -383,6 +427,15 @@ pub trait EncodedPoint:
     /// Creates an `EncodedPoint` from an affine point, as long as the
     /// point is not the point at infinity.
     fn from_affine(affine: Self::Affine) -> Self;
+
+    /// Reads an encoded point of `size()` bytes and converts it into a
+    /// `CurveAffine` element, if the encoding represents a valid element.
+    #[cfg(feature = "std")]
+    fn read_affine<R: Read>(reader: &mut R) -> Result<Self::Affine, SerDesError> {
+        let mut buf = Self::empty();
+        reader.read_exact(buf.as_mut())?;
+        Ok(buf.into_affine()?)
+    }
 }
 
 pub trait SubgroupCheck {
@@ -406,6 +459,7 @@ pub enum GroupDecodingError {
     UnexpectedInformation,
 }
 
+#[cfg(feature = "std")]
 impl Error for GroupDecodingError {
     fn description(&self) -> &str {
         match *self {
@@ -430,3 +484,38 @@ impl fmt::Display for GroupDecodingError {
         }
     }
 }
+
+/// An error that may occur when decoding a hex string with `from_hex` or `FromStr`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HexDecodingError {
+    /// The string had an odd number of digits.
+    OddLength,
+    /// The string contained a character that is not a hex digit.
+    InvalidDigit(char),
+    /// The string did not encode the expected number of bytes.
+    InvalidLength,
+    /// The bytes were not a valid encoding.
+    InvalidEncoding,
+}
+
+#[cfg(feature = "std")]
+impl Error for HexDecodingError {
+    fn description(&self) -> &str {
+        match *self {
+            HexDecodingError::OddLength => "odd number of hex digits",
+            HexDecodingError::InvalidDigit(_) => "invalid hex digit",
+            HexDecodingError::InvalidLength => "hex string has the wrong length",
+            HexDecodingError::InvalidEncoding => "hex string is not a valid encoding",
+        }
+    }
+}
+
+impl fmt::Display for HexDecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            HexDecodingError::OddLength => write!(f, "odd number of hex digits"),
+            HexDecodingError::InvalidDigit(c) => write!(f, "invalid hex digit {:?}", c),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
@@ -3,7 +3,7 @@
  in terms of `ClearH + IsogenyMap + OSSWUMap`.
 */
 
-use bls12_381::{ClearH, IsogenyMap, OSSWUMap};
+use osswu_map::{ClearH, IsogenyMap, OSSWUMap};
 use CurveProjective;
 use SubgroupCheck;
 
@@ -0,0 +1,117 @@
+/*!
+Building blocks of the optimized simplified SWU map to curve, shared by the
+BLS12-381 and BLS12-377 implementations: the map onto an isogenous curve,
+the isogeny back to the target curve, and cofactor clearing.
+*/
+
+use ff::Field;
+use CurveProjective;
+
+/// Alias for the coordinate type corresponding to a CurveProjective type
+type CoordT<PtT> = <PtT as CurveProjective>::Base;
+
+/// Trait for mapping from base field element to curve point
+pub trait OSSWUMap: CurveProjective {
+    /// Evaluate optimized simplified SWU map on supplied base field element
+    fn osswu_map(u: &<Self as CurveProjective>::Base) -> Self;
+}
+
+/// Evaluate isogeny map from curve with non-zero j-invariant.
+pub trait IsogenyMap {
+    /// Eavluate isogeny map
+    fn isogeny_map(&mut self);
+}
+
+/// Trait implementing cofactor clearing for projective coords
+pub trait ClearH: CurveProjective {
+    /// Clear the cofactor in-place
+    fn clear_h(&mut self);
+}
+
+/// Generic isogeny evaluation function
+///
+/// `N` is the number of coefficients of the y numerator, which is the longest
+/// of the four polynomials; it sizes the scratch space.
+pub(crate) fn eval_iso<PtT: CurveProjective, const N: usize>(
+    pt: &mut PtT,
+    coeffs: [&[CoordT<PtT>]; 4],
+) {
+    debug_assert!(N >= 3 && coeffs.iter().all(|c| c.len() <= N));
+    let mut tmp = [CoordT::<PtT>::zero(); N];
+    let mut mapvals = [CoordT::<PtT>::zero(); 4];
+    // scope for pt borrow
+    {
+        // unpack input point
+        let (x, y, z) = pt.as_tuple();
+
+        // precompute powers of z
+        let zpows = {
+            let mut zpows = [CoordT::<PtT>::zero(); N];
+            zpows[0] = *z;
+            zpows[0].square(); // z^2
+            zpows[1] = zpows[0];
+            zpows[1].square(); // z^4
+            {
+                let (z_squared, rest) = zpows.split_at_mut(1);
+                for idx in 1..coeffs[2].len() - 2 {
+                    if idx % 2 == 0 {
+                        rest[idx] = rest[idx / 2 - 1];
+                        rest[idx].square();
+                    } else {
+                        rest[idx] = rest[idx - 1];
+                        rest[idx].mul_assign(&z_squared[0]);
+                    }
+                }
+            }
+            zpows
+        };
+
+        for idx in 0..4 {
+            let clen = coeffs[idx].len() - 1;
+            // multiply coeffs by powers of Z
+            for jdx in 0..clen {
+                tmp[jdx] = coeffs[idx][clen - 1 - jdx];
+                tmp[jdx].mul_assign(&zpows[jdx]);
+            }
+            // compute map value by Horner's rule
+            mapvals[idx] = coeffs[idx][clen];
+            for tmpval in &tmp[..clen] {
+                mapvals[idx].mul_assign(x);
+                mapvals[idx].add_assign(tmpval);
+            }
+        }
+
+        // x denominator is order 1 less than x numerator, so we need an extra factor of Z^2
+        mapvals[1].mul_assign(&zpows[0]);
+
+        // multiply result of Y map by the y-coord, y / z^3
+        mapvals[2].mul_assign(y);
+        mapvals[3].mul_assign(z);
+        mapvals[3].mul_assign(&zpows[0]);
+    } // pt is no longer borrowed here
+
+    // hack to simultaneously access elements of tmp
+    let (xx, yy, zz) = {
+        let (xx, rest) = tmp.split_at_mut(1);
+        let (yy, rest) = rest.split_at_mut(1);
+        (&mut xx[0], &mut yy[0], &mut rest[0])
+    };
+
+    // compute Jacobian coordinates of resulting point
+    *zz = mapvals[1];
+    zz.mul_assign(&mapvals[3]); // Zout = xden * yden
+
+    *xx = mapvals[0];
+    xx.mul_assign(&mapvals[3]); // xnum * yden
+    xx.mul_assign(zz); // xnum * xden * yden^2
+
+    *yy = *zz;
+    yy.square(); // xden^2 * yden^2
+    yy.mul_assign(&mapvals[2]); // ynum * xden^2 * yden^2
+    yy.mul_assign(&mapvals[1]); // ynum * xden^3 * yden^2
+
+    let (x, y, z) = unsafe { pt.as_tuple_mut() };
+    *x = *xx;
+    *y = *yy;
+    *z = *zz;
+}
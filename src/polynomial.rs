@@ -0,0 +1,370 @@
+/*!
+ This module implements dense univariate polynomials over a prime field,
+ generic over `PrimeField` so that they work with the scalar field `Fr`
+ of any `Engine`.
+
+ Coefficients are stored in order of increasing degree and without
+ trailing zeros, so the zero polynomial has no coefficients. Products
+ of large polynomials are computed with the FFTs in `fft`.
+*/
+
+use alloc::vec::Vec;
+use ff::PrimeField;
+use fft::{mul_polynomials, EvaluationDomain};
+use secret::wipe;
+use zeroize::Zeroize;
+
+/// Products where both factors have at least this many coefficients are computed with FFTs.
+const FFT_MUL_THRESHOLD: usize = 64;
+
+/// A polynomial over `F` in coefficient form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial<F: PrimeField> {
+    coeffs: Vec<F>,
+}
+
+impl<F: PrimeField> Polynomial<F> {
+    /// Creates a polynomial from its coefficients, in order of increasing degree.
+    pub fn new(coeffs: Vec<F>) -> Self {
+        let mut res = Polynomial { coeffs };
+        res.truncate();
+        res
+    }
+
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Polynomial { coeffs: vec![] }
+    }
+
+    /// Returns the constant polynomial `c`.
+    pub fn constant(c: F) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// Returns the monic polynomial `prod_i (x - roots[i])`.
+    pub fn from_roots(roots: &[F]) -> Self {
+        let mut coeffs = vec![F::one()];
+        for z in roots {
+            coeffs.push(F::zero());
+            for i in (0..coeffs.len() - 1).rev() {
+                // (x - z) * c x^i = c x^(i+1) - z c x^i
+                let c = coeffs[i];
+                coeffs[i + 1].add_assign(&c);
+                coeffs[i].mul_assign(z);
+                coeffs[i].negate();
+            }
+        }
+        Polynomial { coeffs }
+    }
+
+    /// Returns the polynomial of degree less than `points.len()` that takes `values[i]`
+    /// at `points[i]`, or `None` if the lengths differ or a point is repeated.
+    /// Only values computed from `points` are inverted, so `values` may be secret.
+    pub fn interpolate(points: &[F], values: &[F]) -> Option<Self> {
+        if points.len() != values.len() {
+            return None;
+        }
+        let vanishing = Self::from_roots(points);
+        let mut res = vec![F::zero(); points.len()];
+        for (zi, yi) in points.iter().zip(values.iter()) {
+            // basis_i = vanishing / (x - z_i), scaled by y_i / basis_i(z_i)
+            let basis = vanishing.divide_by_linear(zi).0;
+            let mut scale = basis.evaluate(zi).inverse()?;
+            scale.mul_assign(yi);
+            for (r, b) in res.iter_mut().zip(basis.coeffs.iter()) {
+                let mut tmp = *b;
+                tmp.mul_assign(&scale);
+                r.add_assign(&tmp);
+            }
+        }
+        Some(Self::new(res))
+    }
+
+    /// Returns the coefficients, in order of increasing degree.
+    pub fn coeffs(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    /// Consumes the polynomial, returning its coefficients.
+    pub fn into_coeffs(self) -> Vec<F> {
+        self.coeffs
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns the coefficient of the highest-degree term (zero for the zero polynomial).
+    pub fn leading_coefficient(&self) -> F {
+        self.coeffs.last().cloned().unwrap_or_else(F::zero)
+    }
+
+    /// Evaluates the polynomial at `x` using Horner's rule.
+    pub fn evaluate(&self, x: &F) -> F {
+        let mut res = F::zero();
+        for c in self.coeffs.iter().rev() {
+            res.mul_assign(x);
+            res.add_assign(c);
+        }
+        res
+    }
+
+    /// Evaluates the polynomial at each of `points`.
+    pub fn evaluate_batch(&self, points: &[F]) -> Vec<F> {
+        points.iter().map(|x| self.evaluate(x)).collect()
+    }
+
+    /// Evaluates the polynomial at every element of `domain` with an FFT,
+    /// in the order given by `EvaluationDomain::elements`.
+    pub fn evaluate_over_domain(&self, domain: &EvaluationDomain<F>) -> Vec<F> {
+        // reduce modulo x^n - 1, which does not change the values on the domain
+        let mut evals = vec![F::zero(); domain.size()];
+        for (i, c) in self.coeffs.iter().enumerate() {
+            evals[i % domain.size()].add_assign(c);
+        }
+        domain.fft(&mut evals);
+        evals
+    }
+
+    /// Returns the formal derivative of the polynomial.
+    pub fn derivative(&self) -> Self {
+        let mut coeffs = Vec::with_capacity(self.coeffs.len().saturating_sub(1));
+        let mut k = F::zero();
+        for c in self.coeffs.iter().skip(1) {
+            k.add_assign(&F::one());
+            let mut tmp = *c;
+            tmp.mul_assign(&k);
+            coeffs.push(tmp);
+        }
+        Self::new(coeffs)
+    }
+
+    /// Adds `other` to this polynomial.
+    pub fn add_assign(&mut self, other: &Self) {
+        if self.coeffs.len() < other.coeffs.len() {
+            self.coeffs.resize(other.coeffs.len(), F::zero());
+        }
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.add_assign(b);
+        }
+        self.truncate();
+    }
+
+    /// Subtracts `other` from this polynomial.
+    pub fn sub_assign(&mut self, other: &Self) {
+        if self.coeffs.len() < other.coeffs.len() {
+            self.coeffs.resize(other.coeffs.len(), F::zero());
+        }
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.sub_assign(b);
+        }
+        self.truncate();
+    }
+
+    /// Negates this polynomial.
+    pub fn negate(&mut self) {
+        for c in self.coeffs.iter_mut() {
+            c.negate();
+        }
+    }
+
+    /// Multiplies this polynomial by the constant `c`.
+    pub fn scale(&mut self, c: &F) {
+        for a in self.coeffs.iter_mut() {
+            a.mul_assign(c);
+        }
+        self.truncate();
+    }
+
+    /// Returns the product of this polynomial and `other`.
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        if self.coeffs.len().min(other.coeffs.len()) >= FFT_MUL_THRESHOLD {
+            return Self::new(mul_polynomials(&self.coeffs, &other.coeffs));
+        }
+        let mut coeffs = vec![F::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                let mut tmp = *a;
+                tmp.mul_assign(b);
+                coeffs[i + j].add_assign(&tmp);
+            }
+        }
+        Self::new(coeffs)
+    }
+
+    /// Divides this polynomial by `divisor`, returning the quotient and the remainder,
+    /// or `None` if `divisor` is zero. The leading coefficient of `divisor` is
+    /// inverted with `Field::inverse`, which need not run in constant time.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let lead_inv = divisor.leading_coefficient().inverse()?;
+        let d = divisor.coeffs.len() - 1;
+        if self.coeffs.len() <= d {
+            return Some((Self::zero(), self.clone()));
+        }
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![F::zero(); self.coeffs.len() - d];
+        for i in (0..quot.len()).rev() {
+            let mut lead = rem[i + d];
+            lead.mul_assign(&lead_inv);
+            quot[i] = lead;
+            for (j, c) in divisor.coeffs.iter().enumerate() {
+                let mut tmp = *c;
+                tmp.mul_assign(&lead);
+                rem[i + j].sub_assign(&tmp);
+            }
+        }
+        rem.truncate(d);
+        Some((Self::new(quot), Self::new(rem)))
+    }
+
+    /// Divides this polynomial by `x - z`, returning the quotient and the remainder,
+    /// which is the value of the polynomial at `z`.
+    pub fn divide_by_linear(&self, z: &F) -> (Self, F) {
+        if self.is_zero() {
+            return (Self::zero(), F::zero());
+        }
+        // synthetic division
+        let mut quot = vec![F::zero(); self.coeffs.len() - 1];
+        let mut acc = F::zero();
+        for i in (0..self.coeffs.len()).rev() {
+            acc.mul_assign(z);
+            acc.add_assign(&self.coeffs[i]);
+            if i > 0 {
+                quot[i - 1] = acc;
+            }
+        }
+        (Self::new(quot), acc)
+    }
+
+    fn truncate(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.is_zero()) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+/// Zeroizing a polynomial wipes its coefficients and leaves the zero polynomial,
+/// for polynomials that hide a secret, such as Shamir sharing polynomials.
+impl<F: PrimeField> Zeroize for Polynomial<F> {
+    fn zeroize(&mut self) {
+        for c in self.coeffs.iter_mut() {
+            wipe(c);
+        }
+        self.coeffs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Fr;
+    use ff::Field;
+    use rand_core::{RngCore, SeedableRng};
+
+    fn random_poly<R: RngCore>(n: usize, rng: &mut R) -> Polynomial<Fr> {
+        Polynomial::new((0..n).map(|_| Fr::random(rng)).collect())
+    }
+
+    fn fr(n: u64) -> Fr {
+        Fr::from_repr(n.into()).unwrap()
+    }
+
+    #[test]
+    fn test_polynomial_basics() {
+        let p = Polynomial::new(vec![fr(1), fr(2), fr(3), Fr::zero()]);
+        assert_eq!(p.coeffs(), &[fr(1), fr(2), fr(3)]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.evaluate(&fr(2)), fr(17));
+        assert_eq!(p.derivative(), Polynomial::new(vec![fr(2), fr(6)]));
+        assert_eq!(p.evaluate_batch(&[fr(0), fr(1)]), vec![fr(1), fr(6)]);
+
+        let z = Polynomial::<Fr>::zero();
+        assert!(z.is_zero());
+        assert_eq!(z.degree(), None);
+        assert_eq!(Polynomial::constant(Fr::zero()), z);
+        assert_eq!(z.derivative(), z);
+
+        let mut q = p.clone();
+        q.sub_assign(&p);
+        assert!(q.is_zero());
+        q.add_assign(&p);
+        q.negate();
+        q.add_assign(&p);
+        assert!(q.is_zero());
+
+        let r = Polynomial::from_roots(&[fr(1), fr(2)]);
+        let mut neg_three = fr(3);
+        neg_three.negate();
+        assert_eq!(r.coeffs(), &[fr(2), neg_three, fr(1)]);
+    }
+
+    #[test]
+    fn test_polynomial_mul_div() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for &(la, lb) in &[(1, 1), (5, 3), (70, 65), (100, 10)] {
+            let a = random_poly(la, &mut rng);
+            let b = random_poly(lb, &mut rng);
+            let x = Fr::random(&mut rng);
+            let ab = a.mul(&b);
+            let mut expect = a.evaluate(&x);
+            expect.mul_assign(&b.evaluate(&x));
+            assert_eq!(ab.evaluate(&x), expect);
+            assert_eq!(ab.degree(), Some(la + lb - 2));
+
+            let (q, r) = a.div_rem(&b).unwrap();
+            assert!(r.degree() < b.degree() || r.is_zero());
+            let mut back = q.mul(&b);
+            back.add_assign(&r);
+            assert_eq!(back, a);
+        }
+        assert!(random_poly(3, &mut rng)
+            .div_rem(&Polynomial::zero())
+            .is_none());
+
+        let p = random_poly(10, &mut rng);
+        let z = Fr::random(&mut rng);
+        let (q, v) = p.divide_by_linear(&z);
+        assert_eq!(v, p.evaluate(&z));
+        assert_eq!(
+            p.div_rem(&Polynomial::from_roots(&[z])).unwrap(),
+            (q, Polynomial::constant(v))
+        );
+    }
+
+    #[test]
+    fn test_polynomial_interpolate() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let p = random_poly(8, &mut rng);
+        let points: Vec<Fr> = (0..8).map(|_| Fr::random(&mut rng)).collect();
+        let values = p.evaluate_batch(&points);
+        assert_eq!(Polynomial::interpolate(&points, &values), Some(p.clone()));
+        assert_eq!(
+            Polynomial::interpolate(&points[..0], &values[..0]),
+            Some(Polynomial::zero())
+        );
+        assert!(Polynomial::interpolate(&points, &values[1..]).is_none());
+        let dup = [points[0], points[0]];
+        assert!(Polynomial::interpolate(&dup, &values[..2]).is_none());
+
+        let domain = EvaluationDomain::new(4).unwrap();
+        assert_eq!(
+            p.evaluate_over_domain(&domain),
+            p.evaluate_batch(&domain.elements())
+        );
+    }
+}
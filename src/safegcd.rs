@@ -0,0 +1,283 @@
+/*!
+ Variable-time modular inversion with the Bernstein-Yang "safegcd" algorithm
+ (https://eprint.iacr.org/2019/266), in the variant used by libsecp256k1's
+ `modinv64`: each iteration performs 62 divsteps on the low limbs of f and g
+ and applies the resulting transition matrix to the full-width values.
+
+ Numbers are represented by `N` signed 62-bit limbs. The running time
+ depends on the input, so this must only be used on public data.
+*/
+
+const M62: u64 = u64::MAX >> 2;
+
+/// An odd modulus in signed 62-bit limbs, together with its inverse modulo 2^62.
+#[derive(Debug)]
+pub(crate) struct ModInfo<const N: usize> {
+    modulus: [i64; N],
+    modulus_inv62: u64,
+}
+
+impl<const N: usize> ModInfo<N> {
+    /// Builds the modulus information from little-endian 64-bit limbs.
+    pub(crate) const fn new(limbs: &[u64]) -> Self {
+        // Newton iteration for the inverse modulo 2^64; each step doubles the
+        // number of correct low bits, starting from 3 (m * m = 1 mod 8).
+        let mut inv = limbs[0];
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(limbs[0].wrapping_mul(inv)));
+            i += 1;
+        }
+        ModInfo {
+            modulus: to_signed62(limbs),
+            modulus_inv62: inv & M62,
+        }
+    }
+}
+
+/// The transition matrix [[u, v], [q, r]] of 62 divsteps, scaled by 2^62.
+struct Trans {
+    u: i64,
+    v: i64,
+    q: i64,
+    r: i64,
+}
+
+const fn to_signed62<const N: usize>(limbs: &[u64]) -> [i64; N] {
+    let mut res = [0i64; N];
+    let mut i = 0;
+    while i < N {
+        let bit = 62 * i;
+        let (limb, shift) = (bit / 64, bit % 64);
+        let mut v = 0;
+        if limb < limbs.len() {
+            v = limbs[limb] >> shift;
+            if shift > 2 && limb + 1 < limbs.len() {
+                v |= limbs[limb + 1] << (64 - shift);
+            }
+        }
+        res[i] = (v & M62) as i64;
+        i += 1;
+    }
+    res
+}
+
+/// Converts normalized signed 62-bit limbs, all in [0, 2^62), into 64-bit limbs.
+fn from_signed62<const N: usize>(v: &[i64; N], out: &mut [u64]) {
+    for o in out.iter_mut() {
+        *o = 0;
+    }
+    for (i, &l) in v.iter().enumerate() {
+        let bit = 62 * i;
+        let (limb, shift) = (bit / 64, bit % 64);
+        if limb < out.len() {
+            out[limb] |= (l as u64) << shift;
+        }
+        if shift > 2 && limb + 1 < out.len() {
+            out[limb + 1] |= (l as u64) >> (64 - shift);
+        }
+    }
+}
+
+/// Performs up to 62 divsteps on the low bits f0 and g0 of f and g,
+/// returning the new eta and the transition matrix.
+fn divsteps_62_var(mut eta: i64, f0: u64, g0: u64) -> (i64, Trans) {
+    let (mut u, mut v, mut q, mut r) = (1u64, 0u64, 0u64, 1u64);
+    let (mut f, mut g) = (f0, g0);
+    let mut i = 62;
+
+    loop {
+        // Remove the trailing zeros of g, up to the number of divsteps left.
+        let zeros = (g | (u64::MAX << i)).trailing_zeros();
+        g >>= zeros;
+        u <<= zeros;
+        v <<= zeros;
+        eta -= i64::from(zeros);
+        i -= zeros;
+        if i == 0 {
+            break;
+        }
+
+        // g is now odd. If eta is negative, swap f and g and negate.
+        let w;
+        if eta < 0 {
+            eta = -eta;
+            let tmp = f;
+            f = g;
+            g = tmp.wrapping_neg();
+            let tmp = u;
+            u = q;
+            q = tmp.wrapping_neg();
+            let tmp = v;
+            v = r;
+            r = tmp.wrapping_neg();
+
+            // Cancel up to 6 bits of g at once.
+            let limit = ::core::cmp::min(eta as u32 + 1, i);
+            let m = (u64::MAX >> (64 - limit)) & 63;
+            w = f
+                .wrapping_mul(g)
+                .wrapping_mul(f.wrapping_mul(f).wrapping_sub(2))
+                & m;
+        } else {
+            // Cancel up to 4 bits of g at once.
+            let limit = ::core::cmp::min(eta as u32 + 1, i);
+            let m = (u64::MAX >> (64 - limit)) & 15;
+            let w0 = f.wrapping_add((f.wrapping_add(1) & 4) << 1);
+            w = w0.wrapping_neg().wrapping_mul(g) & m;
+        }
+        g = g.wrapping_add(f.wrapping_mul(w));
+        q = q.wrapping_add(u.wrapping_mul(w));
+        r = r.wrapping_add(v.wrapping_mul(w));
+    }
+
+    (
+        eta,
+        Trans {
+            u: u as i64,
+            v: v as i64,
+            q: q as i64,
+            r: r as i64,
+        },
+    )
+}
+
+/// Computes (t * [d, e]) / 2^62 modulo the modulus, keeping d and e in
+/// the range (-2 * modulus, modulus).
+fn update_de<const N: usize>(d: &mut [i64; N], e: &mut [i64; N], t: &Trans, info: &ModInfo<N>) {
+    let (u, v, q, r) = (t.u, t.v, t.q, t.r);
+
+    // [md, me] start as zero; plus [u, q] if d is negative; plus [v, r] if e is negative.
+    let sd = d[N - 1] >> 63;
+    let se = e[N - 1] >> 63;
+    let mut md = (u & sd) + (v & se);
+    let mut me = (q & sd) + (r & se);
+
+    // Begin computing t * [d, e].
+    let mut cd = i128::from(u) * i128::from(d[0]) + i128::from(v) * i128::from(e[0]);
+    let mut ce = i128::from(q) * i128::from(d[0]) + i128::from(r) * i128::from(e[0]);
+
+    // Correct md and me so that t * [d, e] + modulus * [md, me] has 62 zero bottom bits.
+    md -= (info
+        .modulus_inv62
+        .wrapping_mul(cd as u64)
+        .wrapping_add(md as u64)
+        & M62) as i64;
+    me -= (info
+        .modulus_inv62
+        .wrapping_mul(ce as u64)
+        .wrapping_add(me as u64)
+        & M62) as i64;
+
+    cd += i128::from(info.modulus[0]) * i128::from(md);
+    ce += i128::from(info.modulus[0]) * i128::from(me);
+    debug_assert_eq!(cd as u64 & M62, 0);
+    debug_assert_eq!(ce as u64 & M62, 0);
+    cd >>= 62;
+    ce >>= 62;
+
+    // Compute the remaining limbs, shifting them down by one position.
+    for i in 1..N {
+        cd += i128::from(u) * i128::from(d[i])
+            + i128::from(v) * i128::from(e[i])
+            + i128::from(info.modulus[i]) * i128::from(md);
+        ce += i128::from(q) * i128::from(d[i])
+            + i128::from(r) * i128::from(e[i])
+            + i128::from(info.modulus[i]) * i128::from(me);
+        d[i - 1] = (cd as u64 & M62) as i64;
+        e[i - 1] = (ce as u64 & M62) as i64;
+        cd >>= 62;
+        ce >>= 62;
+    }
+    d[N - 1] = cd as i64;
+    e[N - 1] = ce as i64;
+}
+
+/// Computes (t * [f, g]) / 2^62, looking only at the first `len` limbs.
+fn update_fg<const N: usize>(len: usize, f: &mut [i64; N], g: &mut [i64; N], t: &Trans) {
+    let (u, v, q, r) = (t.u, t.v, t.q, t.r);
+
+    let mut cf = i128::from(u) * i128::from(f[0]) + i128::from(v) * i128::from(g[0]);
+    let mut cg = i128::from(q) * i128::from(f[0]) + i128::from(r) * i128::from(g[0]);
+    debug_assert_eq!(cf as u64 & M62, 0);
+    debug_assert_eq!(cg as u64 & M62, 0);
+    cf >>= 62;
+    cg >>= 62;
+
+    for i in 1..len {
+        cf += i128::from(u) * i128::from(f[i]) + i128::from(v) * i128::from(g[i]);
+        cg += i128::from(q) * i128::from(f[i]) + i128::from(r) * i128::from(g[i]);
+        f[i - 1] = (cf as u64 & M62) as i64;
+        g[i - 1] = (cg as u64 & M62) as i64;
+        cf >>= 62;
+        cg >>= 62;
+    }
+    f[len - 1] = cf as i64;
+    g[len - 1] = cg as i64;
+}
+
+/// Maps r from (-2 * modulus, modulus) to [0, modulus), negating it if `sign` is negative.
+fn normalize<const N: usize>(r: &mut [i64; N], sign: i64, info: &ModInfo<N>) {
+    fn propagate<const N: usize>(r: &mut [i64; N]) {
+        for i in 0..N - 1 {
+            r[i + 1] += r[i] >> 62;
+            r[i] &= M62 as i64;
+        }
+    }
+
+    // Add the modulus if r is negative, and then negate if requested.
+    let cond_add = r[N - 1] >> 63;
+    let cond_negate = sign >> 63;
+    for (l, m) in r.iter_mut().zip(info.modulus.iter()) {
+        *l += m & cond_add;
+        *l = (*l ^ cond_negate) - cond_negate;
+    }
+    propagate(r);
+
+    // Add the modulus again if the result is still negative.
+    let cond_add = r[N - 1] >> 63;
+    for (l, m) in r.iter_mut().zip(info.modulus.iter()) {
+        *l += m & cond_add;
+    }
+    propagate(r);
+}
+
+/// Computes the inverse of x modulo the modulus, writing the result to `out`.
+/// Requires 0 < x < modulus.
+pub(crate) fn inverse_vartime<const N: usize>(x: &[u64], info: &ModInfo<N>, out: &mut [u64]) {
+    let mut d = [0i64; N];
+    let mut e = [0i64; N];
+    e[0] = 1;
+    let mut f = info.modulus;
+    let mut g = to_signed62::<N>(x);
+    let mut len = N;
+    let mut eta = -1;
+
+    loop {
+        let (new_eta, t) = divsteps_62_var(eta, f[0] as u64, g[0] as u64);
+        eta = new_eta;
+        update_de(&mut d, &mut e, &t, info);
+        update_fg(len, &mut f, &mut g, &t);
+
+        // Stop once g is zero.
+        if g[0] == 0 && g[1..len].iter().all(|&l| l == 0) {
+            break;
+        }
+
+        // Shorten f and g if both their top limbs are 0 or -1.
+        let fn_ = f[len - 1];
+        let gn = g[len - 1];
+        let mut cond = (len as i64 - 2) >> 63;
+        cond |= fn_ ^ (fn_ >> 63);
+        cond |= gn ^ (gn >> 63);
+        if cond == 0 {
+            f[len - 2] |= ((fn_ as u64) << 62) as i64;
+            g[len - 2] |= ((gn as u64) << 62) as i64;
+            len -= 1;
+        }
+    }
+
+    // Now g is zero and f is +-1, the gcd; d holds the inverse of x times f.
+    normalize(&mut d, f[len - 1], info);
+    from_signed62(&d, out);
+}
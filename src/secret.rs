@@ -0,0 +1,112 @@
+/*!
+ This module implements `SecretScalar`, a wrapper for secret field elements
+ (signing keys, trapdoors, sharing polynomials) that overwrites its value
+ with zero when it is dropped.
+
+ `Field` does not require `Zeroize`, so the wiping is done with a volatile
+ write of `F::zero()`, which the compiler may not elide; this also lets
+ generic code such as `Share` clear any `PrimeField` element.
+*/
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+use ff::{Field, PrimeField};
+use rand_core::RngCore;
+use zeroize::Zeroize;
+
+/// Overwrites `x` with zero in a way that is not optimized away.
+pub(crate) fn wipe<F: Field>(x: &mut F) {
+    // safe: `x` is a valid, aligned reference and `F: Copy` has no destructor to skip
+    unsafe { ptr::write_volatile(x, F::zero()) };
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// A secret scalar that is zeroized when dropped.
+///
+/// It dereferences to the wrapped scalar, so it can be passed wherever a `&F` is expected.
+/// Copying the scalar out, e.g. with `*secret`, creates a copy that is not wiped.
+#[derive(Clone)]
+pub struct SecretScalar<F: PrimeField>(F);
+
+impl<F: PrimeField> SecretScalar<F> {
+    /// Wraps `secret`.
+    pub fn new(secret: F) -> Self {
+        SecretScalar(secret)
+    }
+
+    /// Samples a uniformly random secret scalar.
+    pub fn random<R: RngCore>(rng: &mut R) -> Self {
+        SecretScalar(F::random(rng))
+    }
+}
+
+impl<F: PrimeField> From<F> for SecretScalar<F> {
+    fn from(secret: F) -> Self {
+        SecretScalar(secret)
+    }
+}
+
+impl<F: PrimeField> Deref for SecretScalar<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.0
+    }
+}
+
+impl<F: PrimeField> DerefMut for SecretScalar<F> {
+    fn deref_mut(&mut self) -> &mut F {
+        &mut self.0
+    }
+}
+
+impl<F: PrimeField> fmt::Debug for SecretScalar<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // never print the secret
+        f.write_str("SecretScalar(..)")
+    }
+}
+
+impl<F: PrimeField> Zeroize for SecretScalar<F> {
+    fn zeroize(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+impl<F: PrimeField> Drop for SecretScalar<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Fr;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn test_secret_scalar() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let x = Fr::random(&mut rng);
+        let mut s = SecretScalar::new(x);
+        assert_eq!(*s, x);
+        assert_eq!(format!("{:?}", s), "SecretScalar(..)");
+
+        // arithmetic goes through the dereferenced scalar
+        let mut y = Fr::one();
+        y.mul_assign(&s);
+        assert_eq!(y, x);
+        s.add_assign(&Fr::one());
+        y.add_assign(&Fr::one());
+        assert_eq!(*s, y);
+
+        s.zeroize();
+        assert!(s.is_zero());
+    }
+}
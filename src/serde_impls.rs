@@ -0,0 +1,354 @@
+/*!
+ `Serialize` and `Deserialize` implementations for the field and group types
+ of the BLS12-381, BLS12-377 and BN254 engines and of the Jubjub curve,
+ enabled by the `serde` feature.
+
+ Values are encoded with their fixed-size `to_bytes` methods: field elements
+ as the big-endian encodings of their coefficients concatenated, and group
+ elements in compressed form. Prepared points are encoded as the points they
+ were prepared from. Human-readable formats get the encoding as a hex string,
+ and binary formats get it as raw bytes.
+
+ Deserialization goes through `from_bytes`: field elements must be canonical,
+ and group elements must be on the curve and, except for Jubjub affine
+ points, in the subgroup.
+*/
+
+use bls12::{Bls12Parameters, G1Prepared, G2Prepared};
+use core::fmt;
+use core::marker::PhantomData;
+use encoding_macros::{from_hex, to_hex};
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use {bls12_377, bls12_381, bn256, jubjub, CurveAffine};
+
+/// Conversion of a type to and from its fixed-length serde encoding.
+trait SerdeBytes: Sized {
+    /// The length of the encoding in bytes.
+    const SIZE: usize;
+    /// What is being decoded, for error messages.
+    const EXPECTING: &'static str;
+    /// The fixed-size encoding.
+    type Bytes: AsRef<[u8]>;
+
+    fn to_bytes(&self) -> Self::Bytes;
+    /// Decodes `SIZE` bytes, returning None if they are not a valid encoding.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! serde_bytes_impl {
+    ($type:ty, $size:expr, $expecting:expr) => {
+        impl SerdeBytes for $type {
+            const SIZE: usize = $size;
+            const EXPECTING: &'static str = $expecting;
+            type Bytes = [u8; $size];
+
+            fn to_bytes(&self) -> [u8; $size] {
+                <$type>::to_bytes(self)
+            }
+
+            fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                let mut buf = [0u8; $size];
+                buf.copy_from_slice(bytes);
+                <$type>::from_bytes(&buf)
+            }
+        }
+    };
+}
+
+serde_bytes_impl!(bls12_381::Fr, 32, "an encoded Fr element");
+serde_bytes_impl!(bls12_381::Fq, 48, "an encoded Fq element");
+serde_bytes_impl!(bls12_381::Fq2, 96, "an encoded Fq2 element");
+serde_bytes_impl!(bls12_381::Fq6, 288, "an encoded Fq6 element");
+serde_bytes_impl!(bls12_381::Fq12, 576, "an encoded Fq12 element");
+serde_bytes_impl!(bls12_381::G1, 48, "a compressed G1 element");
+serde_bytes_impl!(bls12_381::G1Affine, 48, "a compressed G1 element");
+serde_bytes_impl!(bls12_381::G2, 96, "a compressed G2 element");
+serde_bytes_impl!(bls12_381::G2Affine, 96, "a compressed G2 element");
+
+serde_bytes_impl!(bls12_377::Fr, 32, "an encoded Fr element");
+serde_bytes_impl!(bls12_377::Fq, 48, "an encoded Fq element");
+serde_bytes_impl!(bls12_377::Fq2, 96, "an encoded Fq2 element");
+serde_bytes_impl!(bls12_377::Fq6, 288, "an encoded Fq6 element");
+serde_bytes_impl!(bls12_377::Fq12, 576, "an encoded Fq12 element");
+serde_bytes_impl!(bls12_377::G1, 48, "a compressed G1 element");
+serde_bytes_impl!(bls12_377::G1Affine, 48, "a compressed G1 element");
+serde_bytes_impl!(bls12_377::G2, 96, "a compressed G2 element");
+serde_bytes_impl!(bls12_377::G2Affine, 96, "a compressed G2 element");
+
+serde_bytes_impl!(bn256::Fr, 32, "an encoded Fr element");
+serde_bytes_impl!(bn256::Fq, 32, "an encoded Fq element");
+serde_bytes_impl!(bn256::Fq2, 64, "an encoded Fq2 element");
+serde_bytes_impl!(bn256::Fq6, 192, "an encoded Fq6 element");
+serde_bytes_impl!(bn256::Fq12, 384, "an encoded Fq12 element");
+serde_bytes_impl!(bn256::G1, 32, "a compressed G1 element");
+serde_bytes_impl!(bn256::G1Affine, 32, "a compressed G1 element");
+serde_bytes_impl!(bn256::G2, 64, "a compressed G2 element");
+serde_bytes_impl!(bn256::G2Affine, 64, "a compressed G2 element");
+
+serde_bytes_impl!(jubjub::Fs, 32, "an encoded Fs element");
+serde_bytes_impl!(jubjub::AffinePoint, 32, "an encoded Jubjub point");
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<T: SerdeBytes> BytesVisitor<T> {
+    fn decode<E: de::Error>(bytes: &[u8]) -> Result<T, E> {
+        if bytes.len() != T::SIZE {
+            return Err(E::invalid_length(bytes.len(), &T::EXPECTING));
+        }
+        T::from_bytes(bytes)
+            .ok_or_else(|| E::invalid_value(Unexpected::Bytes(bytes), &T::EXPECTING))
+    }
+}
+
+impl<'de, T: SerdeBytes> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(T::EXPECTING)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        Self::decode(&from_hex(s).map_err(E::custom)?)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<T, E> {
+        Self::decode(bytes)
+    }
+}
+
+macro_rules! serde_impl {
+    ($type:ty) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let bytes = SerdeBytes::to_bytes(self);
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&to_hex(bytes.as_ref()))
+                } else {
+                    serializer.serialize_bytes(bytes.as_ref())
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(BytesVisitor(PhantomData))
+                } else {
+                    deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+                }
+            }
+        }
+    };
+}
+
+serde_impl!(bls12_381::Fr);
+serde_impl!(bls12_381::Fq);
+serde_impl!(bls12_381::Fq2);
+serde_impl!(bls12_381::Fq6);
+serde_impl!(bls12_381::Fq12);
+serde_impl!(bls12_381::G1);
+serde_impl!(bls12_381::G1Affine);
+serde_impl!(bls12_381::G2);
+serde_impl!(bls12_381::G2Affine);
+
+serde_impl!(bls12_377::Fr);
+serde_impl!(bls12_377::Fq);
+serde_impl!(bls12_377::Fq2);
+serde_impl!(bls12_377::Fq6);
+serde_impl!(bls12_377::Fq12);
+serde_impl!(bls12_377::G1);
+serde_impl!(bls12_377::G1Affine);
+serde_impl!(bls12_377::G2);
+serde_impl!(bls12_377::G2Affine);
+
+serde_impl!(bn256::Fr);
+serde_impl!(bn256::Fq);
+serde_impl!(bn256::Fq2);
+serde_impl!(bn256::Fq6);
+serde_impl!(bn256::Fq12);
+serde_impl!(bn256::G1);
+serde_impl!(bn256::G1Affine);
+serde_impl!(bn256::G2);
+serde_impl!(bn256::G2Affine);
+
+serde_impl!(jubjub::Fs);
+serde_impl!(jubjub::AffinePoint);
+
+// Prepared points are encoded as the points they were prepared from, which
+// are validated and prepared again when decoding.
+macro_rules! serde_prepared_impl {
+    (impl<$($param:ident: $bound:path),*> $type:ty, $affine:ty, $field:tt) => {
+        impl<$($param: $bound),*> Serialize for $type
+        where
+            $affine: Serialize,
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.$field.serialize(serializer)
+            }
+        }
+
+        impl<'de, $($param: $bound),*> Deserialize<'de> for $type
+        where
+            $affine: Deserialize<'de>,
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$affine>::deserialize(deserializer).map(|p| p.prepare())
+            }
+        }
+    };
+}
+
+serde_prepared_impl!(impl<P: Bls12Parameters> G1Prepared<P>, P::G1Affine, 0);
+serde_prepared_impl!(impl<P: Bls12Parameters> G2Prepared<P>, P::G2Affine, point);
+serde_prepared_impl!(impl<> bn256::G1Prepared, bn256::G1Affine, 0);
+serde_prepared_impl!(impl<> bn256::G2Prepared, bn256::G2Affine, point);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::{Fq, Fr, G1Affine, G1Compressed, G1};
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use {CurveProjective, EncodedPoint};
+
+    fn round_trip<T>(values: &[T], size: usize)
+    where
+        T: SerdeBytes + Serialize + for<'de> Deserialize<'de> + PartialEq + fmt::Debug,
+    {
+        for v in values {
+            let json = serde_json::to_string(v).unwrap();
+            assert_eq!(json, format!("\"{}\"", to_hex(v.to_bytes().as_ref())));
+            assert_eq!(json.len(), 2 * size + 2);
+            assert_eq!(serde_json::from_str::<T>(&json).unwrap(), *v);
+            // upper case hex is accepted too
+            assert_eq!(serde_json::from_str::<T>(&json.to_uppercase()).unwrap(), *v);
+
+            let bin = bincode::serialize(v).unwrap();
+            // bincode prefixes the bytes with their length as a u64
+            assert_eq!(bin.len(), 8 + size);
+            assert_eq!(bincode::deserialize::<T>(&bin).unwrap(), *v);
+
+            // wrong lengths are rejected
+            let short = format!("\"{}\"", &json[1..json.len() - 3]);
+            assert!(serde_json::from_str::<T>(&short).is_err());
+            let long = format!("\"{}00\"", &json[1..json.len() - 1]);
+            assert!(serde_json::from_str::<T>(&long).is_err());
+            let odd = format!("\"{}0\"", &json[1..json.len() - 1]);
+            assert!(serde_json::from_str::<T>(&odd).is_err());
+            let bad = format!("\"x{}\"", &json[2..json.len() - 1]);
+            assert!(serde_json::from_str::<T>(&bad).is_err());
+            assert!(bincode::deserialize::<T>(
+                &bincode::serialize(&v.to_bytes().as_ref()[1..]).unwrap()
+            )
+            .is_err());
+        }
+    }
+
+    // Checks the field, group and prepared types of an engine whose base
+    // field elements take `$fq` bytes.
+    macro_rules! round_trip_engine {
+        ($engine:ident, $fq:expr, $rng:expr) => {{
+            use $engine::{Fq, Fq12, Fq2, Fq6, Fr, G1Affine, G1Prepared, G2Affine, G2Prepared};
+            use $engine::{G1, G2};
+
+            round_trip(&[Fr::zero(), Fr::one(), Fr::random($rng)], 32);
+            round_trip(&[Fq::zero(), Fq::one(), Fq::random($rng)], $fq);
+            round_trip(&[Fq2::zero(), Fq2::one(), Fq2::random($rng)], 2 * $fq);
+            round_trip(&[Fq6::zero(), Fq6::one(), Fq6::random($rng)], 6 * $fq);
+            round_trip(&[Fq12::zero(), Fq12::one(), Fq12::random($rng)], 12 * $fq);
+            round_trip(&[G1::zero(), G1::one(), G1::random($rng)], $fq);
+            round_trip(&[G2::zero(), G2::one(), G2::random($rng)], 2 * $fq);
+            round_trip(
+                &[
+                    G1Affine::zero(),
+                    G1Affine::one(),
+                    G1::random($rng).into_affine(),
+                ],
+                $fq,
+            );
+            round_trip(
+                &[
+                    G2Affine::zero(),
+                    G2Affine::one(),
+                    G2::random($rng).into_affine(),
+                ],
+                2 * $fq,
+            );
+
+            // prepared points are encoded as the points themselves
+            let p = G1::random($rng).into_affine();
+            let json = serde_json::to_string(&p.prepare()).unwrap();
+            assert_eq!(json, serde_json::to_string(&p).unwrap());
+            assert_eq!(serde_json::from_str::<G1Prepared>(&json).unwrap().0, p);
+            let bin = bincode::serialize(&p.prepare()).unwrap();
+            assert_eq!(bincode::deserialize::<G1Prepared>(&bin).unwrap().0, p);
+
+            for q in &[G2Affine::zero(), G2::random($rng).into_affine()] {
+                let json = serde_json::to_string(&q.prepare()).unwrap();
+                assert_eq!(json, serde_json::to_string(q).unwrap());
+                let q_recovered = serde_json::from_str::<G2Prepared>(&json).unwrap();
+                assert_eq!(q_recovered.point, *q);
+                assert_eq!(q_recovered.coeffs, q.prepare().coeffs);
+                let bin = bincode::serialize(&q.prepare()).unwrap();
+                assert_eq!(bincode::deserialize::<G2Prepared>(&bin).unwrap().point, *q);
+            }
+        }};
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        round_trip_engine!(bls12_381, 48, &mut rng);
+        round_trip_engine!(bls12_377, 48, &mut rng);
+        round_trip_engine!(bn256, 32, &mut rng);
+
+        round_trip(
+            &[
+                jubjub::Fs::zero(),
+                jubjub::Fs::one(),
+                jubjub::Fs::random(&mut rng),
+            ],
+            32,
+        );
+        round_trip(
+            &[
+                jubjub::AffinePoint::zero(),
+                jubjub::SubgroupPoint::one().into_affine(),
+                jubjub::SubgroupPoint::random(&mut rng).into_affine(),
+            ],
+            32,
+        );
+    }
+
+    #[test]
+    fn test_serde_invalid() {
+        // the modulus is not a canonical field element
+        let p = "\"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab\"";
+        assert!(serde_json::from_str::<Fq>(p).is_err());
+        let r = "\"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001\"";
+        assert!(serde_json::from_str::<Fr>(r).is_err());
+
+        // an uncompressed encoding is rejected, even with the right length
+        let mut bytes = G1::one().to_bytes().to_vec();
+        bytes[0] &= 0x7f;
+        assert!(bincode::deserialize::<G1>(&bincode::serialize(&bytes).unwrap()).is_err());
+
+        // x = 1 is not the x-coordinate of a point on the curve
+        let mut bytes = vec![0u8; 48];
+        bytes[0] = 0x80;
+        bytes[47] = 1;
+        assert!(bincode::deserialize::<G1>(&bincode::serialize(&bytes).unwrap()).is_err());
+
+        // x = 4 gives a point on the curve, but outside of the subgroup
+        bytes[47] = 4;
+        let mut encoded = G1Compressed::empty();
+        encoded.as_mut().copy_from_slice(&bytes);
+        assert!(encoded.into_affine_unchecked().is_ok());
+        assert!(bincode::deserialize::<G1Affine>(&bincode::serialize(&bytes).unwrap()).is_err());
+    }
+}
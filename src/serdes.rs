@@ -1,422 +1,370 @@
-use bls12_381::{self, *};
-use ff::{PrimeField, PrimeFieldRepr};
-use std::io::{Error, ErrorKind, Read, Result, Write};
+use bls12_381::*;
+use ff::{PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
 use CurveAffine;
 use CurveProjective;
 use EncodedPoint;
+use GroupDecodingError;
 type Compressed = bool;
 
+/// An error that may occur when serializing or deserializing.
+#[derive(Debug)]
+pub enum SerDesError {
+    /// Reading or writing failed, for instance because the input was too short.
+    Io(io::Error),
+    /// A group element could not be decoded.
+    Group(GroupDecodingError),
+    /// A field element was not canonical.
+    Field(PrimeFieldDecodingError),
+}
+
+impl Error for SerDesError {
+    fn description(&self) -> &str {
+        match *self {
+            SerDesError::Io(..) => "I/O error",
+            SerDesError::Group(..) => "group element could not be decoded",
+            SerDesError::Field(..) => "field element could not be decoded",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            SerDesError::Io(ref e) => Some(e),
+            SerDesError::Group(ref e) => Some(e),
+            SerDesError::Field(ref e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for SerDesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SerDesError::Io(ref e) => write!(f, "I/O error: {}", e),
+            SerDesError::Group(ref e) => write!(f, "group decoding error: {}", e),
+            SerDesError::Field(ref e) => write!(f, "field decoding error: {}", e),
+        }
+    }
+}
+
+impl From<io::Error> for SerDesError {
+    fn from(e: io::Error) -> SerDesError {
+        SerDesError::Io(e)
+    }
+}
+
+impl From<GroupDecodingError> for SerDesError {
+    fn from(e: GroupDecodingError) -> SerDesError {
+        SerDesError::Group(e)
+    }
+}
+
+impl From<PrimeFieldDecodingError> for SerDesError {
+    fn from(e: PrimeFieldDecodingError) -> SerDesError {
+        SerDesError::Field(e)
+    }
+}
+
 /// Serialization support for group elements.
 pub trait SerDes: Sized {
     /// Serialize a struct to a writer with a flag of compressness.
-    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()>;
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError>;
 
     /// Deserialize a struct; give an indicator if the element was compressed or not.
     /// Returns an error is the encoding does not match the indicator.
-    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self>;
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError>;
+}
+
+/// Deserialization of group elements from either encoding, without knowing in
+/// advance which one was used.
+pub trait AutoDeserialize: Sized {
+    /// Deserialize a struct, reading the compression flag from the first byte
+    /// and then consuming exactly the size of the compressed or uncompressed
+    /// encoding. Returns the element and whether it was compressed.
+    fn deserialize_auto<R: Read>(reader: &mut R) -> Result<(Self, Compressed), SerDesError>;
+}
+
+/// Reads a compressed or uncompressed point, depending on the first bit of
+/// the first byte. If `expected` is given, the encoding must match it.
+fn read_point<C, U, R>(
+    reader: &mut R,
+    expected: Option<Compressed>,
+) -> Result<(C::Affine, Compressed), SerDesError>
+where
+    C: EncodedPoint,
+    U: EncodedPoint<Affine = C::Affine>,
+    R: Read,
+{
+    let mut c_buf = C::empty();
+    reader.read_exact(c_buf.as_mut())?;
+
+    // first bit is 1 => compressed mode
+    // first bit is 0 => uncompressed mode
+    let compressed = (c_buf.as_ref()[0] & 0x80) == 0x80;
+    if expected.is_some() && expected != Some(compressed) {
+        return Err(GroupDecodingError::UnexpectedCompressionMode.into());
+    }
+
+    let p = if compressed {
+        c_buf.into_affine()?
+    } else {
+        // read the remaining bytes of the uncompressed encoding
+        let mut u_buf = U::empty();
+        u_buf.as_mut()[..C::size()].copy_from_slice(c_buf.as_ref());
+        reader.read_exact(&mut u_buf.as_mut()[C::size()..])?;
+        u_buf.into_affine()?
+    };
+    Ok((p, compressed))
+}
+
+fn write_point<C, U, W>(
+    writer: &mut W,
+    p: C::Affine,
+    compressed: Compressed,
+) -> Result<(), SerDesError>
+where
+    C: EncodedPoint,
+    U: EncodedPoint<Affine = C::Affine>,
+    W: Write,
+{
+    // convert element into an (un)compressed byte string
+    if compressed {
+        writer.write_all(C::from_affine(p).as_ref())?;
+    } else {
+        writer.write_all(U::from_affine(p).as_ref())?;
+    }
+    Ok(())
 }
 
 impl SerDes for Fr {
     /// The compressed parameter has no effect since Fr element will always be compressed.
-    fn serialize<W: Write>(&self, writer: &mut W, _compressed: Compressed) -> Result<()> {
-        self.into_repr().write_be(writer)
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        _compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        Ok(self.into_repr().write_be(writer)?)
     }
 
     /// The compressed parameter has no effect since Fr element will always be compressed.
-    fn deserialize<R: Read>(reader: &mut R, _compressed: Compressed) -> Result<Self> {
+    fn deserialize<R: Read>(reader: &mut R, _compressed: Compressed) -> Result<Self, SerDesError> {
         let mut r = FrRepr::default();
         r.read_be(reader)?;
-        match Fr::from_repr(r) {
-            Err(e) => Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => Ok(p),
-        }
+        Ok(Fr::from_repr(r)?)
     }
 }
 
-impl SerDes for Fq12 {
-    /// The compressed parameter has no effect since Fr element will always be compressed.
-    fn serialize<W: Write>(&self, writer: &mut W, _compressed: Compressed) -> Result<()> {
-        let mut buf: Vec<u8> = vec![];
+impl SerDes for Fq {
+    /// The compressed parameter has no effect since Fq element will always be compressed.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        _compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        Ok(self.into_repr().write_be(writer)?)
+    }
 
-        match self.c0.c0.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c0.c0.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c0.c1.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c0.c1.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c0.c2.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c0.c2.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c1.c0.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
+    /// The compressed parameter has no effect since Fq element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, _compressed: Compressed) -> Result<Self, SerDesError> {
+        let mut q = FqRepr::default();
+        q.read_be(reader)?;
+        Ok(Fq::from_repr(q)?)
+    }
+}
 
-        match self.c1.c0.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c1.c1.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c1.c1.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c1.c2.c0.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        match self.c1.c2.c1.into_repr().write_be(&mut buf) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(p) => p,
-        };
-        writer.write_all(&buf)?;
-        Ok(())
+impl SerDes for Fq2 {
+    /// Serializes c0 followed by c1.
+    /// The compressed parameter has no effect since Fq2 element will always be compressed.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.c0.serialize(writer, compressed)?;
+        self.c1.serialize(writer, compressed)
     }
 
-    /// The compressed parameter has no effect since Fr element will always be compressed.
-    fn deserialize<R: Read>(mut reader: &mut R, _compressed: Compressed) -> Result<Self> {
-        let mut q = FqRepr::default();
-        q.read_be(&mut reader)?;
-        let c000 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c001 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c010 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c011 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c020 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c021 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c100 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c101 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c110 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c111 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c120 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        q.read_be(&mut reader)?;
-        let c121 = match Fq::from_repr(q) {
-            Err(e) => return Err(Error::new(ErrorKind::Other, e)),
-            Ok(q) => q,
-        };
-        Ok(Fq12 {
-            c0: Fq6 {
-                c0: Fq2 { c0: c000, c1: c001 },
+    /// The compressed parameter has no effect since Fq2 element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        let c0 = Fq::deserialize(reader, compressed)?;
+        let c1 = Fq::deserialize(reader, compressed)?;
+        Ok(Fq2 { c0, c1 })
+    }
+}
 
-                c1: Fq2 { c0: c010, c1: c011 },
+impl SerDes for Fq6 {
+    /// Serializes c0, c1 and c2 in this order.
+    /// The compressed parameter has no effect since Fq6 element will always be compressed.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.c0.serialize(writer, compressed)?;
+        self.c1.serialize(writer, compressed)?;
+        self.c2.serialize(writer, compressed)
+    }
 
-                c2: Fq2 { c0: c020, c1: c021 },
-            },
-            c1: Fq6 {
-                c0: Fq2 { c0: c100, c1: c101 },
+    /// The compressed parameter has no effect since Fq6 element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        let c0 = Fq2::deserialize(reader, compressed)?;
+        let c1 = Fq2::deserialize(reader, compressed)?;
+        let c2 = Fq2::deserialize(reader, compressed)?;
+        Ok(Fq6 { c0, c1, c2 })
+    }
+}
 
-                c1: Fq2 { c0: c110, c1: c111 },
+impl SerDes for Fq12 {
+    /// Serializes c0 followed by c1.
+    /// The compressed parameter has no effect since Fq12 element will always be compressed.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.c0.serialize(writer, compressed)?;
+        self.c1.serialize(writer, compressed)
+    }
 
-                c2: Fq2 { c0: c120, c1: c121 },
-            },
-        })
+    /// The compressed parameter has no effect since Fq12 element will always be compressed.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        let c0 = Fq6::deserialize(reader, compressed)?;
+        let c1 = Fq6::deserialize(reader, compressed)?;
+        Ok(Fq12 { c0, c1 })
     }
 }
 
 impl SerDes for G1 {
     /// Convert a G1 point to a blob.
-    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
-        let t = self.into_affine();
-
-        // convert element into an (un)compressed byte string
-        let buf = {
-            if compressed {
-                let tmp = bls12_381::G1Compressed::from_affine(t);
-                tmp.as_ref().to_vec()
-            } else {
-                let tmp = bls12_381::G1Uncompressed::from_affine(t);
-                tmp.as_ref().to_vec()
-            }
-        };
-
-        // format the output
-        writer.write_all(&buf)?;
-        Ok(())
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.into_affine().serialize(writer, compressed)
     }
 
     /// Deserialize a G1 element from a blob.
     /// Returns an error if deserialization fails.
-    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
-        // read into buf of compressed size
-        let mut buf = vec![0u8; G1Compressed::size()];
-        reader.read_exact(&mut buf)?;
-
-        // check the first bit of buf[0] to decide if the point is compressed
-        // or not
-        // first bit is 1 => compressed mode
-        // first bit is 0 => uncompressed mode
-        if ((buf[0] & 0x80) == 0x80) != compressed {
-            return Err(Error::new(ErrorKind::InvalidData, "Invalid compressness"));
-        }
-
-        if compressed {
-            // convert the blob into a group element
-            let mut g_buf = G1Compressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p.into_projective(),
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        } else {
-            // read the next uncompressed - compressed size
-            let mut buf2 = vec![0u8; G1Uncompressed::size() - G1Compressed::size()];
-            reader.read_exact(&mut buf2)?;
-            // now buf holds the whole uncompressed bytes
-            buf.append(&mut buf2);
-            // convert the buf into a group element
-            let mut g_buf = G1Uncompressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p.into_projective(),
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        }
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        G1Affine::deserialize(reader, compressed).map(|p| p.into_projective())
     }
 }
 
 impl SerDes for G2 {
     /// Convert a G2 point to a blob.
-    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
-        let t = self.into_affine();
-        // convert element into an (un)compressed byte string
-        let buf = {
-            if compressed {
-                let tmp = bls12_381::G2Compressed::from_affine(t);
-                tmp.as_ref().to_vec()
-            } else {
-                let tmp = bls12_381::G2Uncompressed::from_affine(t);
-                tmp.as_ref().to_vec()
-            }
-        };
-
-        // format the output
-        writer.write_all(&buf)?;
-        Ok(())
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.into_affine().serialize(writer, compressed)
     }
 
     /// Deserialize a G2 element from a blob.
     /// Returns an error if deserialization fails.
-    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
-        // read into buf of compressed size
-        let mut buf = vec![0u8; G2Compressed::size()];
-        reader.read_exact(&mut buf)?;
-
-        // check the first bit of buf[0] to decide if the point is compressed
-        // or not
-        // first bit is 1 => compressed mode
-        // first bit is 0 => uncompressed mode
-        if ((buf[0] & 0x80) == 0x80) != compressed {
-            return Err(Error::new(ErrorKind::InvalidData, "Invalid compressness"));
-        }
-
-        if compressed {
-            // convert the buf into a group element
-            let mut g_buf = G2Compressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p.into_projective(),
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        } else {
-            // read the next uncompressed - compressed size
-            let mut buf2 = vec![0u8; G2Uncompressed::size() - G2Compressed::size()];
-            reader.read_exact(&mut buf2)?;
-            // now buf holds the whole uncompressed bytes
-            buf.append(&mut buf2);
-            // convert the buf into a group element
-            let mut g_buf = G2Uncompressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p.into_projective(),
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        }
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        G2Affine::deserialize(reader, compressed).map(|p| p.into_projective())
     }
 }
 
 impl SerDes for G1Affine {
     /// Convert a G1 point to a blob.
-    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
-        // convert element into an (un)compressed byte string
-        let buf = {
-            if compressed {
-                let tmp = bls12_381::G1Compressed::from_affine(*self);
-                tmp.as_ref().to_vec()
-            } else {
-                let tmp = bls12_381::G1Uncompressed::from_affine(*self);
-                tmp.as_ref().to_vec()
-            }
-        };
-
-        // format the output
-        writer.write_all(&buf)?;
-        Ok(())
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        write_point::<G1Compressed, G1Uncompressed, W>(writer, *self, compressed)
     }
 
     /// Deserialize a G1 element from a blob.
     /// Returns an error if deserialization fails.
-    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
-        // read into buf of compressed size
-        let mut buf = vec![0u8; G1Compressed::size()];
-        reader.read_exact(&mut buf)?;
-
-        // check the first bit of buf[0] to decide if the point is compressed
-        // or not
-        // first bit is 1 => compressed mode
-        // first bit is 0 => uncompressed mode
-        if ((buf[0] & 0x80) == 0x80) != compressed {
-            return Err(Error::new(ErrorKind::InvalidData, "Invalid compressness"));
-        }
-
-        if compressed {
-            // convert the blob into a group element
-            let mut g_buf = G1Compressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p,
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        } else {
-            // read the next uncompressed - compressed size
-            let mut buf2 = vec![0u8; G1Uncompressed::size() - G1Compressed::size()];
-            reader.read_exact(&mut buf2)?;
-            // now buf holds the whole uncompressed bytes
-            buf.append(&mut buf2);
-            // convert the buf into a group element
-            let mut g_buf = G1Uncompressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p,
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        }
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        read_point::<G1Compressed, G1Uncompressed, R>(reader, Some(compressed)).map(|(p, _)| p)
     }
 }
 
 impl SerDes for G2Affine {
     /// Convert a G2 point to a blob.
-    fn serialize<W: Write>(&self, writer: &mut W, compressed: Compressed) -> Result<()> {
-        // convert element into an (un)compressed byte string
-        let buf = {
-            if compressed {
-                let tmp = bls12_381::G2Compressed::from_affine(*self);
-                tmp.as_ref().to_vec()
-            } else {
-                let tmp = bls12_381::G2Uncompressed::from_affine(*self);
-                tmp.as_ref().to_vec()
-            }
-        };
-
-        // format the output
-        writer.write_all(&buf)?;
-        Ok(())
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        write_point::<G2Compressed, G2Uncompressed, W>(writer, *self, compressed)
     }
 
     /// Deserialize a G2 element from a blob.
     /// Returns an error if deserialization fails.
-    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self> {
-        // read into buf of compressed size
-        let mut buf = vec![0u8; G2Compressed::size()];
-        reader.read_exact(&mut buf)?;
-
-        // check the first bit of buf[0] to decide if the point is compressed
-        // or not
-        // first bit is 1 => compressed mode
-        // first bit is 0 => uncompressed mode
-        if ((buf[0] & 0x80) == 0x80) != compressed {
-            return Err(Error::new(ErrorKind::InvalidData, "Invalid compressness"));
-        }
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        read_point::<G2Compressed, G2Uncompressed, R>(reader, Some(compressed)).map(|(p, _)| p)
+    }
+}
 
-        if compressed {
-            // convert the buf into a group element
-            let mut g_buf = G2Compressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p,
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        } else {
-            // read the next uncompressed - compressed size
-            let mut buf2 = vec![0u8; G2Uncompressed::size() - G2Compressed::size()];
-            reader.read_exact(&mut buf2)?;
-            // now buf holds the whole uncompressed bytes
-            buf.append(&mut buf2);
-            // convert the buf into a group element
-            let mut g_buf = G2Uncompressed::empty();
-            g_buf.as_mut().copy_from_slice(&buf);
-            let g = match g_buf.into_affine() {
-                Ok(p) => p,
-                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
-            };
-            Ok(g)
-        }
+impl SerDes for G1Prepared {
+    /// Serializes the prepared point as a G1 point.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.0.serialize(writer, compressed)
+    }
+
+    /// Deserialize a G1 element from a blob, and prepares it.
+    /// Returns an error if deserialization fails.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        G1Affine::deserialize(reader, compressed).map(G1Prepared::from_affine)
+    }
+}
+
+impl SerDes for G2Prepared {
+    /// Serializes the prepared point as a G2 point.
+    fn serialize<W: Write>(
+        &self,
+        writer: &mut W,
+        compressed: Compressed,
+    ) -> Result<(), SerDesError> {
+        self.point.serialize(writer, compressed)
+    }
+
+    /// Deserialize a G2 element from a blob, and prepares it.
+    /// Returns an error if deserialization fails, or if the element is not
+    /// on the curve or not in the subgroup.
+    fn deserialize<R: Read>(reader: &mut R, compressed: Compressed) -> Result<Self, SerDesError> {
+        G2Affine::deserialize(reader, compressed).map(|q| q.prepare())
+    }
+}
+
+impl AutoDeserialize for G1Affine {
+    fn deserialize_auto<R: Read>(reader: &mut R) -> Result<(Self, Compressed), SerDesError> {
+        read_point::<G1Compressed, G1Uncompressed, R>(reader, None)
+    }
+}
+
+impl AutoDeserialize for G2Affine {
+    fn deserialize_auto<R: Read>(reader: &mut R) -> Result<(Self, Compressed), SerDesError> {
+        read_point::<G2Compressed, G2Uncompressed, R>(reader, None)
+    }
+}
+
+impl AutoDeserialize for G1 {
+    fn deserialize_auto<R: Read>(reader: &mut R) -> Result<(Self, Compressed), SerDesError> {
+        let (p, compressed) = G1Affine::deserialize_auto(reader)?;
+        Ok((p.into_projective(), compressed))
+    }
+}
+
+impl AutoDeserialize for G2 {
+    fn deserialize_auto<R: Read>(reader: &mut R) -> Result<(Self, Compressed), SerDesError> {
+        let (p, compressed) = G2Affine::deserialize_auto(reader)?;
+        Ok((p.into_projective(), compressed))
     }
 }
 
@@ -424,6 +372,7 @@ impl SerDes for G2Affine {
 mod serdes_test {
     use super::*;
     use rand_core::SeedableRng;
+    use std::vec::Vec;
     #[test]
     fn test_g1_serialization_rand() {
         let mut rng = rand_xorshift::XorShiftRng::from_seed([
@@ -664,6 +613,353 @@ mod serdes_test {
         assert_eq!(g2_rand, g2_rand_recover);
     }
 
+    #[test]
+    fn test_auto_deserialization() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let g1s = [
+            G1::zero(),
+            G1::one(),
+            G1::random(&mut rng),
+            G1::random(&mut rng),
+        ];
+        let g2s = [
+            G2::zero(),
+            G2::one(),
+            G2::random(&mut rng),
+            G2::random(&mut rng),
+        ];
+
+        // write a stream that mixes both encodings
+        let mut buf: Vec<u8> = vec![];
+        for (i, (g1, g2)) in g1s.iter().zip(g2s.iter()).enumerate() {
+            let compressed = i & 1 == 0;
+            g1.serialize(&mut buf, compressed).unwrap();
+            g2.serialize(&mut buf, !compressed).unwrap();
+            g1.into_affine().serialize(&mut buf, !compressed).unwrap();
+            g2.into_affine().serialize(&mut buf, compressed).unwrap();
+        }
+        assert_eq!(buf.len(), 4 * (48 + 96 + 96 + 192));
+
+        // and read it back without knowing the encodings
+        let mut reader = &buf[..];
+        for (i, (g1, g2)) in g1s.iter().zip(g2s.iter()).enumerate() {
+            let compressed = i & 1 == 0;
+            assert_eq!(
+                G1::deserialize_auto(&mut reader).unwrap(),
+                (*g1, compressed)
+            );
+            assert_eq!(
+                G2::deserialize_auto(&mut reader).unwrap(),
+                (*g2, !compressed)
+            );
+            assert_eq!(
+                G1Affine::deserialize_auto(&mut reader).unwrap(),
+                (g1.into_affine(), !compressed)
+            );
+            assert_eq!(
+                G2Affine::deserialize_auto(&mut reader).unwrap(),
+                (g2.into_affine(), compressed)
+            );
+        }
+        assert!(reader.is_empty());
+
+        // a truncated uncompressed encoding is an error
+        let mut buf: Vec<u8> = vec![];
+        G1::one().serialize(&mut buf, false).unwrap();
+        assert!(G1::deserialize_auto(&mut &buf[..95]).is_err());
+        let mut buf: Vec<u8> = vec![];
+        G2::one().serialize(&mut buf, false).unwrap();
+        assert!(G2::deserialize_auto(&mut &buf[..96]).is_err());
+    }
+
+    #[test]
+    fn test_serdes_errors() {
+        use ff::Field;
+        use std::io::ErrorKind;
+        use std::string::ToString;
+
+        // short reads
+        let mut buf: Vec<u8> = vec![];
+        G1::one().serialize(&mut buf, false).unwrap();
+        let err = G1::deserialize(&mut &buf[..50], false).unwrap_err();
+        assert!(matches!(err, SerDesError::Io(ref e) if e.kind() == ErrorKind::UnexpectedEof));
+        let err = Fr::deserialize(&mut &buf[..31], true).unwrap_err();
+        assert!(matches!(err, SerDesError::Io(ref e) if e.kind() == ErrorKind::UnexpectedEof));
+        assert!(err.source().is_some());
+
+        // short writes
+        let mut out = [0u8; 95];
+        let err = G2::one().serialize(&mut &mut out[..], true).unwrap_err();
+        assert!(matches!(err, SerDesError::Io(ref e) if e.kind() == ErrorKind::WriteZero));
+
+        // non-canonical field elements
+        let mut buf: Vec<u8> = vec![];
+        Fr::char().write_be(&mut buf).unwrap();
+        let err = Fr::deserialize(&mut &buf[..], true).unwrap_err();
+        assert!(matches!(
+            err,
+            SerDesError::Field(PrimeFieldDecodingError::NotInField(_))
+        ));
+        assert!(err.to_string().starts_with("field decoding error"));
+        let mut buf: Vec<u8> = vec![];
+        Fq12::one().serialize(&mut buf, true).unwrap();
+        Fq::char().write_be(&mut &mut buf[48 * 11..]).unwrap();
+        let err = Fq12::deserialize(&mut &buf[..], true).unwrap_err();
+        assert!(matches!(
+            err,
+            SerDesError::Field(PrimeFieldDecodingError::NotInField(_))
+        ));
+
+        // compression flag that does not match the requested mode
+        let mut buf: Vec<u8> = vec![];
+        G1::one().serialize(&mut buf, true).unwrap();
+        let err = G1::deserialize(&mut &buf[..], false).unwrap_err();
+        assert!(matches!(
+            err,
+            SerDesError::Group(GroupDecodingError::UnexpectedCompressionMode)
+        ));
+        assert!(err.to_string().starts_with("group decoding error"));
+        let mut buf: Vec<u8> = vec![];
+        G2::one().serialize(&mut buf, false).unwrap();
+        let err = G2Affine::deserialize(&mut &buf[..], true).unwrap_err();
+        assert!(matches!(
+            err,
+            SerDesError::Group(GroupDecodingError::UnexpectedCompressionMode)
+        ));
+
+        // compressed G1 encodings with the given x-coordinate
+        let g1_with_x = |x: FqRepr| {
+            let mut buf: Vec<u8> = vec![];
+            x.write_be(&mut buf).unwrap();
+            buf[0] |= 0x80;
+            G1Affine::deserialize(&mut &buf[..], true).unwrap_err()
+        };
+        // x is not a field element
+        assert!(matches!(
+            g1_with_x(Fq::char()),
+            SerDesError::Group(GroupDecodingError::CoordinateDecodingError(
+                "x coordinate",
+                PrimeFieldDecodingError::NotInField(_)
+            ))
+        ));
+        // x^3 + 4 is not a square
+        assert!(matches!(
+            g1_with_x(FqRepr::from(1)),
+            SerDesError::Group(GroupDecodingError::NotOnCurve)
+        ));
+        // (4, sqrt(68)) is on the curve but not in the subgroup
+        assert!(matches!(
+            g1_with_x(FqRepr::from(4)),
+            SerDesError::Group(GroupDecodingError::NotInSubgroup)
+        ));
+
+        // the point at infinity with nonzero coordinates
+        let mut buf: Vec<u8> = vec![];
+        G1::zero().serialize(&mut buf, true).unwrap();
+        buf[47] = 1;
+        assert!(matches!(
+            G1::deserialize(&mut &buf[..], true).unwrap_err(),
+            SerDesError::Group(GroupDecodingError::UnexpectedInformation)
+        ));
+    }
+
+    fn roundtrip<T: SerDes + PartialEq + ::std::fmt::Debug>(t: &T, len: usize) {
+        let mut buf: Vec<u8> = vec![];
+        assert!(t.serialize(&mut buf, true).is_ok());
+        assert_eq!(buf.len(), len, "length of blob is incorrect");
+        let recovered = T::deserialize(&mut buf[..].as_ref(), true).unwrap();
+        assert_eq!(*t, recovered);
+    }
+
+    #[test]
+    fn test_fq_fq2_fq6_serialization_rand() {
+        use ff::Field;
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for fq in &[Fq::zero(), Fq::one(), Fq::random(&mut rng)] {
+            roundtrip(fq, 48);
+        }
+        for fq2 in &[Fq2::zero(), Fq2::one(), Fq2::random(&mut rng)] {
+            roundtrip(fq2, 96);
+        }
+        for fq6 in &[Fq6::zero(), Fq6::one(), Fq6::random(&mut rng)] {
+            roundtrip(fq6, 288);
+        }
+
+        // the coefficients are written in order, in big-endian
+        let fq2 = Fq2 {
+            c0: Fq::from_repr(FqRepr::from(1)).unwrap(),
+            c1: Fq::from_repr(FqRepr::from(2)).unwrap(),
+        };
+        let mut buf: Vec<u8> = vec![];
+        fq2.serialize(&mut buf, true).unwrap();
+        assert_eq!((buf[47], buf[95]), (1, 2));
+
+        // non-canonical coefficients are rejected
+        let mut buf: Vec<u8> = vec![];
+        Fq6::one().serialize(&mut buf, true).unwrap();
+        Fq::char().write_be(&mut &mut buf[96 * 2 + 48..]).unwrap();
+        assert!(matches!(
+            Fq6::deserialize(&mut &buf[..], true).unwrap_err(),
+            SerDesError::Field(PrimeFieldDecodingError::NotInField(_))
+        ));
+    }
+
+    fn serialized<T: SerDes>(t: &T, compressed: Compressed) -> Vec<u8> {
+        let mut buf: Vec<u8> = vec![];
+        t.serialize(&mut buf, compressed).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_fixed_size_bytes() {
+        use ff::Field;
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // the encodings are those of `SerDes`, compressed for group elements
+        for fr in &[Fr::zero(), Fr::one(), Fr::random(&mut rng)] {
+            assert_eq!(fr.to_bytes()[..], serialized(fr, true)[..]);
+            assert_eq!(Fr::from_bytes(&fr.to_bytes()), Some(*fr));
+        }
+        for fq in &[Fq::zero(), Fq::one(), Fq::random(&mut rng)] {
+            assert_eq!(fq.to_bytes()[..], serialized(fq, true)[..]);
+            assert_eq!(Fq::from_bytes(&fq.to_bytes()), Some(*fq));
+        }
+        for fq2 in &[Fq2::zero(), Fq2::one(), Fq2::random(&mut rng)] {
+            assert_eq!(fq2.to_bytes()[..], serialized(fq2, true)[..]);
+            assert_eq!(Fq2::from_bytes(&fq2.to_bytes()), Some(*fq2));
+        }
+        for fq6 in &[Fq6::zero(), Fq6::one(), Fq6::random(&mut rng)] {
+            assert_eq!(fq6.to_bytes()[..], serialized(fq6, true)[..]);
+            assert_eq!(Fq6::from_bytes(&fq6.to_bytes()), Some(*fq6));
+        }
+        for fq12 in &[Fq12::zero(), Fq12::one(), Fq12::random(&mut rng)] {
+            assert_eq!(fq12.to_bytes()[..], serialized(fq12, true)[..]);
+            assert_eq!(Fq12::from_bytes(&fq12.to_bytes()), Some(*fq12));
+        }
+        for g1 in &[G1::zero(), G1::one(), G1::random(&mut rng)] {
+            let p = g1.into_affine();
+            assert_eq!(g1.to_bytes()[..], serialized(g1, true)[..]);
+            assert_eq!(g1.to_uncompressed()[..], serialized(g1, false)[..]);
+            assert_eq!(p.to_bytes(), g1.to_bytes());
+            assert_eq!(p.to_uncompressed(), g1.to_uncompressed());
+            assert_eq!(G1::from_bytes(&g1.to_bytes()), Some(*g1));
+            assert_eq!(G1::from_uncompressed(&g1.to_uncompressed()), Some(*g1));
+            assert_eq!(G1Affine::from_bytes(&p.to_bytes()), Some(p));
+            assert_eq!(G1Affine::from_uncompressed(&p.to_uncompressed()), Some(p));
+        }
+        for g2 in &[G2::zero(), G2::one(), G2::random(&mut rng)] {
+            let q = g2.into_affine();
+            assert_eq!(g2.to_bytes()[..], serialized(g2, true)[..]);
+            assert_eq!(g2.to_uncompressed()[..], serialized(g2, false)[..]);
+            assert_eq!(q.to_bytes(), g2.to_bytes());
+            assert_eq!(q.to_uncompressed(), g2.to_uncompressed());
+            assert_eq!(G2::from_bytes(&g2.to_bytes()), Some(*g2));
+            assert_eq!(G2::from_uncompressed(&g2.to_uncompressed()), Some(*g2));
+            assert_eq!(G2Affine::from_bytes(&q.to_bytes()), Some(q));
+            assert_eq!(G2Affine::from_uncompressed(&q.to_uncompressed()), Some(q));
+        }
+
+        // non-canonical field elements
+        let mut bytes = [0u8; 32];
+        Fr::char().write_be(&mut &mut bytes[..]).unwrap();
+        assert_eq!(Fr::from_bytes(&bytes), None);
+        let mut bytes = Fq2::one().to_bytes();
+        Fq::char().write_be(&mut &mut bytes[48..]).unwrap();
+        assert_eq!(Fq2::from_bytes(&bytes), None);
+
+        // invalid points
+        let mut bytes = [0u8; 48];
+        bytes[0] = 0x80;
+        bytes[47] = 1;
+        assert_eq!(G1Affine::from_bytes(&bytes), None);
+        bytes[47] = 4;
+        assert_eq!(G1::from_bytes(&bytes), None);
+        let mut bytes = G2::one().to_uncompressed();
+        bytes[191] ^= 1;
+        assert_eq!(G2Affine::from_uncompressed(&bytes), None);
+        // the compression flag must match
+        assert_eq!(G1::from_bytes(&[0u8; 48]), None);
+    }
+
+    #[test]
+    fn test_prepared_serialization_rand() {
+        use bls12_381::Bls12;
+        use Engine;
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let p = G1::random(&mut rng).into_affine();
+        let q = G2::random(&mut rng).into_affine();
+
+        // G1Prepared
+        let mut p_prepared = p.prepare();
+        for &(compressed, len) in &[(true, 48), (false, 96)] {
+            let mut buf: Vec<u8> = vec![];
+            p.prepare().serialize(&mut buf, compressed).unwrap();
+            assert_eq!(buf.len(), len, "length of blob is incorrect");
+            p_prepared = G1Prepared::deserialize(&mut &buf[..], compressed).unwrap();
+            assert_eq!(p_prepared.0, p);
+        }
+
+        // G2Prepared
+        let q_prepared = q.prepare();
+        for &(compressed, len) in &[(true, 96), (false, 192)] {
+            let mut buf: Vec<u8> = vec![];
+            q_prepared.serialize(&mut buf, compressed).unwrap();
+            assert_eq!(buf.len(), len, "length of blob is incorrect");
+            let q_recovered = G2Prepared::deserialize(&mut &buf[..], compressed).unwrap();
+            assert_eq!(q_recovered.point, q);
+            assert_eq!(q_recovered.coeffs, q_prepared.coeffs);
+            assert_eq!(
+                Bls12::miller_loop(&[(&p_prepared, &q_recovered)]),
+                Bls12::miller_loop(&[(&p.prepare(), &q_prepared)])
+            );
+
+            // a truncated encoding
+            let err = G2Prepared::deserialize(&mut &buf[..buf.len() - 1], compressed).unwrap_err();
+            assert!(matches!(err, SerDesError::Io(_)));
+        }
+
+        // the point at infinity
+        let mut buf: Vec<u8> = vec![];
+        G2Affine::zero()
+            .prepare()
+            .serialize(&mut buf, true)
+            .unwrap();
+        assert_eq!(buf, G2Affine::zero().to_bytes());
+        let q_recovered = G2Prepared::deserialize(&mut &buf[..], true).unwrap();
+        assert!(q_recovered.coeffs.is_empty());
+        assert!(q_recovered.is_zero());
+
+        // points that are not in G2 are rejected
+        let g2_with_x = |x: u8| {
+            let mut buf = [0u8; 96];
+            buf[0] = 0x80;
+            buf[95] = x;
+            G2Prepared::deserialize(&mut &buf[..], true).unwrap_err()
+        };
+        // x^3 + 4 (u + 1) is not a square
+        assert!(matches!(
+            g2_with_x(1),
+            SerDesError::Group(GroupDecodingError::NotOnCurve)
+        ));
+        // x = 2 gives a point on the curve, but not in the subgroup
+        assert!(matches!(
+            g2_with_x(2),
+            SerDesError::Group(GroupDecodingError::NotInSubgroup)
+        ));
+    }
+
     #[test]
     fn test_fr_serialization_rand() {
         use ff::Field;
@@ -0,0 +1,401 @@
+/*!
+ This module implements Shamir secret sharing over a prime field and
+ Lagrange recombination of shares, both in the field and "in the exponent"
+ (i.e., recombination of partial signatures in G1 or G2).
+
+ Shares are evaluations of a random `Polynomial` of degree `threshold - 1`
+ whose constant term is the secret. Share indices are nonzero `u64` values;
+ the index 0 is reserved for the secret itself.
+*/
+
+use alloc::vec::Vec;
+use batch::batch_inverse;
+use core::fmt;
+use ff::PrimeField;
+use polynomial::Polynomial;
+use rand_core::RngCore;
+use secret::wipe;
+#[cfg(feature = "std")]
+use std::error::Error;
+use zeroize::Zeroize;
+use CurveAffine;
+
+/// A share of a secret: the evaluation of the sharing polynomial at `index`.
+/// The value is zeroized when the share is dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share<F: PrimeField> {
+    pub index: u64,
+    pub value: F,
+}
+
+impl<F: PrimeField> Zeroize for Share<F> {
+    fn zeroize(&mut self) {
+        wipe(&mut self.value);
+    }
+}
+
+impl<F: PrimeField> Drop for Share<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// An error that may occur when sharing or recombining a secret.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SharingError {
+    /// The threshold was zero or larger than the number of shares.
+    InvalidThreshold,
+    /// A share index was zero.
+    ZeroIndex,
+    /// The same share index was used more than once.
+    DuplicateIndex(u64),
+    /// The number of indices did not match the number of values.
+    LengthMismatch,
+}
+
+#[cfg(feature = "std")]
+impl Error for SharingError {
+    fn description(&self) -> &str {
+        match *self {
+            SharingError::InvalidThreshold => "threshold is zero or exceeds the number of shares",
+            SharingError::ZeroIndex => "share index is zero",
+            SharingError::DuplicateIndex(_) => "share index is repeated",
+            SharingError::LengthMismatch => "number of indices and values differ",
+        }
+    }
+}
+
+impl fmt::Display for SharingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SharingError::DuplicateIndex(i) => write!(f, "share index {} is repeated", i),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Converts a share index into a field element.
+fn index_to_field<F: PrimeField>(index: u64) -> F {
+    // unwrap is safe: every u64 is smaller than the modulus of any field we use
+    F::from_repr(index.into()).unwrap()
+}
+
+/// Checks that the indices are nonzero and pairwise distinct.
+fn check_indices(indices: &[u64]) -> Result<(), SharingError> {
+    for (i, idx) in indices.iter().enumerate() {
+        if *idx == 0 {
+            return Err(SharingError::ZeroIndex);
+        }
+        if indices[..i].contains(idx) {
+            return Err(SharingError::DuplicateIndex(*idx));
+        }
+    }
+    Ok(())
+}
+
+/// Samples a random polynomial of degree `threshold - 1` whose constant term is `secret`.
+/// The polynomial is secret too; callers should `zeroize` it once the shares are computed.
+pub fn random_polynomial<F: PrimeField, R: RngCore>(
+    secret: &F,
+    threshold: usize,
+    rng: &mut R,
+) -> Result<Polynomial<F>, SharingError> {
+    if threshold == 0 {
+        return Err(SharingError::InvalidThreshold);
+    }
+    let mut coeffs = Vec::with_capacity(threshold);
+    coeffs.push(*secret);
+    for _ in 1..threshold {
+        coeffs.push(F::random(rng));
+    }
+    Ok(Polynomial::new(coeffs))
+}
+
+/// Computes one share for each of the supplied indices from the sharing polynomial `poly`.
+/// At least `poly.degree() + 1` indices are needed for the shares to determine the secret.
+pub fn shares_from_polynomial<F: PrimeField>(
+    poly: &Polynomial<F>,
+    indices: &[u64],
+) -> Result<Vec<Share<F>>, SharingError> {
+    if poly.coeffs().len() > indices.len() {
+        return Err(SharingError::InvalidThreshold);
+    }
+    check_indices(indices)?;
+    Ok(indices
+        .iter()
+        .map(|&index| Share {
+            index,
+            value: poly.evaluate(&index_to_field(index)),
+        })
+        .collect())
+}
+
+/// Splits `secret` into `num_shares` shares with indices `1..=num_shares`,
+/// any `threshold` of which suffice to recover the secret.
+pub fn split_secret<F: PrimeField, R: RngCore>(
+    secret: &F,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share<F>>, SharingError> {
+    if threshold > num_shares {
+        return Err(SharingError::InvalidThreshold);
+    }
+    let indices: Vec<u64> = (1..=num_shares as u64).collect();
+    let mut poly = random_polynomial(secret, threshold, rng)?;
+    let shares = shares_from_polynomial(&poly, &indices);
+    poly.zeroize();
+    shares
+}
+
+/// Computes the Lagrange coefficients for interpolating at zero from evaluations at `indices`:
+/// `lambda_i = prod_{j != i} x_j / (x_j - x_i)`.
+///
+/// All denominators are inverted together with a single field inversion. They depend
+/// only on the public indices, never on the secret or the shares, so the inversion
+/// need not run in constant time.
+pub fn lagrange_coefficients_at_zero<F: PrimeField>(
+    indices: &[u64],
+) -> Result<Vec<F>, SharingError> {
+    check_indices(indices)?;
+    let xs: Vec<F> = indices.iter().map(|&i| index_to_field(i)).collect();
+
+    // numerator = prod_j x_j; lambda_i = numerator / (x_i * prod_{j != i} (x_j - x_i))
+    let mut numerator = F::one();
+    for x in &xs {
+        numerator.mul_assign(x);
+    }
+    let mut denominators: Vec<F> = xs
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            let mut den = *xi;
+            for (j, xj) in xs.iter().enumerate() {
+                if i != j {
+                    let mut diff = *xj;
+                    diff.sub_assign(xi);
+                    den.mul_assign(&diff);
+                }
+            }
+            den
+        })
+        .collect();
+
+    // all denominators are nonzero since indices are distinct and nonzero
+    batch_inverse(&mut denominators);
+
+    for d in denominators.iter_mut() {
+        d.mul_assign(&numerator);
+    }
+    Ok(denominators)
+}
+
+/// Recovers the secret from (at least `threshold`) shares by Lagrange interpolation at zero.
+pub fn recover_secret<F: PrimeField>(shares: &[Share<F>]) -> Result<F, SharingError> {
+    if shares.is_empty() {
+        return Err(SharingError::InvalidThreshold);
+    }
+    let indices: Vec<u64> = shares.iter().map(|s| s.index).collect();
+    let lambdas = lagrange_coefficients_at_zero::<F>(&indices)?;
+    let mut res = F::zero();
+    for (s, l) in shares.iter().zip(lambdas.iter()) {
+        let mut tmp = s.value;
+        tmp.mul_assign(l);
+        res.add_assign(&tmp);
+        wipe(&mut tmp);
+    }
+    Ok(res)
+}
+
+/// Recombines partial results `partials[i] = s_i * P` computed by the holders of the shares
+/// with the given `indices` into `s * P`, as a multi-scalar multiplication.
+/// This is how partial BLS signatures in G1 or G2 are combined into a threshold signature.
+pub fn recombine<G: CurveAffine>(
+    indices: &[u64],
+    partials: &[G],
+) -> Result<G::Projective, SharingError> {
+    if indices.len() != partials.len() {
+        return Err(SharingError::LengthMismatch);
+    }
+    if indices.is_empty() {
+        return Err(SharingError::InvalidThreshold);
+    }
+    let lambdas = lagrange_coefficients_at_zero::<G::Scalar>(indices)?;
+    let scalars: Vec<[u64; 4]> = lambdas.iter().map(scalar_to_limbs).collect();
+    let scalar_refs: Vec<&[u64; 4]> = scalars.iter().collect();
+    Ok(G::sum_of_products(partials, &scalar_refs))
+}
+
+/// Converts a scalar into the little-endian limb array expected by `sum_of_products`,
+/// zero-extending representations with fewer than four limbs.
+///
+/// Panics if the representation has more than four limbs.
+pub(crate) fn scalar_to_limbs<F: PrimeField>(s: &F) -> [u64; 4] {
+    let repr = s.into_repr();
+    let limbs = repr.as_ref();
+    assert!(limbs.len() <= 4, "scalars must fit in four limbs");
+    let mut res = [0u64; 4];
+    res[..limbs.len()].copy_from_slice(limbs);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::{Fr, G1, G2};
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use CurveProjective;
+
+    #[test]
+    fn test_shamir_recover_secret() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for threshold in 1..6 {
+            let secret = Fr::random(&mut rng);
+            let shares = split_secret(&secret, threshold, 7, &mut rng).unwrap();
+            assert_eq!(shares.len(), 7);
+
+            // any `threshold` shares recover the secret
+            assert_eq!(recover_secret(&shares[..threshold]).unwrap(), secret);
+            assert_eq!(recover_secret(&shares[7 - threshold..]).unwrap(), secret);
+            assert_eq!(recover_secret(&shares).unwrap(), secret);
+
+            // fewer shares do not
+            if threshold > 1 {
+                assert_ne!(recover_secret(&shares[..threshold - 1]).unwrap(), secret);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shamir_zeroize() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let secret = Fr::random(&mut rng);
+        let mut poly = random_polynomial(&secret, 3, &mut rng).unwrap();
+        let mut shares = shares_from_polynomial(&poly, &[1, 2, 3]).unwrap();
+
+        poly.zeroize();
+        assert_eq!(poly, Polynomial::zero());
+        shares[0].zeroize();
+        assert_eq!(shares[0].index, 1);
+        assert!(shares[0].value.is_zero());
+        assert_ne!(recover_secret(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_shamir_errors() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let secret = Fr::random(&mut rng);
+        assert_eq!(
+            split_secret(&secret, 0, 3, &mut rng),
+            Err(SharingError::InvalidThreshold)
+        );
+        assert_eq!(
+            split_secret(&secret, 4, 3, &mut rng),
+            Err(SharingError::InvalidThreshold)
+        );
+        let poly = random_polynomial(&secret, 3, &mut rng).unwrap();
+        assert_eq!(
+            shares_from_polynomial(&poly, &[1, 2]),
+            Err(SharingError::InvalidThreshold)
+        );
+
+        // the zero secret is shared by the zero polynomial when the threshold is one
+        let shares = split_secret(&Fr::zero(), 1, 3, &mut rng).unwrap();
+        assert!(shares.iter().all(|s| s.value.is_zero()));
+        assert_eq!(recover_secret(&shares[1..2]).unwrap(), Fr::zero());
+        assert_eq!(
+            lagrange_coefficients_at_zero::<Fr>(&[1, 0, 2]),
+            Err(SharingError::ZeroIndex)
+        );
+        assert_eq!(
+            lagrange_coefficients_at_zero::<Fr>(&[3, 5, 3]),
+            Err(SharingError::DuplicateIndex(3))
+        );
+        assert_eq!(
+            recombine::<::bls12_381::G1Affine>(&[1, 2], &[G1::one().into_affine()]),
+            Err(SharingError::LengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_shamir_lagrange_sum() {
+        // the coefficients for interpolating at zero sum to one
+        let lambdas = lagrange_coefficients_at_zero::<Fr>(&[2, 7, 11, 1000]).unwrap();
+        let mut sum = Fr::zero();
+        for l in &lambdas {
+            sum.add_assign(l);
+        }
+        assert_eq!(sum, Fr::one());
+    }
+
+    #[test]
+    fn test_scalar_to_limbs() {
+        use ff::{PrimeFieldDecodingError, PrimeFieldRepr};
+
+        // a field with two-limb representations, modulo 2^127 - 1
+        #[derive(PrimeField, Zeroize)]
+        #[PrimeFieldModulus = "170141183460469231731687303715884105727"]
+        #[PrimeFieldGenerator = "3"]
+        struct F127(F127Repr);
+
+        let mut x = F127::one();
+        x.negate();
+        assert_eq!(
+            scalar_to_limbs(&x),
+            [0xffff_ffff_ffff_fffe, 0x7fff_ffff_ffff_ffff, 0, 0]
+        );
+
+        let mut y = Fr::one();
+        y.negate();
+        assert_eq!(scalar_to_limbs(&y), y.into_repr().0);
+    }
+
+    #[test]
+    fn test_shamir_recombine_in_exponent() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let secret = Fr::random(&mut rng);
+        let poly = random_polynomial(&secret, 3, &mut rng).unwrap();
+        let shares = shares_from_polynomial(&poly, &[4, 9, 2, 17]).unwrap();
+        let subset = [shares[0].clone(), shares[2].clone(), shares[3].clone()];
+        let indices: Vec<u64> = subset.iter().map(|s| s.index).collect();
+
+        let h1 = G1::random(&mut rng);
+        let mut expect1 = h1;
+        expect1.mul_assign(secret);
+        let partials1: Vec<_> = subset
+            .iter()
+            .map(|s| {
+                let mut p = h1;
+                p.mul_assign(s.value);
+                p.into_affine()
+            })
+            .collect();
+        assert_eq!(recombine(&indices, &partials1).unwrap(), expect1);
+
+        let h2 = G2::random(&mut rng);
+        let mut expect2 = h2;
+        expect2.mul_assign(secret);
+        let partials2: Vec<_> = subset
+            .iter()
+            .map(|s| {
+                let mut p = h2;
+                p.mul_assign(s.value);
+                p.into_affine()
+            })
+            .collect();
+        assert_eq!(recombine(&indices, &partials2).unwrap(), expect2);
+    }
+}
@@ -2,8 +2,8 @@
 Signum trait: sgn0 for field elements
 */
 
+use core::ops::BitXor;
 use ff::Field;
-use std::ops::BitXor;
 
 /// Result of Sgn0
 #[derive(Debug, PartialEq, Eq)]
@@ -1,6 +1,7 @@
 use crate::bls12_381::*;
 use ff::{BitIterator, Field, PrimeField};
 use rand_core::SeedableRng;
+use std::vec::Vec;
 use {CurveAffine, CurveProjective, EncodedPoint};
 
 pub fn curve_tests<G: CurveProjective>() {
@@ -0,0 +1,51 @@
+use hash_to_curve::HashToCurve;
+use hash_to_field::{hash_to_field, ExpandMsgXmd, FromRO};
+use osswu_map::{IsogenyMap, OSSWUMap};
+use std::vec::Vec;
+use CurveAffine;
+
+/// A hash_to_curve test vector in the format of appendix J of RFC 9380, with
+/// field elements given as their `to_bytes` encodings and points as their
+/// uncompressed encodings.
+#[derive(Debug)]
+pub struct Vector {
+    pub msg: &'static str,
+    pub u0: &'static str,
+    pub u1: &'static str,
+    pub q0: &'static str,
+    pub q1: &'static str,
+    pub p: &'static str,
+}
+
+pub fn from_hex(s: &str) -> Vec<u8> {
+    ::encoding_macros::from_hex(s).unwrap()
+}
+
+/// Checks the intermediate and final values of `hash_to_curve` with
+/// expand_message_xmd and SHA-256 against known answers.
+pub fn hash_to_curve_tests<PtT>(dst: &[u8], vectors: &[Vector], to_bytes: fn(&PtT::Base) -> Vec<u8>)
+where
+    PtT: OSSWUMap + IsogenyMap + HashToCurve<ExpandMsgXmd<sha2::Sha256>>,
+    PtT::Base: FromRO,
+{
+    for v in vectors {
+        let u = hash_to_field::<PtT::Base, ExpandMsgXmd<sha2::Sha256>>(v.msg.as_bytes(), dst, 2);
+        assert_eq!(to_bytes(&u[0]), from_hex(v.u0));
+        assert_eq!(to_bytes(&u[1]), from_hex(v.u1));
+
+        for (u, q) in u.iter().zip(&[v.q0, v.q1]) {
+            let mut q_u = PtT::osswu_map(u);
+            q_u.isogeny_map();
+            assert_eq!(
+                q_u.into_affine().into_uncompressed().as_ref(),
+                &from_hex(q)[..]
+            );
+        }
+
+        let p = <PtT as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(v.msg, dst);
+        assert_eq!(
+            p.into_affine().into_uncompressed().as_ref(),
+            &from_hex(v.p)[..]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod curve;
 pub mod engine;
 pub mod field;
+pub mod hash_to_curve;
 pub mod repr;
@@ -2,12 +2,16 @@ use ff::{PrimeField, PrimeFieldRepr};
 use rand_core::SeedableRng;
 
 pub fn random_repr_tests<F: PrimeField, R: PrimeFieldRepr>() {
+    #[cfg(feature = "std")]
     random_encoding_tests::<F, R>();
     random_shl_tests::<F>();
     random_shr_tests::<F>();
 }
 
+#[cfg(feature = "std")]
 fn random_encoding_tests<F: PrimeField, R: PrimeFieldRepr>() {
+    use std::vec::Vec;
+
     let mut rng = rand_xorshift::XorShiftRng::from_seed([
         0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
         0xe5,
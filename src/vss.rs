@@ -0,0 +1,237 @@
+/*!
+ This module implements verifiable secret sharing on top of the Shamir
+ sharing in `shamir`: Feldman commitments `C_k = a_k * G` and Pedersen
+ commitments `C_k = a_k * G + b_k * H` to the coefficients of the sharing
+ polynomial, and verification of individual shares against them.
+
+ `G` is the fixed generator `CurveAffine::one()` of G1 or G2; the Pedersen
+ generator `H` is derived with `hash_to_curve` so that its discrete log with
+ respect to `G` is unknown.
+*/
+
+use alloc::vec::Vec;
+use core::cmp;
+use ff::{Field, PrimeField};
+use hash_to_curve::HashToCurve;
+use hash_to_field::ExpandMsg;
+use polynomial::Polynomial;
+use shamir::{scalar_to_limbs, Share};
+use {CurveAffine, CurveProjective};
+
+/// Returns the powers `1, x, x^2, ..., x^(n-1)` of the share index `x`.
+fn index_powers<F: PrimeField>(index: u64, n: usize) -> Vec<F> {
+    // unwrap is safe: every u64 is smaller than the modulus of any field we use
+    let x = F::from_repr(index.into()).unwrap();
+    let mut res = Vec::with_capacity(n);
+    let mut cur = F::one();
+    for _ in 0..n {
+        res.push(cur);
+        cur.mul_assign(&x);
+    }
+    res
+}
+
+/// Returns the coefficient of degree `k` of `poly`, which is zero beyond its degree.
+fn coefficient<F: PrimeField>(poly: &Polynomial<F>, k: usize) -> F {
+    poly.coeffs().get(k).cloned().unwrap_or_else(F::zero)
+}
+
+/// Checks that `sum_i scalars[i] * bases[i]` is the identity, as a single multi-scalar multiplication.
+fn msm_is_zero<G: CurveAffine>(bases: &[G], scalars: &[G::Scalar]) -> bool {
+    let limbs: Vec<[u64; 4]> = scalars.iter().map(scalar_to_limbs).collect();
+    let limb_refs: Vec<&[u64; 4]> = limbs.iter().collect();
+    G::sum_of_products(bases, &limb_refs).is_zero()
+}
+
+/// Derives the second Pedersen generator `H` for the group of `G` by hashing `dst`
+/// to the curve, so that nobody knows `log_G(H)`.
+pub fn pedersen_generator<G, X>(dst: &[u8]) -> G
+where
+    G: CurveAffine,
+    G::Projective: HashToCurve<X>,
+    X: ExpandMsg,
+{
+    <G::Projective as HashToCurve<X>>::hash_to_curve(b"pairing-plus pedersen generator", dst)
+        .into_affine()
+}
+
+/// Feldman commitments to the coefficients of a sharing polynomial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeldmanCommitment<G: CurveAffine> {
+    /// `commitments[k] = a_k * G`, where `a_k` is the coefficient of degree `k`.
+    pub commitments: Vec<G>,
+}
+
+impl<G: CurveAffine> FeldmanCommitment<G> {
+    /// Commits to the coefficients of the sharing polynomial `poly`. The zero polynomial
+    /// gets a single commitment, to its constant term.
+    pub fn new(poly: &Polynomial<G::Scalar>) -> Self {
+        let mut commitments: Vec<G::Projective> = (0..cmp::max(poly.coeffs().len(), 1))
+            .map(|k| {
+                let mut tmp = G::Projective::one();
+                tmp.mul_assign(coefficient(poly, k));
+                tmp
+            })
+            .collect();
+        G::Projective::batch_normalization(&mut commitments);
+        FeldmanCommitment {
+            commitments: commitments.iter().map(|c| c.into_affine()).collect(),
+        }
+    }
+
+    /// Returns the commitment to the secret, `a_0 * G`, i.e., the group public key.
+    pub fn secret_commitment(&self) -> G {
+        self.commitments[0]
+    }
+
+    /// Returns the commitment to the share with the given index, `sum_k index^k * C_k`.
+    pub fn share_commitment(&self, index: u64) -> G::Projective {
+        let powers = index_powers::<G::Scalar>(index, self.commitments.len());
+        let limbs: Vec<[u64; 4]> = powers.iter().map(scalar_to_limbs).collect();
+        let limb_refs: Vec<&[u64; 4]> = limbs.iter().collect();
+        G::sum_of_products(&self.commitments, &limb_refs)
+    }
+
+    /// Verifies that `share.value * G == sum_k share.index^k * C_k`.
+    pub fn verify(&self, share: &Share<G::Scalar>) -> bool {
+        if share.index == 0 || self.commitments.is_empty() {
+            return false;
+        }
+        let mut bases = self.commitments.clone();
+        let mut scalars = index_powers::<G::Scalar>(share.index, bases.len());
+        let mut neg_s = share.value;
+        neg_s.negate();
+        bases.push(G::one());
+        scalars.push(neg_s);
+        msm_is_zero(&bases, &scalars)
+    }
+}
+
+/// Pedersen commitments to the coefficients of a sharing polynomial and a blinding polynomial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PedersenCommitment<G: CurveAffine> {
+    /// The second generator `H`.
+    pub h: G,
+    /// `commitments[k] = a_k * G + b_k * H`, where `a_k` and `b_k` are the coefficients
+    /// of degree `k` of the sharing and blinding polynomials.
+    pub commitments: Vec<G>,
+}
+
+impl<G: CurveAffine> PedersenCommitment<G> {
+    /// Commits to the coefficients of the sharing polynomial `poly` using those of the
+    /// blinding polynomial `blinding`. There is one commitment per coefficient of the
+    /// polynomial of higher degree, and at least one.
+    pub fn new(poly: &Polynomial<G::Scalar>, blinding: &Polynomial<G::Scalar>, h: &G) -> Self {
+        let len = cmp::max(cmp::max(poly.coeffs().len(), blinding.coeffs().len()), 1);
+        let mut commitments: Vec<G::Projective> = (0..len)
+            .map(|k| {
+                let mut tmp = G::Projective::one();
+                tmp.mul_assign(coefficient(poly, k));
+                tmp.add_assign(&h.mul(coefficient(blinding, k)));
+                tmp
+            })
+            .collect();
+        G::Projective::batch_normalization(&mut commitments);
+        PedersenCommitment {
+            h: *h,
+            commitments: commitments.iter().map(|c| c.into_affine()).collect(),
+        }
+    }
+
+    /// Verifies that `share.value * G + blinding.value * H == sum_k index^k * C_k`.
+    pub fn verify(&self, share: &Share<G::Scalar>, blinding: &Share<G::Scalar>) -> bool {
+        if share.index == 0 || share.index != blinding.index || self.commitments.is_empty() {
+            return false;
+        }
+        let mut bases = self.commitments.clone();
+        let mut scalars = index_powers::<G::Scalar>(share.index, bases.len());
+        let mut neg_s = share.value;
+        neg_s.negate();
+        let mut neg_t = blinding.value;
+        neg_t.negate();
+        bases.push(G::one());
+        scalars.push(neg_s);
+        bases.push(self.h);
+        scalars.push(neg_t);
+        msm_is_zero(&bases, &scalars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::{Fr, G1Affine, G2Affine};
+    use hash_to_field::ExpandMsgXmd;
+    use rand_core::SeedableRng;
+    use shamir::{random_polynomial, shares_from_polynomial};
+
+    fn feldman_test<G: CurveAffine<Scalar = Fr>>() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let secret = Fr::random(&mut rng);
+        let poly = random_polynomial(&secret, 3, &mut rng).unwrap();
+        let shares = shares_from_polynomial(&poly, &[1, 2, 3, 4, 5]).unwrap();
+        let comm = FeldmanCommitment::<G>::new(&poly);
+
+        assert_eq!(comm.secret_commitment(), G::one().mul(secret).into_affine());
+        for s in &shares {
+            assert!(comm.verify(s));
+            assert_eq!(comm.share_commitment(s.index), G::one().mul(s.value));
+
+            let mut bad = s.clone();
+            bad.value.add_assign(&Fr::one());
+            assert!(!comm.verify(&bad));
+            bad = s.clone();
+            bad.index += 1;
+            assert!(!comm.verify(&bad));
+        }
+    }
+
+    #[test]
+    fn test_feldman_vss() {
+        feldman_test::<G1Affine>();
+        feldman_test::<G2Affine>();
+    }
+
+    #[test]
+    fn test_pedersen_vss() {
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let h = pedersen_generator::<G1Affine, ExpandMsgXmd<sha2::Sha256>>(b"test dst");
+        assert!(!h.is_zero());
+        assert_ne!(h, G1Affine::one());
+
+        let secret = Fr::random(&mut rng);
+        let poly = random_polynomial(&secret, 4, &mut rng).unwrap();
+        let blinding = random_polynomial(&Fr::random(&mut rng), 4, &mut rng).unwrap();
+        let indices = [3, 5, 7, 11, 13];
+        let shares = shares_from_polynomial(&poly, &indices).unwrap();
+        let bshares = shares_from_polynomial(&blinding, &indices).unwrap();
+        let comm = PedersenCommitment::new(&poly, &blinding, &h);
+        assert_eq!(comm.commitments.len(), 4);
+
+        for (s, t) in shares.iter().zip(bshares.iter()) {
+            assert!(comm.verify(s, t));
+
+            let mut bad = t.clone();
+            bad.value.add_assign(&Fr::one());
+            assert!(!comm.verify(s, &bad));
+        }
+        assert!(!comm.verify(&shares[0], &bshares[1]));
+
+        let h2 = pedersen_generator::<G2Affine, ExpandMsgXmd<sha2::Sha256>>(b"test dst");
+        let comm2 = PedersenCommitment::new(&poly, &blinding, &h2);
+        assert!(comm2.verify(&shares[2], &bshares[2]));
+
+        // a blinding polynomial of lower degree is padded with zero coefficients
+        let low = random_polynomial(&Fr::random(&mut rng), 2, &mut rng).unwrap();
+        let lshares = shares_from_polynomial(&low, &indices).unwrap();
+        let comm3 = PedersenCommitment::new(&poly, &low, &h);
+        assert_eq!(comm3.commitments.len(), 4);
+        assert!(comm3.verify(&shares[1], &lshares[1]));
+    }
+}
@@ -1,4 +1,6 @@
 use super::{CurveProjective, PrimeField, PrimeFieldRepr};
+use alloc::vec::Vec;
+use zeroize::{Zeroize, Zeroizing};
 
 /// Replaces the contents of `table` with a w-NAF window table for the given window size.
 pub(crate) fn wnaf_table<G: CurveProjective>(table: &mut Vec<G>, mut base: G, window: usize) {
@@ -15,8 +17,12 @@ pub(crate) fn wnaf_table<G: CurveProjective>(table: &mut Vec<G>, mut base: G, wi
 }
 
 /// Replaces the contents of `wnaf` with the w-NAF representation of a scalar.
+///
+/// The previous digits are wiped, and enough space is reserved up front that the
+/// digits are never left behind in a reallocated buffer. `c` is consumed down to zero.
 pub(crate) fn wnaf_form<S: PrimeFieldRepr>(wnaf: &mut Vec<i64>, mut c: S, window: usize) {
-    wnaf.truncate(0);
+    wnaf.zeroize();
+    wnaf.reserve(c.num_bits() as usize + 1);
 
     while !c.is_zero() {
         let mut u;
@@ -71,6 +77,8 @@ pub(crate) fn wnaf_exp<G: CurveProjective>(table: &[G], wnaf: &[i64]) -> G {
 }
 
 /// A "w-ary non-adjacent form" exponentiation context.
+///
+/// The digits of the scalar are secret; storage owned by the context is zeroized on drop.
 #[derive(Debug)]
 pub struct Wnaf<W, B, S> {
     base: B,
@@ -78,12 +86,12 @@ pub struct Wnaf<W, B, S> {
     window_size: W,
 }
 
-impl<G: CurveProjective> Wnaf<(), Vec<G>, Vec<i64>> {
+impl<G: CurveProjective> Wnaf<(), Vec<G>, Zeroizing<Vec<i64>>> {
     /// Construct a new wNAF context without allocating.
     pub fn new() -> Self {
         Wnaf {
             base: vec![],
-            scalar: vec![],
+            scalar: Zeroizing::new(vec![]),
             window_size: (),
         }
     }
@@ -101,7 +109,7 @@ impl<G: CurveProjective> Wnaf<(), Vec<G>, Vec<i64>> {
         // but mutably borrows the scalar storage location.
         Wnaf {
             base: &self.base[..],
-            scalar: &mut self.scalar,
+            scalar: &mut *self.scalar,
             window_size,
         }
     }
@@ -131,10 +139,10 @@ impl<G: CurveProjective> Wnaf<(), Vec<G>, Vec<i64>> {
 impl<'a, G: CurveProjective> Wnaf<usize, &'a [G], &'a mut Vec<i64>> {
     /// Constructs new space for the scalar representation while borrowing
     /// the computed window table, for sending the window table across threads.
-    pub fn shared(&self) -> Wnaf<usize, &'a [G], Vec<i64>> {
+    pub fn shared(&self) -> Wnaf<usize, &'a [G], Zeroizing<Vec<i64>>> {
         Wnaf {
             base: self.base,
-            scalar: vec![],
+            scalar: Zeroizing::new(vec![]),
             window_size: self.window_size,
         }
     }